@@ -1,5 +1,5 @@
 use gtk::prelude::*;
-use gtk::{gio, glib};
+use gtk::{gdk, gio, glib};
 use relm4::gtk;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -7,11 +7,14 @@ use std::rc::Rc;
 use std::path::Path;
 use std::fmt;
 use webkit6::prelude::WebViewExt;
+use serde::{Deserialize, Serialize};
 
 use crate::core::{
     Base, BaseQueryEngine, BaseView, ColumnConfig, Filter, FilterGroup, 
     FilterOperator, GroupedRecord, NoteMetadata, NoteWithProperties, NotesDatabase, PropertyValue, 
     SortConfig, SortDirection, SourceType, ViewType, HtmlRenderer, PreviewTheme,
+    SearchOptions, FilterNode, SortKey, NullOrder, compare_sort_keys,
+    export_view, ExportFormat, export_h_entry, export_h_feed,
 };
 use crate::graph_view::GraphView;
 use crate::i18n::{I18n, Language};
@@ -23,16 +26,41 @@ pub struct BaseTableWidget {
     table_webview: webkit6::WebView,  // WebView para la tabla HTML
     column_view: gtk::ColumnView,  // ColumnView (mantenido para lógica de columnas)
     list_store: gio::ListStore,  // ListStore para datos
+    selection: gtk::MultiSelection,  // Modelo de selección múltiple para acciones en lote
     filter_bar: gtk::Box,
     filters_container: gtk::Box,
+    /// Entrada de búsqueda full-text incremental sobre el contenido de las notas
+    search_entry: gtk::SearchEntry,
+    /// Modificadores de la búsqueda (sensible a mayúsculas / palabra / regex)
+    search_case_btn: gtk::ToggleButton,
+    search_word_btn: gtk::ToggleButton,
+    search_regex_btn: gtk::ToggleButton,
     view_tabs: gtk::Box,
     status_bar: gtk::Box,
     graph_view: GraphView,  // Vista de grafo de relaciones
     graph_toggle: gtk::ToggleButton,  // Botón para alternar vista
     sort_btn: gtk::MenuButton,  // Botón de ordenamiento
     columns_btn: gtk::MenuButton,  // Botón de columnas
+    group_by_btn: gtk::MenuButton,  // Botón de agrupación (group by)
+    selection_btn: gtk::MenuButton,  // Botón de acciones de selección (lote)
     source_type_btn: gtk::MenuButton,  // Botón para cambiar modo (Notes/GroupedRecords)
-    
+    theme_btn: gtk::MenuButton,  // Botón para elegir el tema (claro/oscuro/sistema)
+    export_btn: gtk::MenuButton,  // Botón para exportar la vista actual a CSV/Markdown
+
+    /// CSS provider del tema actual, reemplazado cada vez que cambia el tema.
+    theme_css_provider: Rc<RefCell<Option<gtk::CssProvider>>>,
+
+    /// Claves de grupo colapsadas (persisten el plegado entre re-renderizados)
+    collapsed_groups: Rc<RefCell<std::collections::HashSet<String>>>,
+
+    /// Consulta de búsqueda full-text de la barra de la Base.
+    search_query: Rc<RefCell<String>>,
+    /// Modificadores de la búsqueda (case/word/regex).
+    search_options: Rc<RefCell<SearchOptions>>,
+    /// `Regex` compilado cacheado para la consulta/opciones actuales. `Err`
+    /// indica un patrón inválido (se muestran cero resultados con aviso).
+    search_regex: Rc<RefCell<Option<Result<regex::Regex, String>>>>,
+
     /// Internacionalización
     i18n: Rc<RefCell<I18n>>,
     
@@ -45,11 +73,16 @@ pub struct BaseTableWidget {
     /// Notas filtradas (mostradas)
     notes: Rc<RefCell<Vec<NoteWithProperties>>>,
     
-    /// Filtros activos (adicionales a los de la vista)
+    /// Filtros activos (adicionales a los de la vista). Espejo plano de las
+    /// hojas de `filter_root`, conservado para los chips y el alta rápida.
     active_filters: Rc<RefCell<Vec<Filter>>>,
-    
+
+    /// Árbol de filtros booleano de la vista (AND/OR/NOT anidados). Es la
+    /// fuente de verdad para el filtrado; `active_filters` es su espejo plano.
+    filter_root: Rc<RefCell<FilterNode>>,
+
     /// Ordenamiento actual
-    current_sort: Rc<RefCell<Option<SortConfig>>>,
+    current_sort: Rc<RefCell<Vec<SortConfig>>>,
     
     /// Propiedades disponibles
     available_properties: Rc<RefCell<Vec<String>>>,
@@ -76,6 +109,10 @@ pub struct BaseTableWidget {
     
     /// Callback cuando se hace clic en la vista (para cerrar sidebar)
     on_view_clicked: Rc<RefCell<Option<Box<dyn Fn()>>>>,
+
+    /// Callback invocado cada vez que cambia la `MultiSelection`, con los `id`
+    /// (como texto) de las notas actualmente seleccionadas.
+    on_selection_changed: Rc<RefCell<Option<Box<dyn Fn(&[String])>>>>,
 }
 
 impl fmt::Debug for BaseTableWidget {
@@ -96,7 +133,25 @@ impl BaseTableWidget {
             .build();
 
         // Barra de filtros (arriba)
-        let (filter_bar, filters_container, sort_btn, columns_btn, graph_toggle, source_type_btn) = Self::create_filter_bar(&i18n.borrow());
+        let (filter_bar, filters_container, search_entry, search_case_btn, search_word_btn, search_regex_btn, sort_btn, columns_btn, group_by_btn, graph_toggle, source_type_btn, theme_btn) = Self::create_filter_bar(&i18n.borrow());
+
+        // Botón de acciones de selección en lote (insertado tras el de columnas).
+        let selection_btn = gtk::MenuButton::builder()
+            .icon_name("object-select-symbolic")
+            .tooltip_text(&i18n.borrow().t("base_selection"))
+            .css_classes(["flat"])
+            .build();
+        filter_bar.insert_child_after(&selection_btn, Some(&group_by_btn));
+
+        // Botón para exportar la vista actual (filtrada y ordenada) a CSV o
+        // Markdown, insertado tras el de tema.
+        let export_btn = gtk::MenuButton::builder()
+            .icon_name("document-save-symbolic")
+            .tooltip_text(&i18n.borrow().t("base_export_view"))
+            .css_classes(["flat"])
+            .build();
+        filter_bar.insert_child_after(&export_btn, Some(&theme_btn));
+
         container.append(&filter_bar);
 
         // Tabs de vistas
@@ -129,13 +184,51 @@ impl BaseTableWidget {
             settings.set_enable_smooth_scrolling(true);
         }
         
+        // Modelo de datos y selección (la MultiSelection se necesita ya para el
+        // handler de selección de filas del WebView).
+        let list_store = gio::ListStore::new::<glib::BoxedAnyObject>();
+        // MultiSelection para poder operar sobre varias notas a la vez (borrar,
+        // mover, fijar propiedad). La selección se dirige desde el WebView.
+        let selection_model = gtk::MultiSelection::new(Some(list_store.clone()));
+
         // Configurar UserContentManager para recibir mensajes JS→Rust
         let on_note_selected: Rc<RefCell<Option<Box<dyn Fn(&str)>>>> = Rc::new(RefCell::new(None));
         let on_view_clicked: Rc<RefCell<Option<Box<dyn Fn()>>>> = Rc::new(RefCell::new(None));
-        
+        let collapsed_groups: Rc<RefCell<std::collections::HashSet<String>>> =
+            Rc::new(RefCell::new(std::collections::HashSet::new()));
+
         if let Some(content_manager) = table_webview.user_content_manager() {
             content_manager.register_script_message_handler("noteClick", None);
-            
+
+            // Plegado/desplegado de grupos: el JS publica la clave del grupo y
+            // aquí solo actualizamos el conjunto colapsado para que el estado
+            // sobreviva a los re-renderizados (sin recargar la tabla).
+            content_manager.register_script_message_handler("groupToggle", None);
+            let collapsed_clone = collapsed_groups.clone();
+            content_manager.connect_script_message_received(Some("groupToggle"), move |_, result| {
+                let key = result.to_str().trim_matches('"').to_string();
+                let mut set = collapsed_clone.borrow_mut();
+                if !set.remove(&key) {
+                    set.insert(key);
+                }
+            });
+
+            // Selección de filas: el WebView publica el índice (posición en la
+            // tabla) al hacer Ctrl/⌘+clic; aquí lo reflejamos en la
+            // MultiSelection, que es la fuente de verdad para las acciones en
+            // lote.
+            content_manager.register_script_message_handler("rowSelect", None);
+            let selection_for_msg = selection_model.clone();
+            content_manager.connect_script_message_received(Some("rowSelect"), move |_, result| {
+                if let Ok(idx) = result.to_str().trim_matches('"').parse::<u32>() {
+                    if selection_for_msg.is_selected(idx) {
+                        selection_for_msg.unselect_item(idx);
+                    } else {
+                        selection_for_msg.select_item(idx, false);
+                    }
+                }
+            });
+
             // Conectar el handler inmediatamente
             let on_note_selected_clone = on_note_selected.clone();
             let on_view_clicked_clone = on_view_clicked.clone();
@@ -162,9 +255,6 @@ impl BaseTableWidget {
         scroll.set_child(Some(&table_webview));
         
         // Lista vacía para datos (mantenida para lógica de filtros/orden)
-        let list_store = gio::ListStore::new::<glib::BoxedAnyObject>();
-        let selection_model = gtk::SingleSelection::new(Some(list_store.clone()));
-        
         // ColumnView (oculto, solo para lógica de columnas)
         let column_view = gtk::ColumnView::builder()
             .model(&selection_model)
@@ -192,27 +282,42 @@ impl BaseTableWidget {
         let status_bar = Self::create_status_bar();
         container.append(&status_bar);
 
-        Self {
+        let widget = Self {
             container,
             content_stack,
             table_webview,
             column_view,
             list_store,
+            selection: selection_model,
             filter_bar,
             filters_container,
+            search_entry,
+            search_case_btn,
+            search_word_btn,
+            search_regex_btn,
             view_tabs,
             status_bar,
             graph_view,
             graph_toggle,
             sort_btn,
             columns_btn,
+            group_by_btn,
+            selection_btn,
             source_type_btn,
+            theme_btn,
+            export_btn,
+            theme_css_provider: Rc::new(RefCell::new(None)),
+            collapsed_groups,
+            search_query: Rc::new(RefCell::new(String::new())),
+            search_options: Rc::new(RefCell::new(SearchOptions::default())),
+            search_regex: Rc::new(RefCell::new(None)),
             i18n,
             base: Rc::new(RefCell::new(None)),
             all_notes: Rc::new(RefCell::new(Vec::new())),
             notes: Rc::new(RefCell::new(Vec::new())),
             active_filters: Rc::new(RefCell::new(Vec::new())),
-            current_sort: Rc::new(RefCell::new(None)),
+            filter_root: Rc::new(RefCell::new(FilterNode::default())),
+            current_sort: Rc::new(RefCell::new(Vec::new())),
             available_properties: Rc::new(RefCell::new(Vec::new())),
             db_path: Rc::new(RefCell::new(None)),
             notes_root: Rc::new(RefCell::new(None)),
@@ -223,10 +328,24 @@ impl BaseTableWidget {
             on_graph_note_click: std::sync::Arc::new(std::sync::Mutex::new(None)),
             on_source_type_changed: Rc::new(RefCell::new(None)),
             on_view_clicked,
-        }
+            on_selection_changed: Rc::new(RefCell::new(None)),
+        };
+
+        // Conectar la búsqueda full-text incremental una sola vez (los
+        // handlers de script y las señales no deben re-registrarse por carga).
+        widget.setup_search_entry();
+        widget.setup_search_filter();
+        widget.setup_command_palette();
+        widget.setup_selection();
+        widget.setup_tag_filter_handler();
+        widget.setup_theme_popover();
+        widget.setup_export_popover();
+        widget.apply_theme_css();
+
+        widget
     }
 
-    fn create_filter_bar(i18n: &I18n) -> (gtk::Box, gtk::Box, gtk::MenuButton, gtk::MenuButton, gtk::ToggleButton, gtk::MenuButton) {
+    fn create_filter_bar(i18n: &I18n) -> (gtk::Box, gtk::Box, gtk::SearchEntry, gtk::ToggleButton, gtk::ToggleButton, gtk::ToggleButton, gtk::MenuButton, gtk::MenuButton, gtk::MenuButton, gtk::ToggleButton, gtk::MenuButton, gtk::MenuButton) {
         let bar = gtk::Box::builder()
             .orientation(gtk::Orientation::Horizontal)
             .spacing(8)
@@ -256,6 +375,38 @@ impl BaseTableWidget {
             .build();
         bar.append(&filters_container);
 
+        // Búsqueda full-text incremental (complementa los filtros estructurados)
+        let search_entry = gtk::SearchEntry::builder()
+            .placeholder_text(&i18n.t("base_search_placeholder"))
+            .width_chars(24)
+            .css_classes(["base-search-entry"])
+            .build();
+        bar.append(&search_entry);
+
+        // Modificadores de la búsqueda: sensible a mayúsculas, palabra completa
+        // y expresión regular (estilo buscador de editor de código).
+        let case_btn = gtk::ToggleButton::builder()
+            .label("Aa")
+            .tooltip_text(&i18n.t("base_search_case"))
+            .css_classes(["flat", "base-search-toggle"])
+            .build();
+        bar.append(&case_btn);
+        let word_btn = gtk::ToggleButton::builder()
+            .label("W")
+            .tooltip_text(&i18n.t("base_search_word"))
+            .css_classes(["flat", "base-search-toggle"])
+            .build();
+        bar.append(&word_btn);
+        let regex_btn = gtk::ToggleButton::builder()
+            .label(".*")
+            .tooltip_text(&i18n.t("base_search_regex"))
+            .css_classes(["flat", "base-search-toggle"])
+            .build();
+        bar.append(&regex_btn);
+
+        // Separator
+        bar.append(&gtk::Separator::new(gtk::Orientation::Vertical));
+
         // Botón de ordenamiento
         let sort_btn = gtk::MenuButton::builder()
             .icon_name("view-sort-ascending-symbolic")
@@ -272,6 +423,14 @@ impl BaseTableWidget {
             .build();
         bar.append(&columns_btn);
 
+        // Botón de agrupación (group by)
+        let group_by_btn = gtk::MenuButton::builder()
+            .icon_name("view-list-ordered-symbolic")
+            .tooltip_text(&i18n.t("base_group_by"))
+            .css_classes(["flat"])
+            .build();
+        bar.append(&group_by_btn);
+
         // Separator antes del toggle de grafo
         bar.append(&gtk::Separator::new(gtk::Orientation::Vertical));
 
@@ -283,6 +442,14 @@ impl BaseTableWidget {
             .build();
         bar.append(&source_type_btn);
 
+        // Botón para elegir el tema (claro/oscuro/sistema) de esta Base
+        let theme_btn = gtk::MenuButton::builder()
+            .icon_name("weather-clear-night-symbolic")
+            .tooltip_text(&i18n.t("base_theme"))
+            .css_classes(["flat"])
+            .build();
+        bar.append(&theme_btn);
+
         // Separator antes del toggle de grafo
         bar.append(&gtk::Separator::new(gtk::Orientation::Vertical));
 
@@ -294,7 +461,325 @@ impl BaseTableWidget {
             .build();
         bar.append(&graph_toggle);
 
-        (bar, filters_container, sort_btn, columns_btn, graph_toggle, source_type_btn)
+        (bar, filters_container, search_entry, case_btn, word_btn, regex_btn, sort_btn, columns_btn, group_by_btn, graph_toggle, source_type_btn, theme_btn)
+    }
+
+    /// Construir la lista de acciones disponibles en la paleta de comandos.
+    fn build_palette_entries(
+        i18n: &Rc<RefCell<I18n>>,
+        base: &Rc<RefCell<Option<Base>>>,
+    ) -> Vec<PaletteEntry> {
+        let mut entries = Vec::new();
+        let i18n = i18n.borrow();
+
+        if let Some(base) = base.borrow().as_ref() {
+            for (i, view) in base.views.iter().enumerate() {
+                entries.push(PaletteEntry {
+                    label: format!("{}: {}", i18n.t("base_switch_view"), view.name),
+                    action: PaletteAction::SwitchView(i),
+                });
+            }
+        }
+
+        entries.push(PaletteEntry { label: i18n.t("base_show_graph"), action: PaletteAction::ToggleGraph });
+        entries.push(PaletteEntry { label: i18n.t("base_add_filter"), action: PaletteAction::AddFilter });
+        entries.push(PaletteEntry { label: i18n.t("base_sort"), action: PaletteAction::Sort });
+        entries.push(PaletteEntry { label: i18n.t("base_columns"), action: PaletteAction::Columns });
+        entries.push(PaletteEntry { label: i18n.t("base_data_source"), action: PaletteAction::ToggleSourceType });
+        entries
+    }
+
+    /// Instalar el disparador de teclado (Ctrl+P) de la paleta de comandos.
+    fn setup_command_palette(&self) {
+        let key = gtk::EventControllerKey::new();
+        let container = self.container.clone();
+        let i18n = self.i18n.clone();
+        let base = self.base.clone();
+        let graph_toggle = self.graph_toggle.clone();
+        let sort_btn = self.sort_btn.clone();
+        let columns_btn = self.columns_btn.clone();
+        let source_type_btn = self.source_type_btn.clone();
+        let filter_bar = self.filter_bar.clone();
+        let view_tabs = self.view_tabs.clone();
+        key.connect_key_pressed(move |_, keyval, _, state| {
+            let is_p = matches!(keyval, gdk::Key::p | gdk::Key::P);
+            if state.contains(gdk::ModifierType::CONTROL_MASK) && is_p {
+                Self::show_command_palette(
+                    &container, &i18n, &base, &graph_toggle, &sort_btn,
+                    &columns_btn, &source_type_btn, &filter_bar, &view_tabs,
+                );
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        self.container.add_controller(key);
+    }
+
+    /// Mostrar la paleta de comandos (Ctrl+P): una entrada de búsqueda sobre
+    /// un `ListBox` que se refiltra en vivo con [`rank_palette_entries`].
+    ///
+    /// Cada entrada ejecuta el mismo camino que su botón/popover equivalente:
+    /// conmutar el grafo, desplegar los popovers de filtro/orden/columnas/origen
+    /// o activar el tab de una vista. Los callbacks no pueden capturar `&self`,
+    /// así que se reciben y clonan los manejadores de widgets y el estado.
+    #[allow(clippy::too_many_arguments)]
+    fn show_command_palette(
+        container: &gtk::Box,
+        i18n: &Rc<RefCell<I18n>>,
+        base: &Rc<RefCell<Option<Base>>>,
+        graph_toggle: &gtk::ToggleButton,
+        sort_btn: &gtk::MenuButton,
+        columns_btn: &gtk::MenuButton,
+        source_type_btn: &gtk::MenuButton,
+        filter_bar: &gtk::Box,
+        view_tabs: &gtk::Box,
+    ) {
+        let entries = Self::build_palette_entries(i18n, base);
+
+        let popover = gtk::Popover::builder()
+            .css_classes(["command-palette"])
+            .has_arrow(false)
+            .autohide(true)
+            .build();
+        popover.set_parent(container);
+
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(4)
+            .width_request(360)
+            .build();
+
+        let search = gtk::SearchEntry::builder()
+            .placeholder_text(&i18n.borrow().t("base_command_palette"))
+            .build();
+        content.append(&search);
+
+        let list = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::Browse)
+            .css_classes(["command-palette-list"])
+            .build();
+        let scroll = gtk::ScrolledWindow::builder()
+            .height_request(280)
+            .child(&list)
+            .build();
+        content.append(&scroll);
+
+        popover.set_child(Some(&content));
+
+        // Estado compartido: entradas actualmente mostradas (en orden).
+        let visible: Rc<RefCell<Vec<PaletteEntry>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let rebuild = {
+            let list = list.clone();
+            let entries = entries.clone();
+            let visible = visible.clone();
+            move |query: &str| {
+                while let Some(row) = list.first_child() {
+                    list.remove(&row);
+                }
+                let ranked = rank_palette_entries(&entries, query);
+                for entry in &ranked {
+                    let row = gtk::Label::builder()
+                        .label(&entry.label)
+                        .xalign(0.0)
+                        .margin_start(8)
+                        .margin_end(8)
+                        .margin_top(6)
+                        .margin_bottom(6)
+                        .build();
+                    list.append(&row);
+                }
+                if let Some(first) = list.row_at_index(0) {
+                    list.select_row(Some(&first));
+                }
+                *visible.borrow_mut() = ranked;
+            }
+        };
+        rebuild("");
+
+        {
+            let rebuild = rebuild.clone();
+            search.connect_search_changed(move |e| rebuild(&e.text()));
+        }
+
+        // Ejecutar la acción de la fila activada, reutilizando los manejadores
+        // de los botones existentes.
+        {
+            let visible = visible.clone();
+            let popover = popover.clone();
+            let graph_toggle = graph_toggle.clone();
+            let sort_btn = sort_btn.clone();
+            let columns_btn = columns_btn.clone();
+            let source_type_btn = source_type_btn.clone();
+            let filter_bar = filter_bar.clone();
+            let view_tabs = view_tabs.clone();
+            list.connect_row_activated(move |_, row| {
+                let action = visible.borrow().get(row.index() as usize).map(|e| e.action.clone());
+                popover.popdown();
+                let Some(action) = action else { return };
+                match action {
+                    PaletteAction::SwitchView(i) => {
+                        // Pulsar la etiqueta de la pestaña correspondiente, que
+                        // cambia de vista y dispara la recarga.
+                        if let Some(tab) = view_tabs.observe_children().item(i as u32)
+                            .and_downcast::<gtk::Box>()
+                        {
+                            if let Some(label_btn) = tab.first_child().and_downcast::<gtk::Button>() {
+                                label_btn.emit_clicked();
+                            }
+                        }
+                    }
+                    PaletteAction::ToggleGraph => graph_toggle.set_active(!graph_toggle.is_active()),
+                    PaletteAction::AddFilter => {
+                        if let Some(btn) = filter_bar.first_child().and_downcast::<gtk::MenuButton>() {
+                            btn.popup();
+                        }
+                    }
+                    PaletteAction::Sort => sort_btn.popup(),
+                    PaletteAction::Columns => columns_btn.popup(),
+                    PaletteAction::ToggleSourceType => source_type_btn.popup(),
+                }
+            });
+        }
+
+        // Enter en la entrada de búsqueda activa la primera fila.
+        {
+            let list = list.clone();
+            search.connect_activate(move |_| {
+                if let Some(row) = list.selected_row() {
+                    row.emit_by_name::<()>("activate", &[]);
+                }
+            });
+        }
+
+        // Desparentar el popover al cerrarse para no acumular hijos ocultos
+        // en el contenedor con cada pulsación de Ctrl+P.
+        popover.connect_closed(|popover| {
+            popover.unparent();
+        });
+
+        popover.popup();
+        search.grab_focus();
+    }
+
+    /// Conectar la búsqueda full-text incremental de la barra de filtros.
+    ///
+    /// Cada pulsación se reboteja (debounce) un breve intervalo antes de
+    /// inyectar el término en el `table_webview` mediante la función JS
+    /// `applySearch`, que resalta coincidencias y actualiza el contador
+    /// "N de M coincidencias" de la barra de estado.
+    fn setup_search_entry(&self) {
+        // Etiqueta del recuento de coincidencias en la barra de estado.
+        let match_label = gtk::Label::builder()
+            .css_classes(["dim-label", "base-search-count"])
+            .build();
+        self.status_bar.append(&match_label);
+
+        // Recibir "N/M" desde el WebView y reflejarlo en la barra de estado.
+        if let Some(cm) = self.table_webview.user_content_manager() {
+            cm.register_script_message_handler("searchCount", None);
+            let match_label = match_label.clone();
+            cm.connect_script_message_received(Some("searchCount"), move |_, result| {
+                let msg = result.to_str();
+                let trimmed = msg.trim_matches('"');
+                if let Some((hits, total)) = trimmed.split_once('/') {
+                    match_label.set_text(&format!("{hits} / {total}"));
+                }
+            });
+        }
+
+        // Botones next/previous para saltar entre coincidencias.
+        let table_webview = self.table_webview.clone();
+        self.search_entry.connect_next_match(move |_| {
+            table_webview.evaluate_javascript("nextHit();", None, None, None::<&gio::Cancellable>, |_| {});
+        });
+        let table_webview = self.table_webview.clone();
+        self.search_entry.connect_previous_match(move |_| {
+            table_webview.evaluate_javascript("prevHit();", None, None, None::<&gio::Cancellable>, |_| {});
+        });
+
+        let table_webview = self.table_webview.clone();
+        let pending: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+        self.search_entry.connect_search_changed(move |entry| {
+            let query = entry.text().to_string();
+
+            // Cancelar el timeout pendiente para coalescer pulsaciones rápidas.
+            if let Some(source) = pending.borrow_mut().take() {
+                source.remove();
+            }
+
+            let table_webview = table_webview.clone();
+            let pending_inner = pending.clone();
+            let source = glib::timeout_add_local(std::time::Duration::from_millis(150), move || {
+                let escaped = query
+                    .replace('\\', "\\\\")
+                    .replace('\'', "\\'")
+                    .replace('\n', " ")
+                    .replace('\r', " ");
+                let script = format!("if (window.applySearch) applySearch('{escaped}');");
+                table_webview.evaluate_javascript(&script, None, None, None::<&gio::Cancellable>, |_| {});
+                *pending_inner.borrow_mut() = None;
+                glib::ControlFlow::Break
+            });
+            *pending.borrow_mut() = Some(source);
+        });
+    }
+
+    /// Conectar la búsqueda full-text del lado de Rust: la consulta y los tres
+    /// modificadores (case/word/regex) alimentan `apply_search_and_refresh`,
+    /// que filtra las filas reutilizando el pipeline de filtros.
+    fn setup_search_filter(&self) {
+        // Fábrica de un closure que ejecuta el refresco con el estado clonado,
+        // para compartirlo entre la entrada y los tres toggles.
+        let make_refresh = || {
+            let search_query = self.search_query.clone();
+            let search_options = self.search_options.clone();
+            let search_regex = self.search_regex.clone();
+            let current_sort = self.current_sort.clone();
+            let all_notes = self.all_notes.clone();
+            let notes = self.notes.clone();
+            let filter_root = self.filter_root.clone();
+            let list_store = self.list_store.clone();
+            let status_bar = self.status_bar.clone();
+            let table_webview = self.table_webview.clone();
+            let base = self.base.clone();
+            let base_id = self.base_id.clone();
+            let i18n = self.i18n.clone();
+            let selection = self.selection.clone();
+            move || {
+                apply_search_and_refresh(
+                    &search_query, &search_options, &search_regex, &current_sort,
+                    &all_notes, &notes, &filter_root, &list_store, &status_bar,
+                    &table_webview, &base, &base_id, &i18n, &selection,
+                );
+            }
+        };
+
+        {
+            let refresh = make_refresh();
+            let search_query = self.search_query.clone();
+            self.search_entry.connect_search_changed(move |entry| {
+                *search_query.borrow_mut() = entry.text().to_string();
+                refresh();
+            });
+        }
+
+        // Cada toggle actualiza su opción y vuelve a filtrar.
+        let toggles: [(&gtk::ToggleButton, fn(&mut SearchOptions, bool)); 3] = [
+            (&self.search_case_btn, |o, v| o.case_sensitive = v),
+            (&self.search_word_btn, |o, v| o.whole_word = v),
+            (&self.search_regex_btn, |o, v| o.regex = v),
+        ];
+        for (btn, set) in toggles {
+            let refresh = make_refresh();
+            let search_options = self.search_options.clone();
+            btn.connect_toggled(move |b| {
+                set(&mut search_options.borrow_mut(), b.is_active());
+                refresh();
+            });
+        }
     }
 
     fn create_view_tabs() -> gtk::Box {
@@ -329,6 +814,13 @@ impl BaseTableWidget {
             .build();
         bar.append(&count_label);
 
+        // Contador de selección (vacío mientras no haya filas seleccionadas).
+        let selection_label = gtk::Label::builder()
+            .label("")
+            .css_classes(["dim-label"])
+            .build();
+        bar.append(&selection_label);
+
         bar
     }
 
@@ -404,11 +896,31 @@ impl BaseTableWidget {
         // Guardar paths para refrescar
         *self.notes_root.borrow_mut() = Some(notes_root.to_path_buf());
         
-        // Cargar filtros y sort guardados desde la vista activa
+        // Cargar filtros, sort y búsqueda guardados desde la vista activa
         if let Some(view) = base.active_view() {
-            *self.active_filters.borrow_mut() = view.filter.filters.clone();
+            // El árbol de filtros es la fuente de verdad. Si la vista trae uno
+            // guardado se usa tal cual; si no (configuración antigua) se envuelve
+            // la lista plana en un grupo `All` para preservar la semántica AND.
+            let root = view
+                .filter
+                .node
+                .clone()
+                .unwrap_or_else(|| FilterNode::from_filters(view.filter.filters.clone()));
+            *self.active_filters.borrow_mut() = root.leaves().into_iter().cloned().collect();
+            *self.filter_root.borrow_mut() = root;
             *self.current_sort.borrow_mut() = view.sort.clone();
+            *self.search_query.borrow_mut() = view.search_query.clone();
+            *self.search_options.borrow_mut() = view.search_options.clone();
+        }
+        // Reflejar las opciones de búsqueda en sus toggles y recompilar.
+        {
+            let opts = self.search_options.borrow().clone();
+            self.search_case_btn.set_active(opts.case_sensitive);
+            self.search_word_btn.set_active(opts.whole_word);
+            self.search_regex_btn.set_active(opts.regex);
+            self.search_entry.set_text(&self.search_query.borrow());
         }
+        self.recompile_search();
 
         // Comportamiento según el tipo de fuente
         match base.source_type {
@@ -423,6 +935,7 @@ impl BaseTableWidget {
                 self.setup_filter_popover();
                 self.setup_sort_popover();
                 self.setup_columns_popover();
+                self.setup_group_by_popover();
 
                 // Actualizar tabs de vistas
                 self.update_view_tabs(&base);
@@ -466,6 +979,7 @@ impl BaseTableWidget {
                 self.setup_filter_popover();
                 self.setup_sort_popover();
                 self.setup_columns_popover();
+                self.setup_group_by_popover();
                 
                 // Actualizar tabs
                 self.update_view_tabs(base);
@@ -579,7 +1093,11 @@ impl BaseTableWidget {
         // Ejecutar query
         let engine = BaseQueryEngine::new(db, notes_root);
         match engine.query_view(view, source_folder) {
-            Ok(notes) => {
+            Ok(mut notes) => {
+                // Cosechar hashtags del cuerpo antes de nada más, para que
+                // filtros, orden y columnas vean ya la propiedad "tags" fusionada.
+                Self::merge_content_hashtags(&mut notes);
+
                 // Guardar todas las notas
                 *self.all_notes.borrow_mut() = notes.clone();
                 
@@ -601,45 +1119,93 @@ impl BaseTableWidget {
     /// Aplicar filtros activos y ordenamiento
     fn apply_filters_and_sort(&self) {
         let all_notes = self.all_notes.borrow();
-        let filters = self.active_filters.borrow();
+        let filter_root = self.filter_root.borrow();
         let sort = self.current_sort.borrow();
-        
+
+        // Predicado de búsqueda full-text, ANDed con los filtros. Se evalúa
+        // contra la forma de texto de cada columna visible de la vista activa.
+        let search = self.search_regex.borrow();
+        let columns: Vec<ColumnConfig> = self
+            .base
+            .borrow()
+            .as_ref()
+            .and_then(|b| b.active_view().map(|v| v.columns.clone()))
+            .unwrap_or_default();
+        let invalid_pattern = matches!(search.as_ref(), Some(Err(_)));
+
         // Filtrar notas
         let mut filtered: Vec<NoteWithProperties> = all_notes
             .iter()
-            .filter(|note| {
-                filters.iter().all(|f| f.evaluate(&note.properties))
-            })
+            .filter(|note| filter_root.evaluate(&note.properties))
+            .filter(|note| Self::note_matches_search(note, &columns, search.as_ref()))
             .cloned()
             .collect();
         
         // Ordenar
-        if let Some(sort_config) = sort.as_ref() {
-            filtered.sort_by(|a, b| {
-                let key_a = a.properties
-                    .get(&sort_config.property)
-                    .map(|v| v.sort_key())
-                    .unwrap_or_default();
-                let key_b = b.properties
-                    .get(&sort_config.property)
-                    .map(|v| v.sort_key())
-                    .unwrap_or_default();
-
-                match sort_config.direction {
-                    SortDirection::Asc => key_a.cmp(&key_b),
-                    SortDirection::Desc => key_b.cmp(&key_a),
-                }
-            });
+        if !sort.is_empty() {
+            filtered.sort_by(|a, b| compare_by_sort_levels(a, b, &sort));
         }
-        
+
+        drop(search);
+
         // Actualizar notas mostradas
         *self.notes.borrow_mut() = filtered.clone();
-        
+
         // Actualizar UI
         self.update_data(&filtered);
-        self.update_status_bar(filtered.len());
+        if invalid_pattern {
+            if let Some(label) = self.status_bar.first_child().and_downcast::<gtk::Label>() {
+                label.set_text(&self.i18n.borrow().t("base_search_invalid"));
+            }
+        } else {
+            self.update_status_bar(filtered.len());
+        }
         self.update_filter_chips();
     }
+
+    /// ¿Casa una nota con la búsqueda full-text actual?
+    ///
+    /// Un patrón inválido (`Some(Err)`) no casa con nada; sin búsqueda activa
+    /// (`None`) casan todas. Con un `Regex` válido, basta que lo case la forma
+    /// de texto de cualquier columna visible.
+    fn note_matches_search(
+        note: &NoteWithProperties,
+        columns: &[ColumnConfig],
+        search: Option<&Result<regex::Regex, String>>,
+    ) -> bool {
+        match search {
+            None => true,
+            Some(Err(_)) => false,
+            Some(Ok(re)) => columns
+                .iter()
+                .filter(|c| c.visible)
+                .any(|c| re.is_match(&Self::get_property_value(note, &c.property))),
+        }
+    }
+
+    /// Recompilar el `Regex` de búsqueda a partir de la consulta y las opciones
+    /// actuales. Deja `None` cuando la consulta está vacía.
+    ///
+    /// En modo literal la consulta se escapa; `whole_word` la envuelve en
+    /// `\b…\b`; `case_sensitive` desactiva el flag insensible. Un patrón que no
+    /// compila se guarda como `Err` para mostrar cero resultados con aviso.
+    fn recompile_search(&self) {
+        let query = self.search_query.borrow().clone();
+        if query.trim().is_empty() {
+            *self.search_regex.borrow_mut() = None;
+            return;
+        }
+        let opts = self.search_options.borrow().clone();
+        let mut pattern = if opts.regex { query } else { regex::escape(&query) };
+        if opts.whole_word {
+            pattern = format!(r"\b(?:{})\b", pattern);
+        }
+        let compiled = regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!opts.case_sensitive)
+            .build()
+            .map_err(|e| e.to_string());
+        *self.search_regex.borrow_mut() = Some(compiled);
+    }
     
     /// Persistir la configuración actual de la Base en la BD
     fn save_config(&self) {
@@ -648,10 +1214,16 @@ impl BaseTableWidget {
         let mut base_opt = self.base.borrow_mut();
         
         if let (Some(id), Some(db), Some(base)) = (base_id.as_ref(), notes_db.as_ref(), base_opt.as_mut()) {
-            // Sincronizar filtros y sort a la vista activa
+            // Sincronizar filtros, sort y búsqueda a la vista activa
             if let Some(view) = base.views.get_mut(base.active_view) {
-                view.filter.filters = self.active_filters.borrow().clone();
+                // Persistir el árbol completo y mantener la lista plana por
+                // compatibilidad con lectores antiguos.
+                let root = self.filter_root.borrow().clone();
+                view.filter.filters = root.leaves().into_iter().cloned().collect();
+                view.filter.node = Some(root);
                 view.sort = self.current_sort.borrow().clone();
+                view.search_query = self.search_query.borrow().clone();
+                view.search_options = self.search_options.borrow().clone();
             }
             
             // Serializar y guardar
@@ -664,12 +1236,17 @@ impl BaseTableWidget {
     }
     
     /// Añadir un filtro
+    ///
+    /// El alta rápida es una operación plana: se añade la hoja al espejo y se
+    /// reconstruye el árbol como un grupo `All`. Para consultas anidadas
+    /// (OR/NOT) se usa el constructor de árbol del popover de filtros.
     pub fn add_filter(&self, filter: Filter) {
         self.active_filters.borrow_mut().push(filter);
+        self.rebuild_filter_root_from_active();
         self.apply_filters_and_sort();
         self.save_config();
     }
-    
+
     /// Eliminar un filtro por índice
     pub fn remove_filter(&self, index: usize) {
         let mut filters = self.active_filters.borrow_mut();
@@ -677,20 +1254,30 @@ impl BaseTableWidget {
             filters.remove(index);
         }
         drop(filters);
+        self.rebuild_filter_root_from_active();
         self.apply_filters_and_sort();
         self.save_config();
     }
-    
+
     /// Limpiar todos los filtros
     pub fn clear_filters(&self) {
         self.active_filters.borrow_mut().clear();
+        self.rebuild_filter_root_from_active();
         self.apply_filters_and_sort();
         self.save_config();
     }
+
+    /// Reconstruir el árbol plano (`All` de hojas) a partir del espejo de
+    /// filtros activos, tras una edición del alta rápida.
+    fn rebuild_filter_root_from_active(&self) {
+        let leaves = self.active_filters.borrow().clone();
+        *self.filter_root.borrow_mut() = FilterNode::from_filters(leaves);
+    }
     
-    /// Establecer ordenamiento
-    pub fn set_sort(&self, sort: Option<SortConfig>) {
-        *self.current_sort.borrow_mut() = sort;
+    /// Establecer los niveles de ordenamiento (el primero es el primario,
+    /// los siguientes solo desempatan).
+    pub fn set_sort(&self, levels: Vec<SortConfig>) {
+        *self.current_sort.borrow_mut() = levels;
         self.apply_filters_and_sort();
         self.save_config();
     }
@@ -710,10 +1297,13 @@ impl BaseTableWidget {
         } else {
             self.available_properties.borrow().clone()
         };
-        let (popover, prop_combo, op_combo, value_entry) = create_filter_popover_with_refs(&properties, &self.i18n.borrow());
+        let distinct_tags = Self::distinct_tags(&self.all_notes.borrow());
+        let (popover, prop_combo, op_combo, value_entry, combine_combo, tags_box) =
+            create_filter_popover_with_refs(&properties, &distinct_tags, &self.i18n.borrow());
         
         // Clonar referencias para el closure
         let active_filters = self.active_filters.clone();
+        let filter_root = self.filter_root.clone();
         let all_notes = self.all_notes.clone();
         let notes = self.notes.clone();
         let current_sort = self.current_sort.clone();
@@ -724,7 +1314,11 @@ impl BaseTableWidget {
         let properties_clone = properties.clone();
         let table_webview = self.table_webview.clone();
         let base = self.base.clone();
-        
+        let base_id = self.base_id.clone();
+        let i18n = self.i18n.clone();
+        let selection = self.selection.clone();
+        let tags_box = tags_box.clone();
+
         // Buscar el botón Apply dentro del popover y conectarlo
         if let Some(content) = popover.child().and_downcast::<gtk::Box>() {
             // El último hijo es el box de botones
@@ -736,66 +1330,121 @@ impl BaseTableWidget {
                         let prop_idx = prop_combo.selected() as usize;
                         let op_idx = op_combo.selected() as usize;
                         let value_text = value_entry.text().to_string();
-                        
+
                         if prop_idx < properties_clone.len() {
                             let property = properties_clone[prop_idx].clone();
                             let operator = index_to_operator(op_idx);
-                            let value = parse_filter_value(&value_text);
-                            
-                            let filter = Filter {
-                                property,
-                                operator,
-                                value,
+
+                            // Si el selector de etiquetas está visible, cada
+                            // casilla marcada aporta su propia hoja en vez del
+                            // único valor del campo de texto libre.
+                            let checked_tags: Vec<String> = if tags_box.is_visible() {
+                                let mut checked = Vec::new();
+                                let mut child = tags_box.first_child();
+                                while let Some(widget) = child {
+                                    if let Some(check) = widget.downcast_ref::<gtk::CheckButton>() {
+                                        if check.is_active() {
+                                            checked.push(check.label().unwrap_or_default().to_string());
+                                        }
+                                    }
+                                    child = widget.next_sibling();
+                                }
+                                checked
+                            } else {
+                                Vec::new()
                             };
-                            
-                            // Añadir filtro
-                            active_filters.borrow_mut().push(filter);
-                            
+
+                            let new_leaves: Vec<FilterNode> = if !checked_tags.is_empty() {
+                                checked_tags
+                                    .into_iter()
+                                    .map(|tag| {
+                                        FilterNode::Leaf(Filter {
+                                            property: property.clone(),
+                                            operator: operator.clone(),
+                                            value: PropertyValue::Text(tag),
+                                        })
+                                    })
+                                    .collect()
+                            } else {
+                                vec![FilterNode::Leaf(Filter {
+                                    property,
+                                    operator,
+                                    value: parse_filter_value(&value_text),
+                                })]
+                            };
+                            if new_leaves.is_empty() {
+                                return;
+                            }
+                            let new_group = if new_leaves.len() == 1 {
+                                new_leaves.into_iter().next().unwrap()
+                            } else {
+                                // Varias etiquetas marcadas a la vez: casa con
+                                // cualquiera de ellas, igual que un filtro
+                                // facetado habitual.
+                                FilterNode::Any(new_leaves)
+                            };
+
+                            if combine_combo.selected() == 1 && !filter_root.borrow().is_empty() {
+                                // "O": envolver el árbol existente y la nueva
+                                // hoja en un grupo `Any` nuevo, en vez de
+                                // aplanar como hace el alta rápida de siempre.
+                                let previous_root = filter_root.borrow().clone();
+                                *filter_root.borrow_mut() =
+                                    FilterNode::Any(vec![previous_root, new_group]);
+                                *active_filters.borrow_mut() =
+                                    filter_root.borrow().leaves().into_iter().cloned().collect();
+                            } else if matches!(new_group, FilterNode::Leaf(_)) {
+                                // "Y": el alta rápida es plana, así que se
+                                // empuja al espejo y se reconstruye el árbol
+                                // `All`, que es la fuente de verdad del filtrado.
+                                active_filters.borrow_mut().extend(
+                                    new_group.leaves().into_iter().cloned(),
+                                );
+                                *filter_root.borrow_mut() =
+                                    FilterNode::from_filters(active_filters.borrow().clone());
+                            } else {
+                                // "Y" con varias etiquetas: el grupo `Any` se
+                                // añade entero al árbol para no aplanarlo.
+                                let previous_root = filter_root.borrow().clone();
+                                *filter_root.borrow_mut() = if previous_root.is_empty() {
+                                    new_group
+                                } else {
+                                    FilterNode::All(vec![previous_root, new_group])
+                                };
+                                *active_filters.borrow_mut() =
+                                    filter_root.borrow().leaves().into_iter().cloned().collect();
+                            }
+
                             // Re-aplicar filtros
                             let all = all_notes.borrow();
-                            let filters = active_filters.borrow();
+                            let root = filter_root.borrow();
                             let sort = current_sort.borrow();
-                            
+
                             let mut filtered: Vec<NoteWithProperties> = all
                                 .iter()
-                                .filter(|note| {
-                                    filters.iter().all(|f| f.evaluate(&note.properties))
-                                })
+                                .filter(|note| root.evaluate(&note.properties))
                                 .cloned()
                                 .collect();
                             
                             // Ordenar
-                            if let Some(sort_config) = sort.as_ref() {
-                                filtered.sort_by(|a, b| {
-                                    let key_a = a.properties
-                                        .get(&sort_config.property)
-                                        .map(|v| v.sort_key())
-                                        .unwrap_or_default();
-                                    let key_b = b.properties
-                                        .get(&sort_config.property)
-                                        .map(|v| v.sort_key())
-                                        .unwrap_or_default();
-
-                                    match sort_config.direction {
-                                        SortDirection::Asc => key_a.cmp(&key_b),
-                                        SortDirection::Desc => key_b.cmp(&key_a),
-                                    }
-                                });
+                            if !sort.is_empty() {
+                                filtered.sort_by(|a, b| compare_by_sort_levels(a, b, &sort));
                             }
-                            
+
                             drop(all);
-                            drop(filters);
+                            drop(root);
                             drop(sort);
-                            
+
+                            let selected_ids = selected_note_ids_before_refresh(&selection, &notes.borrow());
                             *notes.borrow_mut() = filtered.clone();
-                            
+
                             // Actualizar UI (list_store para lógica)
                             list_store.remove_all();
                             for note in &filtered {
                                 let boxed = glib::BoxedAnyObject::new(note.clone());
                                 list_store.append(&boxed);
                             }
-                            
+
                             // Actualizar WebView
                             let columns = if let Some(base) = base.borrow().as_ref() {
                                 if let Some(view) = base.views.get(base.active_view) {
@@ -812,9 +1461,17 @@ impl BaseTableWidget {
                                     ColumnConfig { property: "created".to_string(), title: None, width: Some(150), visible: true },
                                 ]
                             };
-                            let html = Self::render_table_html_static(&filtered, &columns, Language::from_env());
+                            let group_by = base.borrow().as_ref()
+                                .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+                            let theme = base.borrow().as_ref().and_then(|b| b.theme).unwrap_or_default();
+                            let html = Self::render_table_html_static(
+                                &filtered, &columns, Language::from_env(),
+                                group_by.as_deref(), &std::collections::HashSet::new(),
+                                base_id.borrow().unwrap_or(-1), theme,
+                            );
                             table_webview.load_html(&html, None);
-                            
+                            restore_selection_by_identity(&selection, &selected_ids, &filtered);
+
                             // Actualizar status
                             if let Some(label) = status_bar.first_child().and_downcast::<gtk::Label>() {
                                 let text = if filtered.len() == 1 {
@@ -824,9 +1481,14 @@ impl BaseTableWidget {
                                 };
                                 label.set_text(&text);
                             }
-                            
-                            // Actualizar chips
-                            update_filter_chips_in_container(&filters_container, &active_filters.borrow());
+
+                            // Actualizar chips (árbol anidado, con i18n)
+                            render_filter_tree_chips(
+                                &filters_container, &filter_root, &active_filters,
+                                &current_sort, &all_notes, &notes, &list_store,
+                                &status_bar, &table_webview, &base, &base_id, &i18n,
+                                &selection,
+                            );
                         }
                         
                         // Cerrar popover
@@ -846,52 +1508,822 @@ impl BaseTableWidget {
     }
     
     /// Configurar el popover de ordenamiento
+    /// Configurar el popover de ordenamiento.
+    ///
+    /// El contenido se reconstruye cada vez que se abre (en vez de una sola
+    /// vez en `new()`), porque la lista de propiedades ordenables son las
+    /// columnas visibles de la vista activa, que cambian al cambiar de
+    /// pestaña de vista.
     fn setup_sort_popover(&self) {
-        // Obtener solo las columnas visibles de la vista actual
-        let properties: Vec<String> = if let Some(base) = self.base.borrow().as_ref() {
-            if let Some(view) = base.active_view() {
-                view.columns.iter()
-                    .filter(|c| c.visible)
-                    .map(|c| c.property.clone())
-                    .collect()
-            } else {
-                self.available_properties.borrow().clone()
-            }
-        } else {
-            self.available_properties.borrow().clone()
-        };
-        let popover = create_sort_popover_with_callbacks(
-            &properties,
-            self.current_sort.clone(),
-            self.all_notes.clone(),
-            self.notes.clone(),
-            self.active_filters.clone(),
-            self.list_store.clone(),
-            self.status_bar.clone(),
-            self.table_webview.clone(),
-            self.base.clone(),
-            &self.i18n.borrow(),
-        );
-        
-        // Usar referencia directa al botón de sort
+        let popover = gtk::Popover::builder().css_classes(["sort-popover"]).build();
         self.sort_btn.set_popover(Some(&popover));
-    }
-    
-    /// Configurar el popover de columnas (se regenera cada vez que se abre)
-    fn setup_columns_popover(&self) {
-        // Usar referencia directa al botón de columnas
-        let columns_btn = &self.columns_btn;
-        
-        // Clonar referencias necesarias
-        let base_ref = self.base.clone();
-        let base_id = self.base_id.clone();
-        let notes_db = self.notes_db.clone();
-        let column_view = self.column_view.clone();
-        let available_props = self.available_properties.clone();
-        let table_webview = self.table_webview.clone();
-        let notes = self.notes.clone();
+
+        let base = self.base.clone();
+        let available_properties = self.available_properties.clone();
+        let current_sort = self.current_sort.clone();
+        let all_notes = self.all_notes.clone();
+        let notes = self.notes.clone();
+        let filter_root = self.filter_root.clone();
+        let list_store = self.list_store.clone();
+        let status_bar = self.status_bar.clone();
+        let table_webview = self.table_webview.clone();
+        let base_id = self.base_id.clone();
+        let i18n = self.i18n.clone();
+        let selection = self.selection.clone();
+
+        popover.connect_notify_local(Some("visible"), move |pop, _| {
+            if !pop.is_visible() {
+                return;
+            }
+            // Obtener solo las columnas visibles de la vista actual.
+            let properties: Vec<String> = base
+                .borrow()
+                .as_ref()
+                .and_then(|b| b.active_view())
+                .map(|view| {
+                    view.columns
+                        .iter()
+                        .filter(|c| c.visible)
+                        .map(|c| c.property.clone())
+                        .collect()
+                })
+                .unwrap_or_else(|| available_properties.borrow().clone());
+
+            let content = build_sort_popover_content(
+                &properties,
+                pop,
+                current_sort.clone(),
+                all_notes.clone(),
+                notes.clone(),
+                filter_root.clone(),
+                list_store.clone(),
+                status_bar.clone(),
+                table_webview.clone(),
+                base.clone(),
+                base_id.clone(),
+                &i18n.borrow(),
+                selection.clone(),
+            );
+            pop.set_child(Some(&content));
+        });
+    }
+    
+    /// Configurar el popover de agrupación (group by).
+    ///
+    /// Ofrece "Sin agrupar" más una opción por cada propiedad disponible. Al
+    /// elegir una, se guarda en `group_by` de la vista activa, se re-renderiza
+    /// la tabla con secciones colapsables y se persiste la configuración.
+    fn setup_group_by_popover(&self) {
+        let base = self.base.clone();
+        let base_id = self.base_id.clone();
+        let notes_db = self.notes_db.clone();
+        let available = self.available_properties.clone();
+        let notes = self.notes.clone();
+        let table_webview = self.table_webview.clone();
+        let collapsed = self.collapsed_groups.clone();
         let i18n = self.i18n.clone();
+
+        let popover = gtk::Popover::builder()
+            .css_classes(["group-by-popover"])
+            .has_arrow(true)
+            .build();
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(4)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .width_request(200)
+            .build();
+        popover.set_child(Some(&content));
+        self.group_by_btn.set_popover(Some(&popover));
+
+        popover.connect_notify_local(Some("visible"), move |pop, _| {
+            if !pop.is_visible() {
+                return;
+            }
+            while let Some(child) = content.first_child() {
+                content.remove(&child);
+            }
+
+            let current = base
+                .borrow()
+                .as_ref()
+                .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+
+            // "Sin agrupar" + una opción por propiedad, como grupo de radios.
+            let none_radio = gtk::CheckButton::builder()
+                .label(&i18n.borrow().t("base_group_none"))
+                .active(current.is_none())
+                .build();
+            content.append(&none_radio);
+
+            for prop in available.borrow().iter() {
+                let radio = gtk::CheckButton::builder()
+                    .label(&Self::format_column_header(prop, i18n.borrow().current_language()))
+                    .active(current.as_deref() == Some(prop.as_str()))
+                    .build();
+                radio.set_group(Some(&none_radio));
+
+                let prop = prop.clone();
+                let (base, base_id, notes_db, notes, table_webview, collapsed, i18n, pop) = (
+                    base.clone(), base_id.clone(), notes_db.clone(), notes.clone(),
+                    table_webview.clone(), collapsed.clone(), i18n.clone(), pop.clone(),
+                );
+                radio.connect_toggled(move |r| {
+                    if !r.is_active() {
+                        return;
+                    }
+                    Self::apply_group_by(
+                        Some(prop.clone()), &base, &base_id, &notes_db, &notes,
+                        &table_webview, &collapsed, &i18n,
+                    );
+                    pop.popdown();
+                });
+            }
+
+            {
+                let (base, base_id, notes_db, notes, table_webview, collapsed, i18n, pop) = (
+                    base.clone(), base_id.clone(), notes_db.clone(), notes.clone(),
+                    table_webview.clone(), collapsed.clone(), i18n.clone(), pop.clone(),
+                );
+                none_radio.connect_toggled(move |r| {
+                    if !r.is_active() {
+                        return;
+                    }
+                    Self::apply_group_by(
+                        None, &base, &base_id, &notes_db, &notes,
+                        &table_webview, &collapsed, &i18n,
+                    );
+                    pop.popdown();
+                });
+            }
+        });
+    }
+
+    /// Aplicar una elección de agrupación: persistir en la vista, limpiar el
+    /// estado de plegado y re-renderizar la tabla.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_group_by(
+        group_by: Option<String>,
+        base: &Rc<RefCell<Option<Base>>>,
+        base_id: &Rc<RefCell<Option<i64>>>,
+        notes_db: &Rc<RefCell<Option<NotesDatabase>>>,
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        table_webview: &webkit6::WebView,
+        collapsed: &Rc<RefCell<std::collections::HashSet<String>>>,
+        i18n: &Rc<RefCell<I18n>>,
+    ) {
+        collapsed.borrow_mut().clear();
+
+        let (columns, language) = {
+            let mut base_mut = base.borrow_mut();
+            let Some(base_data) = base_mut.as_mut() else { return };
+            let active = base_data.active_view;
+            if let Some(view) = base_data.views.get_mut(active) {
+                view.group_by = group_by;
+            }
+            let columns = base_data
+                .views
+                .get(active)
+                .map(|v| v.columns.clone())
+                .unwrap_or_default();
+            (columns, i18n.borrow().current_language())
+        };
+
+        // Persistir la configuración.
+        if let (Some(id), Some(db), Some(base_data)) =
+            (base_id.borrow().as_ref(), notes_db.borrow().as_ref(), base.borrow().as_ref())
+        {
+            if let Ok(yaml) = base_data.serialize() {
+                if let Err(e) = db.update_base(*id, &yaml, base_data.active_view as i32) {
+                    eprintln!("Error saving Base config: {}", e);
+                }
+            }
+        }
+
+        let group_by = base
+            .borrow()
+            .as_ref()
+            .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+        let theme = base.borrow().as_ref().and_then(|b| b.theme).unwrap_or_default();
+        let html = Self::render_table_html_static(
+            &notes.borrow(),
+            &columns,
+            language,
+            group_by.as_deref(),
+            &collapsed.borrow(),
+            base_id.borrow().unwrap_or(-1),
+            theme,
+        );
+        table_webview.load_html(&html, None);
+    }
+
+    /// Conectar el clic en un chip de etiqueta (`.tag-chip`) con un filtro
+    /// rápido `tags contains <etiqueta>`, igual que si se hubiera dado de alta
+    /// desde el popover de filtros.
+    fn setup_tag_filter_handler(&self) {
+        let Some(content_manager) = self.table_webview.user_content_manager() else {
+            return;
+        };
+        content_manager.register_script_message_handler("tagFilter", None);
+
+        let active_filters = self.active_filters.clone();
+        let filter_root = self.filter_root.clone();
+        let current_sort = self.current_sort.clone();
+        let all_notes = self.all_notes.clone();
+        let notes = self.notes.clone();
+        let list_store = self.list_store.clone();
+        let status_bar = self.status_bar.clone();
+        let table_webview = self.table_webview.clone();
+        let base = self.base.clone();
+        let base_id = self.base_id.clone();
+        let filters_container = self.filters_container.clone();
+        let i18n = self.i18n.clone();
+        let selection = self.selection.clone();
+
+        content_manager.connect_script_message_received(Some("tagFilter"), move |_, result| {
+            let tag = result.to_str().trim_matches('"').to_string();
+            if tag.is_empty() {
+                return;
+            }
+            let filter = Filter {
+                property: "tags".to_string(),
+                operator: FilterOperator::Contains,
+                value: PropertyValue::Text(tag),
+            };
+            active_filters.borrow_mut().push(filter);
+            *filter_root.borrow_mut() =
+                FilterNode::from_filters(active_filters.borrow().clone());
+
+            apply_sort_and_refresh(
+                &current_sort, &all_notes, &notes, &filter_root,
+                &list_store, &status_bar, &table_webview, &base, &base_id, &selection,
+            );
+            render_filter_tree_chips(
+                &filters_container, &filter_root, &active_filters, &current_sort,
+                &all_notes, &notes, &list_store, &status_bar, &table_webview,
+                &base, &base_id, &i18n, &selection,
+            );
+        });
+    }
+
+    /// Configurar la selección múltiple y sus acciones en lote.
+    ///
+    /// La `MultiSelection` es la fuente de verdad: el WebView alterna filas con
+    /// Ctrl/⌘+clic (mensaje `rowSelect`) y aquí reflejamos el conjunto de vuelta
+    /// al WebView (`applySelection`) y el recuento en la barra de estado. El
+    /// popover del botón de selección ofrece seleccionar todo / nada / invertir
+    /// y las acciones en lote (borrar, fijar propiedad) sobre las notas elegidas.
+    fn setup_selection(&self) {
+        // Reflejar cada cambio de selección en el WebView y la barra de estado.
+        let table_webview = self.table_webview.clone();
+        let status_bar = self.status_bar.clone();
+        let notes_for_sync = self.notes.clone();
+        let on_selection_changed = self.on_selection_changed.clone();
+        self.selection.connect_selection_changed(move |model, _, _| {
+            Self::sync_selection(model, &notes_for_sync, &table_webview, &status_bar, &on_selection_changed);
+        });
+
+        let popover = gtk::Popover::builder()
+            .css_classes(["selection-popover"])
+            .has_arrow(true)
+            .build();
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(4)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .width_request(220)
+            .build();
+        popover.set_child(Some(&content));
+        self.selection_btn.set_popover(Some(&popover));
+
+        let i18n = self.i18n.borrow();
+        let selection = self.selection.clone();
+        let table_webview = self.table_webview.clone();
+        let status_bar = self.status_bar.clone();
+
+        // Seleccionar todo / deseleccionar todo / invertir.
+        let select_all = gtk::Button::builder()
+            .label(&i18n.t("base_select_all"))
+            .css_classes(["flat"])
+            .build();
+        {
+            let selection = selection.clone();
+            select_all.connect_clicked(move |_| {
+                selection.select_all();
+            });
+        }
+        content.append(&select_all);
+
+        let deselect_all = gtk::Button::builder()
+            .label(&i18n.t("base_deselect_all"))
+            .css_classes(["flat"])
+            .build();
+        {
+            let selection = selection.clone();
+            deselect_all.connect_clicked(move |_| {
+                selection.unselect_all();
+            });
+        }
+        content.append(&deselect_all);
+
+        let invert = gtk::Button::builder()
+            .label(&i18n.t("base_invert_selection"))
+            .css_classes(["flat"])
+            .build();
+        {
+            let selection = selection.clone();
+            invert.connect_clicked(move |_| {
+                let n = selection.n_items();
+                for i in 0..n {
+                    if selection.is_selected(i) {
+                        selection.unselect_item(i);
+                    } else {
+                        selection.select_item(i, false);
+                    }
+                }
+            });
+        }
+        content.append(&invert);
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        // Borrar las notas seleccionadas.
+        let delete = gtk::Button::builder()
+            .label(&i18n.t("base_delete_selected"))
+            .css_classes(["flat", "destructive-action"])
+            .build();
+        {
+            let notes = self.notes.clone();
+            let selection = selection.clone();
+            let notes_db = self.notes_db.clone();
+            let all_notes = self.all_notes.clone();
+            let table_webview = table_webview.clone();
+            let status_bar = status_bar.clone();
+            let base = self.base.clone();
+            let base_id = self.base_id.clone();
+            let collapsed = self.collapsed_groups.clone();
+            let i18n_rc = self.i18n.clone();
+            let popover_ref = popover.clone();
+            delete.connect_clicked(move |_| {
+                let ids = Self::selected_note_ids(&selection, &notes);
+                if let Some(db) = notes_db.borrow().as_ref() {
+                    for id in &ids {
+                        if let Err(e) = db.delete_note(*id) {
+                            eprintln!("Error deleting note {}: {}", id, e);
+                        }
+                    }
+                }
+                Self::reload_after_bulk(
+                    &notes_db, &all_notes, &notes, &base, &base_id, &collapsed,
+                    &table_webview, &status_bar, &i18n_rc, &selection,
+                );
+                popover_ref.popdown();
+            });
+        }
+        content.append(&delete);
+
+        // Mover las notas seleccionadas a otra Base/cuaderno.
+        let move_to = gtk::Button::builder()
+            .label(&i18n.t("base_move_to"))
+            .css_classes(["flat"])
+            .build();
+        {
+            let notes = self.notes.clone();
+            let selection = selection.clone();
+            let notes_db = self.notes_db.clone();
+            let all_notes = self.all_notes.clone();
+            let table_webview = table_webview.clone();
+            let status_bar = status_bar.clone();
+            let base = self.base.clone();
+            let base_id = self.base_id.clone();
+            let collapsed = self.collapsed_groups.clone();
+            let i18n_rc = self.i18n.clone();
+            let popover_ref = popover.clone();
+            move_to.connect_clicked(move |btn| {
+                Self::show_move_destinations(
+                    btn, &notes_db, &base_id, &all_notes, &notes, &base,
+                    &collapsed, &table_webview, &status_bar, &i18n_rc, &selection,
+                    &popover_ref,
+                );
+            });
+        }
+        content.append(&move_to);
+
+        // Fijar una propiedad en todas las notas seleccionadas.
+        let set_row = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(4)
+            .build();
+        let props = self.available_properties.borrow().clone();
+        let prop_combo = gtk::DropDown::from_strings(
+            &props.iter().map(|s| s.as_str()).collect::<Vec<_>>()
+        );
+        let value_entry = gtk::Entry::builder()
+            .placeholder_text(&i18n.t("base_set_property_value"))
+            .hexpand(true)
+            .build();
+        let apply = gtk::Button::builder()
+            .label(&i18n.t("base_set_property"))
+            .css_classes(["flat"])
+            .build();
+        {
+            let notes = self.notes.clone();
+            let selection = selection.clone();
+            let notes_db = self.notes_db.clone();
+            let all_notes = self.all_notes.clone();
+            let table_webview = table_webview.clone();
+            let status_bar = status_bar.clone();
+            let base = self.base.clone();
+            let base_id = self.base_id.clone();
+            let collapsed = self.collapsed_groups.clone();
+            let i18n_rc = self.i18n.clone();
+            let popover_ref = popover.clone();
+            let prop_combo = prop_combo.clone();
+            let value_entry = value_entry.clone();
+            let props = props.clone();
+            apply.connect_clicked(move |_| {
+                let Some(property) = props.get(prop_combo.selected() as usize).cloned() else { return };
+                let value = value_entry.text().to_string();
+                let ids = Self::selected_note_ids(&selection, &notes);
+                if let Some(db) = notes_db.borrow().as_ref() {
+                    for id in &ids {
+                        if let Err(e) = db.set_note_property(*id, &property, &value) {
+                            eprintln!("Error setting property on note {}: {}", id, e);
+                        }
+                    }
+                }
+                Self::reload_after_bulk(
+                    &notes_db, &all_notes, &notes, &base, &base_id, &collapsed,
+                    &table_webview, &status_bar, &i18n_rc, &selection,
+                );
+                popover_ref.popdown();
+            });
+        }
+        set_row.append(&prop_combo);
+        set_row.append(&value_entry);
+        content.append(&set_row);
+        content.append(&apply);
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        // Detección de duplicados: agrupar por una propiedad y seleccionar todas
+        // las notas de cada grupo salvo la más reciente (o la más antigua).
+        let dup_label = gtk::Label::builder()
+            .label(&i18n.t("base_dedup_by"))
+            .xalign(0.0)
+            .css_classes(["dim-label"])
+            .build();
+        content.append(&dup_label);
+        let dup_combo = gtk::DropDown::from_strings(
+            &props.iter().map(|s| s.as_str()).collect::<Vec<_>>()
+        );
+        content.append(&dup_combo);
+
+        let keep_newest = gtk::Button::builder()
+            .label(&i18n.t("base_dedup_keep_newest"))
+            .css_classes(["flat"])
+            .build();
+        let keep_oldest = gtk::Button::builder()
+            .label(&i18n.t("base_dedup_keep_oldest"))
+            .css_classes(["flat"])
+            .build();
+        {
+            let notes = self.notes.clone();
+            let selection = selection.clone();
+            let dup_combo = dup_combo.clone();
+            let props = props.clone();
+            keep_newest.connect_clicked(move |_| {
+                if let Some(property) = props.get(dup_combo.selected() as usize) {
+                    Self::select_duplicates(&notes, &selection, property, true);
+                }
+            });
+        }
+        {
+            let notes = self.notes.clone();
+            let selection = selection.clone();
+            let dup_combo = dup_combo.clone();
+            let props = props.clone();
+            keep_oldest.connect_clicked(move |_| {
+                if let Some(property) = props.get(dup_combo.selected() as usize) {
+                    Self::select_duplicates(&notes, &selection, property, false);
+                }
+            });
+        }
+        content.append(&keep_newest);
+        content.append(&keep_oldest);
+    }
+
+    /// Seleccionar los duplicados de cada grupo, dejando sin seleccionar la nota
+    /// "superviviente" de cada grupo con ≥2 notas.
+    ///
+    /// Se agrupa por el `sort_key` de `property`; los grupos con una sola nota se
+    /// descartan. Dentro de cada grupo las notas se ordenan por `created` y se
+    /// seleccionan todas menos la primera: con `keep_newest` se conserva la más
+    /// reciente, en caso contrario la más antigua.
+    fn select_duplicates(
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        selection: &gtk::MultiSelection,
+        property: &str,
+        keep_newest: bool,
+    ) {
+        let notes = notes.borrow();
+        let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, note) in notes.iter().enumerate() {
+            let key = note
+                .properties
+                .get(property)
+                .map(|v| v.sort_key())
+                .unwrap_or_default();
+            groups.entry(key).or_default().push(idx);
+        }
+
+        selection.unselect_all();
+        for (_, mut bucket) in groups {
+            if bucket.len() < 2 {
+                continue;
+            }
+            // Orden por `created`: descendente para conservar la más reciente,
+            // ascendente para conservar la más antigua. El superviviente queda
+            // en la posición 0; el resto se selecciona.
+            bucket.sort_by(|&a, &b| {
+                let ca = notes[a].metadata.created_at;
+                let cb = notes[b].metadata.created_at;
+                if keep_newest { cb.cmp(&ca) } else { ca.cmp(&cb) }
+            });
+            for &idx in &bucket[1..] {
+                selection.select_item(idx as u32, false);
+            }
+        }
+    }
+
+    /// Reflejar la `MultiSelection` en el WebView (`applySelection`), en el
+    /// recuento "N de M seleccionadas" de la barra de estado, y notificar a
+    /// quien escuche [`BaseTableWidget::on_selection_changed`].
+    fn sync_selection(
+        model: &gtk::MultiSelection,
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        table_webview: &webkit6::WebView,
+        status_bar: &gtk::Box,
+        on_selection_changed: &Rc<RefCell<Option<Box<dyn Fn(&[String])>>>>,
+    ) {
+        let mut indices: Vec<u32> = Vec::new();
+        for i in 0..model.n_items() {
+            if model.is_selected(i) {
+                indices.push(i);
+            }
+        }
+
+        let list = indices
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let script = format!("if (window.applySelection) applySelection([{list}]);");
+        table_webview.evaluate_javascript(&script, None, None, None::<&gio::Cancellable>, |_| {});
+
+        let total = model.n_items();
+        if let Some(label) = status_bar.observe_children().item(1).and_downcast::<gtk::Label>() {
+            if indices.is_empty() {
+                label.set_text("");
+            } else {
+                label.set_text(&format!("{} of {} selected", indices.len(), total));
+            }
+        }
+
+        if let Some(ref callback) = *on_selection_changed.borrow() {
+            let ids: Vec<String> = Self::selected_note_ids(model, notes)
+                .into_iter()
+                .map(|id| id.to_string())
+                .collect();
+            callback(&ids);
+        }
+    }
+
+    /// `id` de las notas actualmente seleccionadas, mapeando las posiciones del
+    /// modelo de selección a `notes` (que conserva el mismo orden que la tabla).
+    fn selected_note_ids(
+        selection: &gtk::MultiSelection,
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    ) -> Vec<i64> {
+        let notes = notes.borrow();
+        let mut ids = Vec::new();
+        for i in 0..selection.n_items() {
+            if selection.is_selected(i) {
+                if let Some(note) = notes.get(i as usize) {
+                    ids.push(note.metadata.id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Recargar las notas desde la base de datos tras una acción en lote y
+    /// re-renderizar la tabla, limpiando la selección (ya no es válida).
+    #[allow(clippy::too_many_arguments)]
+    fn reload_after_bulk(
+        notes_db: &Rc<RefCell<Option<NotesDatabase>>>,
+        all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        base: &Rc<RefCell<Option<Base>>>,
+        base_id: &Rc<RefCell<Option<i64>>>,
+        collapsed: &Rc<RefCell<std::collections::HashSet<String>>>,
+        table_webview: &webkit6::WebView,
+        status_bar: &gtk::Box,
+        i18n: &Rc<RefCell<I18n>>,
+        selection: &gtk::MultiSelection,
+    ) {
+        let records = match notes_db.borrow().as_ref() {
+            Some(db) => match db.get_all_grouped_records() {
+                Ok(records) => records,
+                Err(e) => {
+                    eprintln!("Error reloading records: {}", e);
+                    return;
+                }
+            },
+            None => return,
+        };
+
+        let reloaded: Vec<NoteWithProperties> = records.iter().map(|r| {
+            let mut properties = HashMap::new();
+            properties.insert("_note".to_string(), PropertyValue::Text(r.note_name.clone()));
+            for (k, v) in &r.properties {
+                properties.insert(k.clone(), PropertyValue::Text(v.clone()));
+            }
+            let metadata = NoteMetadata {
+                id: r.note_id,
+                name: r.note_name.clone(),
+                path: String::new(),
+                folder: None,
+                order_index: 0,
+                icon: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            };
+            NoteWithProperties { metadata, properties, content: None }
+        }).collect();
+
+        *all_notes.borrow_mut() = reloaded.clone();
+        *notes.borrow_mut() = reloaded.clone();
+        selection.unselect_all();
+
+        let (columns, group_by, language, theme) = {
+            let base_ref = base.borrow();
+            let columns = base_ref
+                .as_ref()
+                .and_then(|b| b.active_view().map(|v| v.columns.clone()))
+                .unwrap_or_default();
+            let group_by = base_ref
+                .as_ref()
+                .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+            let theme = base_ref.as_ref().and_then(|b| b.theme).unwrap_or_default();
+            (columns, group_by, i18n.borrow().current_language(), theme)
+        };
+
+        let html = Self::render_table_html_static(
+            &reloaded,
+            &columns,
+            language,
+            group_by.as_deref(),
+            &collapsed.borrow(),
+            base_id.borrow().unwrap_or(-1),
+            theme,
+        );
+        table_webview.load_html(&html, None);
+
+        if let Some(label) = status_bar.first_child().and_downcast::<gtk::Label>() {
+            let text = if reloaded.len() == 1 {
+                "1 note".to_string()
+            } else {
+                format!("{} notes", reloaded.len())
+            };
+            label.set_text(&text);
+        }
+    }
+
+    /// Mostrar un popover con las Bases/cuadernos de destino para mover las
+    /// notas seleccionadas.
+    ///
+    /// Las destinos se consultan a `NotesDatabase` (excluyendo la Base actual).
+    /// Al elegir uno se mueve cada nota con `move_note_to_base`; los fallos por
+    /// nota se registran sin abortar el lote, y las propiedades que la vista
+    /// destino espere pero la nota no tenga quedan simplemente vacías.
+    #[allow(clippy::too_many_arguments)]
+    fn show_move_destinations(
+        anchor: &gtk::Button,
+        notes_db: &Rc<RefCell<Option<NotesDatabase>>>,
+        base_id: &Rc<RefCell<Option<i64>>>,
+        all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+        base: &Rc<RefCell<Option<Base>>>,
+        collapsed: &Rc<RefCell<std::collections::HashSet<String>>>,
+        table_webview: &webkit6::WebView,
+        status_bar: &gtk::Box,
+        i18n: &Rc<RefCell<I18n>>,
+        selection: &gtk::MultiSelection,
+        parent_popover: &gtk::Popover,
+    ) {
+        let current = *base_id.borrow();
+        let destinations: Vec<(i64, String)> = match notes_db.borrow().as_ref() {
+            Some(db) => match db.list_bases() {
+                Ok(bases) => bases.into_iter().filter(|(id, _)| Some(*id) != current).collect(),
+                Err(e) => {
+                    eprintln!("Error listing destinations: {}", e);
+                    return;
+                }
+            },
+            None => return,
+        };
+
+        let popover = gtk::Popover::builder()
+            .css_classes(["move-to-popover"])
+            .has_arrow(true)
+            .build();
+        popover.set_parent(anchor);
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(4)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .width_request(200)
+            .build();
+        popover.set_child(Some(&content));
+
+        if destinations.is_empty() {
+            content.append(&gtk::Label::new(Some(&i18n.borrow().t("base_move_no_destinations"))));
+        }
+
+        for (target_id, name) in destinations {
+            let button = gtk::Button::builder()
+                .label(&name)
+                .css_classes(["flat"])
+                .build();
+            let notes_db = notes_db.clone();
+            let all_notes = all_notes.clone();
+            let notes = notes.clone();
+            let base = base.clone();
+            let base_id = base_id.clone();
+            let collapsed = collapsed.clone();
+            let table_webview = table_webview.clone();
+            let status_bar = status_bar.clone();
+            let i18n = i18n.clone();
+            let selection = selection.clone();
+            let popover_ref = popover.clone();
+            let parent_popover = parent_popover.clone();
+            button.connect_clicked(move |_| {
+                let ids = Self::selected_note_ids(&selection, &notes);
+                let mut failed = 0usize;
+                if let Some(db) = notes_db.borrow().as_ref() {
+                    for id in &ids {
+                        if let Err(e) = db.move_note_to_base(*id, target_id) {
+                            eprintln!("Error moving note {}: {}", id, e);
+                            failed += 1;
+                        }
+                    }
+                }
+                if failed > 0 {
+                    eprintln!("{} of {} notes could not be moved", failed, ids.len());
+                }
+                // Las notas movidas desaparecen de la Base actual al recargar.
+                Self::reload_after_bulk(
+                    &notes_db, &all_notes, &notes, &base, &base_id, &collapsed,
+                    &table_webview, &status_bar, &i18n, &selection,
+                );
+                popover_ref.popdown();
+                parent_popover.popdown();
+            });
+            content.append(&button);
+        }
+
+        popover.connect_closed(|popover| {
+            popover.unparent();
+        });
+        popover.popup();
+    }
+
+    /// Configurar el popover de columnas (se regenera cada vez que se abre)
+    fn setup_columns_popover(&self) {
+        // Usar referencia directa al botón de columnas
+        let columns_btn = &self.columns_btn;
         
+        // Clonar referencias necesarias
+        let base_ref = self.base.clone();
+        let base_id = self.base_id.clone();
+        let notes_db = self.notes_db.clone();
+        let column_view = self.column_view.clone();
+        let available_props = self.available_properties.clone();
+        let table_webview = self.table_webview.clone();
+        let notes = self.notes.clone();
+        let i18n = self.i18n.clone();
+        let collapsed_groups = self.collapsed_groups.clone();
+
         // Crear el popover una vez
         let popover = gtk::Popover::builder()
             .css_classes(["columns-popover"])
@@ -931,12 +2363,15 @@ impl BaseTableWidget {
                     &table_webview,
                     &notes,
                     &i18n.borrow(),
+                    &collapsed_groups,
+                    &i18n,
                 );
             }
         });
     }
     
     /// Refrescar el contenido del popover de columnas (sin cerrarlo)
+    #[allow(clippy::too_many_arguments)]
     fn refresh_columns_popover_content(
         content: &gtk::Box,
         base_ref: &Rc<RefCell<Option<Base>>>,
@@ -948,6 +2383,8 @@ impl BaseTableWidget {
         table_webview: &webkit6::WebView,
         notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
         i18n: &I18n,
+        collapsed: &Rc<RefCell<std::collections::HashSet<String>>>,
+        i18n_rc: &Rc<RefCell<I18n>>,
     ) {
         // Limpiar contenido existente
         while let Some(child) = content.first_child() {
@@ -1014,7 +2451,9 @@ impl BaseTableWidget {
                         let available_props_for_refresh = available_props_vec.clone();
                         let table_webview_clone = table_webview.clone();
                         let notes_clone = notes.clone();
-                        
+                        let collapsed_for_refresh = collapsed.clone();
+                        let i18n_rc_for_refresh = i18n_rc.clone();
+
                         remove_btn.connect_clicked(move |_| {
                             let columns_for_html: Vec<ColumnConfig>;
                             {
@@ -1025,8 +2464,11 @@ impl BaseTableWidget {
                                             view.columns.remove(col_idx);
                                             
                                             // Actualizar ColumnView inmediatamente
-                                            Self::rebuild_column_view(&column_view_clone, &view.columns);
-                                            
+                                            Self::rebuild_column_view(
+                                                &column_view_clone, &view.columns,
+                                                &base_ref_clone, &base_id_clone, &notes_db_clone,
+                                            );
+
                                             columns_for_html = view.columns.clone();
                                             
                                             // Persistir
@@ -1038,7 +2480,12 @@ impl BaseTableWidget {
                                             
                                             // Actualizar WebView
                                             let notes_borrowed = notes_clone.borrow();
-                                            let html = Self::render_table_html_static(&notes_borrowed, &columns_for_html, Language::from_env());
+                                            let html = Self::render_table_html_static(
+                                                &notes_borrowed, &columns_for_html, Language::from_env(),
+                                                view.group_by.as_deref(), &std::collections::HashSet::new(),
+                                                base_id_clone.borrow().unwrap_or(-1),
+                                                base.theme.unwrap_or_default(),
+                                            );
                                             table_webview_clone.load_html(&html, None);
                                         }
                                     }
@@ -1055,10 +2502,12 @@ impl BaseTableWidget {
                                 &popover_for_refresh,
                                 &table_webview_clone,
                                 &notes_clone,
-                                &I18n::new(Language::from_env()),
+                                &i18n_rc_for_refresh.borrow(),
+                                &collapsed_for_refresh,
+                                &i18n_rc_for_refresh,
                             );
                         });
-                        
+
                         row.append(&remove_btn);
                     }
                     
@@ -1078,11 +2527,19 @@ impl BaseTableWidget {
                                     col_config.visible = btn.is_active();
                                     
                                     // Reconstruir ColumnView para reflejar cambio
-                                    Self::rebuild_column_view(&column_view_clone, &view.columns);
+                                    Self::rebuild_column_view(
+                                        &column_view_clone, &view.columns,
+                                        &base_ref_clone, &base_id_clone, &notes_db_clone,
+                                    );
                                     
                                     // Actualizar WebView
                                     let notes_borrowed = notes_clone.borrow();
-                                    let html = Self::render_table_html_static(&notes_borrowed, &view.columns, Language::from_env());
+                                    let html = Self::render_table_html_static(
+                                        &notes_borrowed, &view.columns, Language::from_env(),
+                                        view.group_by.as_deref(), &std::collections::HashSet::new(),
+                                        base_id_clone.borrow().unwrap_or(-1),
+                                        base.theme.unwrap_or_default(),
+                                    );
                                     table_webview_clone.load_html(&html, None);
                                     
                                     // Persistir
@@ -1157,7 +2614,9 @@ impl BaseTableWidget {
                         let available_props_for_refresh = available_props_vec.clone();
                         let table_webview_clone = table_webview.clone();
                         let notes_clone = notes.clone();
-                        
+                        let collapsed_for_refresh = collapsed.clone();
+                        let i18n_rc_for_refresh = i18n_rc.clone();
+
                         add_btn.connect_clicked(move |_| {
                             let columns_for_html: Vec<ColumnConfig>;
                             {
@@ -1168,8 +2627,11 @@ impl BaseTableWidget {
                                         view.columns.push(ColumnConfig::new(&prop_clone));
                                         
                                         // Actualizar ColumnView inmediatamente
-                                        Self::rebuild_column_view(&column_view_clone, &view.columns);
-                                        
+                                        Self::rebuild_column_view(
+                                            &column_view_clone, &view.columns,
+                                            &base_ref_clone, &base_id_clone, &notes_db_clone,
+                                        );
+
                                         columns_for_html = view.columns.clone();
                                         
                                         // Persistir
@@ -1181,7 +2643,12 @@ impl BaseTableWidget {
                                         
                                         // Actualizar WebView
                                         let notes_borrowed = notes_clone.borrow();
-                                        let html = Self::render_table_html_static(&notes_borrowed, &columns_for_html, Language::from_env());
+                                        let html = Self::render_table_html_static(
+                                            &notes_borrowed, &columns_for_html, Language::from_env(),
+                                            view.group_by.as_deref(), &std::collections::HashSet::new(),
+                                            base_id_clone.borrow().unwrap_or(-1),
+                                            base.theme.unwrap_or_default(),
+                                        );
                                         table_webview_clone.load_html(&html, None);
                                     }
                                 }
@@ -1197,17 +2664,96 @@ impl BaseTableWidget {
                                 &popover_for_refresh,
                                 &table_webview_clone,
                                 &notes_clone,
-                                &I18n::new(Language::from_env()),
+                                &i18n_rc_for_refresh.borrow(),
+                                &collapsed_for_refresh,
+                                &i18n_rc_for_refresh,
                             );
                         });
                         
                         content.append(&row);
                     }
                 }
+
+                // === Sección: Agrupar por ===
+                // Permite cambiar la agrupación sin salir del popover de columnas.
+                let group_separator = gtk::Separator::new(gtk::Orientation::Horizontal);
+                group_separator.set_margin_top(8);
+                group_separator.set_margin_bottom(8);
+                content.append(&group_separator);
+
+                let group_title = gtk::Label::builder()
+                    .label(&i18n.t("base_group_by"))
+                    .css_classes(["heading"])
+                    .xalign(0.0)
+                    .margin_bottom(4)
+                    .build();
+                content.append(&group_title);
+
+                let current_group_by = view.group_by.clone();
+
+                let none_radio = gtk::CheckButton::builder()
+                    .label(&i18n.t("base_group_none"))
+                    .active(current_group_by.is_none())
+                    .build();
+                content.append(&none_radio);
+
+                let mut group_radios: Vec<gtk::CheckButton> = Vec::new();
+                for prop in available_props.iter() {
+                    let radio = gtk::CheckButton::builder()
+                        .label(&Self::format_column_header(prop, i18n.current_language()))
+                        .active(current_group_by.as_deref() == Some(prop.as_str()))
+                        .build();
+                    radio.set_group(Some(&none_radio));
+                    content.append(&radio);
+                    group_radios.push(radio);
+                }
+
+                {
+                    let base_ref_clone = base_ref.clone();
+                    let base_id_clone = base_id.clone();
+                    let notes_db_clone = notes_db.clone();
+                    let notes_clone = notes.clone();
+                    let table_webview_clone = table_webview.clone();
+                    let collapsed_clone = collapsed.clone();
+                    let i18n_rc_clone = i18n_rc.clone();
+                    let popover_clone = popover.clone();
+                    none_radio.connect_toggled(move |r| {
+                        if !r.is_active() {
+                            return;
+                        }
+                        Self::apply_group_by(
+                            None, &base_ref_clone, &base_id_clone, &notes_db_clone, &notes_clone,
+                            &table_webview_clone, &collapsed_clone, &i18n_rc_clone,
+                        );
+                        popover_clone.popdown();
+                    });
+                }
+
+                for (radio, prop) in group_radios.into_iter().zip(available_props.iter()) {
+                    let prop = prop.clone();
+                    let base_ref_clone = base_ref.clone();
+                    let base_id_clone = base_id.clone();
+                    let notes_db_clone = notes_db.clone();
+                    let notes_clone = notes.clone();
+                    let table_webview_clone = table_webview.clone();
+                    let collapsed_clone = collapsed.clone();
+                    let i18n_rc_clone = i18n_rc.clone();
+                    let popover_clone = popover.clone();
+                    radio.connect_toggled(move |r| {
+                        if !r.is_active() {
+                            return;
+                        }
+                        Self::apply_group_by(
+                            Some(prop.clone()), &base_ref_clone, &base_id_clone, &notes_db_clone, &notes_clone,
+                            &table_webview_clone, &collapsed_clone, &i18n_rc_clone,
+                        );
+                        popover_clone.popdown();
+                    });
+                }
             }
         }
     }
-    
+
     /// Configurar el popover para cambiar el modo de datos (Notes/GroupedRecords)
     fn setup_source_type_popover(&self) {
         let popover = gtk::Popover::builder()
@@ -1346,61 +2892,48 @@ impl BaseTableWidget {
     
     /// Actualizar los chips de filtros activos
     fn update_filter_chips(&self) {
-        // Limpiar chips existentes
-        while let Some(child) = self.filters_container.first_child() {
-            self.filters_container.remove(&child);
-        }
-        
-        let filters = self.active_filters.borrow();
-        
-        if filters.is_empty() {
-            // Mostrar placeholder
-            let placeholder = gtk::Label::builder()
-                .label(&self.i18n.borrow().t("base_no_filters"))
-                .css_classes(["dim-label"])
-                .build();
-            self.filters_container.append(&placeholder);
-        } else {
-            // Crear chips para cada filtro
-            for (i, filter) in filters.iter().enumerate() {
-                let chip = create_filter_chip(filter, i);
-                
-                // Conectar botón de cerrar
-                let active_filters = self.active_filters.clone();
-                let all_notes = self.all_notes.clone();
-                let notes = self.notes.clone();
-                let current_sort = self.current_sort.clone();
-                let list_store = self.list_store.clone();
-                let status_bar = self.status_bar.clone();
-                let filters_container = self.filters_container.clone();
-                
-                if let Some(close_btn) = chip.last_child().and_downcast::<gtk::Button>() {
-                    close_btn.connect_clicked(move |_| {
-                        // Eliminar filtro
-                        let mut filters = active_filters.borrow_mut();
-                        if i < filters.len() {
-                            filters.remove(i);
-                        }
-                        drop(filters);
-                        
-                        // Re-aplicar filtros (esto debería llamar a apply_filters_and_sort pero
-                        // necesitamos acceso a self, así que por ahora solo refrescamos los datos)
-                        // TODO: Refactorizar para mejor manejo de estado
-                    });
-                }
-                
-                self.filters_container.append(&chip);
-            }
-        }
+        render_filter_tree_chips(
+            &self.filters_container,
+            &self.filter_root,
+            &self.active_filters,
+            &self.current_sort,
+            &self.all_notes,
+            &self.notes,
+            &self.list_store,
+            &self.status_bar,
+            &self.table_webview,
+            &self.base,
+            &self.base_id,
+            &self.i18n,
+            &self.selection,
+        );
     }
 
     /// Actualizar las columnas del ColumnView
     fn update_columns(&self, columns: &[ColumnConfig]) {
-        Self::rebuild_column_view(&self.column_view, columns);
+        Self::rebuild_column_view(
+            &self.column_view,
+            columns,
+            &self.base,
+            &self.base_id,
+            &self.notes_db,
+        );
     }
-    
+
     /// Reconstruir las columnas de un ColumnView (función estática para usar en callbacks)
-    fn rebuild_column_view(column_view: &gtk::ColumnView, columns: &[ColumnConfig]) {
+    ///
+    /// Tras (re)crear las columnas visibles se conecta en cada una un manejador
+    /// de `fixed-width` que persiste el nuevo ancho en la Base: así redimensionar
+    /// una columna sobrevive a las recargas. El cableado vive aquí, el único
+    /// embudo por el que pasan todas las reconstrucciones, para que cualquier
+    /// ruta (cambio de vista, alta/baja de columna) herede la persistencia.
+    fn rebuild_column_view(
+        column_view: &gtk::ColumnView,
+        columns: &[ColumnConfig],
+        base: &Rc<RefCell<Option<Base>>>,
+        base_id: &Rc<RefCell<Option<i64>>>,
+        notes_db: &Rc<RefCell<Option<NotesDatabase>>>,
+    ) {
         // Limpiar columnas existentes
         while let Some(col) = column_view.columns().item(0) {
             if let Some(column) = col.downcast_ref::<gtk::ColumnViewColumn>() {
@@ -1460,6 +2993,62 @@ impl BaseTableWidget {
 
             column_view.append_column(&column);
         }
+
+        // Persistir el ancho de cada columna al redimensionarla. Las columnas
+        // del `ColumnView` son solo las visibles y en el mismo orden en que se
+        // añadieron, así que su posición basta para localizar el `ColumnConfig`
+        // entre las columnas visibles de la vista activa.
+        let view_columns = column_view.columns();
+        for pos in 0..view_columns.n_items() {
+            let Some(column) = view_columns
+                .item(pos)
+                .and_downcast::<gtk::ColumnViewColumn>()
+            else {
+                continue;
+            };
+            let base = base.clone();
+            let base_id = base_id.clone();
+            let notes_db = notes_db.clone();
+            let visible_pos = pos as usize;
+            column.connect_notify_local(Some("fixed-width"), move |col, _| {
+                let width = col.fixed_width();
+                if width <= 0 {
+                    return;
+                }
+                // `try_borrow_mut` evita un pánico si la notificación llega
+                // mientras la Base ya está prestada (p. ej. durante un redibujo).
+                let Ok(mut base_ref) = base.try_borrow_mut() else {
+                    return;
+                };
+                let Some(base) = base_ref.as_mut() else {
+                    return;
+                };
+                let Some(view) = base.views.get_mut(base.active_view) else {
+                    return;
+                };
+                let target = view
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.visible)
+                    .nth(visible_pos)
+                    .map(|(idx, _)| idx);
+                let Some(idx) = target else {
+                    return;
+                };
+                if view.columns[idx].width == Some(width as u32) {
+                    return;
+                }
+                view.columns[idx].width = Some(width as u32);
+                if let (Some(id), Some(db)) =
+                    (base_id.borrow().as_ref(), notes_db.borrow().as_ref())
+                {
+                    if let Ok(yaml) = base.serialize() {
+                        let _ = db.update_base(*id, &yaml, base.active_view as i32);
+                    }
+                }
+            });
+        }
     }
 
     /// Actualizar los datos de la tabla usando el WebView
@@ -1489,14 +3078,36 @@ impl BaseTableWidget {
     
     /// Generar el HTML para la tabla
     fn render_table_html(&self, notes: &[NoteWithProperties], columns: &[ColumnConfig]) -> String {
-        Self::render_table_html_static(notes, columns, self.i18n.borrow().current_language())
+        let group_by = self
+            .base
+            .borrow()
+            .as_ref()
+            .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+        Self::render_table_html_static(
+            notes,
+            columns,
+            self.i18n.borrow().current_language(),
+            group_by.as_deref(),
+            &self.collapsed_groups.borrow(),
+            self.base_id.borrow().unwrap_or(-1),
+            self.current_theme(),
+        )
     }
-    
+
     /// Generar el HTML para la tabla (versión estática para usar en closures)
-    fn render_table_html_static(notes: &[NoteWithProperties], columns: &[ColumnConfig], language: Language) -> String {
-        let is_dark = Self::is_dark_theme();
+    #[allow(clippy::too_many_arguments)]
+    fn render_table_html_static(
+        notes: &[NoteWithProperties],
+        columns: &[ColumnConfig],
+        language: Language,
+        group_by: Option<&str>,
+        collapsed: &std::collections::HashSet<String>,
+        base_id: i64,
+        theme: BaseTheme,
+    ) -> String {
+        let is_dark = theme.is_dark();
         let theme_class = if is_dark { "dark" } else { "light" };
-        
+
         // Traducciones para el HTML
         let (search_placeholder, items_label, no_notes_label) = if language == Language::Spanish {
             ("Buscar en tabla...", "elementos", "No se encontraron notas")
@@ -1593,6 +3204,28 @@ tr:hover {
     font-size: 0.9em;
 }
 
+.select-th, .select-cell {
+    width: 32px;
+    text-align: center;
+}
+
+.row-checkbox {
+    cursor: pointer;
+}
+
+.tag-chip {
+    display: inline-block;
+    margin: 0 4px 2px 0;
+    padding: 1px 8px;
+    border-radius: 999px;
+    font-size: 0.85em;
+    cursor: pointer;
+}
+
+.tag-chip:hover {
+    text-decoration: underline;
+}
+
 .empty-state {
     text-align: center;
     padding: 48px;
@@ -1654,6 +3287,21 @@ tr:hover {
     color: var(--fg-muted);
 }
 
+/* Idiomas RTL: espejar el icono y el padding del buscador */
+html[dir="rtl"] .search-input {
+    padding: 8px 36px 8px 12px;
+}
+
+html[dir="rtl"] .search-icon {
+    left: auto;
+    right: 12px;
+}
+
+html[dir="rtl"] .search-results-count {
+    margin-left: 0;
+    margin-right: 12px;
+}
+
 tr.hidden-by-search {
     display: none;
 }
@@ -1661,52 +3309,352 @@ tr.hidden-by-search {
 tr.search-highlight td {
     background-color: rgba(137, 180, 250, 0.15);
 }
+
+tr.selected td {
+    background-color: rgba(137, 180, 250, 0.28);
+}
+
+/* Secciones de grupo (group by) */
+tr.group-header td {
+    background-color: var(--bg-tertiary);
+    font-weight: 600;
+    color: var(--fg-primary);
+    cursor: pointer;
+    user-select: none;
+}
+
+tr.group-header .group-caret {
+    display: inline-block;
+    width: 1em;
+    transition: transform 0.15s ease;
+}
+
+tr.group-header.collapsed .group-caret {
+    transform: rotate(-90deg);
+}
+
+tr.group-header .group-count {
+    color: var(--fg-muted);
+    font-weight: 400;
+    margin-left: 8px;
+}
+
+tr.group-header .group-agg {
+    color: var(--fg-secondary);
+    font-weight: 500;
+}
 "#;
         
         let mut html = format!(r#"<!DOCTYPE html>
-<html>
+<html dir="{}">
 <head>
     <meta charset="UTF-8">
     <style>{}</style>
 </head>
 <body class="{}">
 <script>
-// Función de búsqueda en la tabla
+// Función de búsqueda en la tabla. Delega en applySearch para que la caja de
+// búsqueda HTML embebida y la SearchEntry de GTK compartan una única
+// implementación (mismo resaltado, mismo contador, mismo conjunto de filas).
 function filterTable(query) {{
+    applySearch(query);
+}}
+
+// Búsqueda full-text incremental con ranking BM25 inyectada desde la
+// SearchEntry de GTK y desde la caja embebida. Puntúa cada fila contra el
+// índice invertido embebido (`__searchIndex`), reordena el tbody por
+// relevancia descendente, oculta las filas con puntuación cero, resalta los
+// términos y publica el recuento "N de M" en la barra de estado de Rust.
+var __searchHits = [];
+var __searchCurrent = -1;
+var __origOrder = null;
+
+// Búsqueda en segundo plano: la tokenización/ranking se delega en un Web
+// Worker para que no bloquee el hilo principal en bases grandes. El input se
+// debouncea y cada consulta lleva un número de secuencia para descartar
+// respuestas obsoletas; si `Worker` no está disponible se usa el camino inline.
+var __worker = null;
+var __searchSeq = 0;
+var __debounceTimer = null;
+var __lastQuery = '';
+
+var STOPWORDS = {{}};
+{}.forEach(function(w) {{ STOPWORDS[w] = true; }});
+
+// Tokenizar igual que el indexador de Rust (`search_tokenize`): minúsculas,
+// corte por no-alfanuméricos unicode y descarte de palabras vacías.
+function __tokenize(s) {{
+    var out = [];
+    var parts = s.toLowerCase().split(/[^\p{{L}}\p{{N}}]+/u);
+    for (var i = 0; i < parts.length; i++) {{
+        var t = parts[i];
+        if (t && !STOPWORDS[t]) out.push(t);
+    }}
+    return out;
+}}
+
+function __escapeHtml(s) {{
+    return s.replace(/[&<>]/g, function(c) {{
+        return {{'&':'&amp;','<':'&lt;','>':'&gt;'}}[c];
+    }});
+}}
+
+function __reEscape(s) {{ return s.replace(/[.*+?^${{}}()|[\]\\]/g, '\\$&'); }}
+
+// Resaltar en cada celda de la fila todas las ocurrencias de los términos.
+function __highlightRow(row, terms) {{
+    if (!terms || terms.length === 0) return;
+    var re = new RegExp('(' + terms.map(__reEscape).join('|') + ')', 'giu');
+    row.querySelectorAll('td').forEach(function(td) {{
+        var parts = td.dataset.raw.split(re);
+        var html = '';
+        for (var i = 0; i < parts.length; i++) {{
+            if (i % 2 === 1) html += '<mark>' + __escapeHtml(parts[i]) + '</mark>';
+            else html += __escapeHtml(parts[i]);
+        }}
+        td.innerHTML = html;
+    }});
+}}
+
+function __updateCount(hits, total) {{
+    var countEl = document.getElementById('search-count');
+    if (countEl) countEl.textContent = hits + ' of ' + total + ' items';
+    if (window.webkit && window.webkit.messageHandlers.searchCount) {{
+        window.webkit.messageHandlers.searchCount.postMessage(hits + '/' + total);
+    }}
+}}
+
+// Cuerpo del Web Worker: recibe el índice y resuelve cada consulta con el
+// mismo BM25 por prefijo que el camino inline. Se serializa con toString()
+// para construir el Blob, así que no puede cerrar sobre nada del hilo
+// principal. Devuelve la lista ordenada de índices de fila (data-idx).
+function __workerBootstrap() {{
+    var IDX = null, STOP = {{}};
+    function tok(s) {{
+        var out = [], parts = s.toLowerCase().split(/[^\p{{L}}\p{{N}}]+/u);
+        for (var i = 0; i < parts.length; i++) {{
+            var t = parts[i];
+            if (t && !STOP[t]) out.push(t);
+        }}
+        return out;
+    }}
+    function rank(q) {{
+        var terms = tok(q);
+        if (!IDX || terms.length === 0) return [];
+        var k1 = 1.2, b = 0.75, scores = {{}};
+        terms.forEach(function(t) {{
+            for (var k in IDX.postings) {{
+                if (k.lastIndexOf(t, 0) !== 0) continue;
+                var post = IDX.postings[k], df = post.length;
+                var idf = Math.log(1 + (IDX.N - df + 0.5) / (df + 0.5));
+                post.forEach(function(p) {{
+                    var row = p[0], tf = p[1], dl = IDX.docLen[row] || 0;
+                    var den = tf + k1 * (1 - b + b * dl / (IDX.avgdl || 1));
+                    scores[row] = (scores[row] || 0) + idf * (tf * (k1 + 1)) / (den || 1);
+                }});
+            }}
+        }});
+        return Object.keys(scores)
+            .map(function(r) {{ return {{ id: parseInt(r, 10), s: scores[r] }}; }})
+            .filter(function(e) {{ return e.s > 0; }})
+            .sort(function(a, b) {{ return b.s - a.s; }})
+            .map(function(e) {{ return e.id; }});
+    }}
+    self.onmessage = function(e) {{
+        var d = e.data;
+        if (d.type === 'init') {{ IDX = d.index; STOP = d.stop; return; }}
+        if (d.type === 'query') {{ self.postMessage({{ seq: d.seq, q: d.q, ids: rank(d.q) }}); }}
+    }};
+}}
+var __WORKER_SRC = '(' + __workerBootstrap.toString() + ')()';
+
+// Crear (una vez) el worker a partir de un Blob y sembrarlo con el índice.
+// Devuelve null si `Worker` no existe o si no hay índice embebido.
+function __initWorker() {{
+    if (__worker !== null) return __worker;
+    if (typeof Worker === 'undefined' || typeof __searchIndex === 'undefined') return null;
+    try {{
+        var blob = new Blob([__WORKER_SRC], {{ type: 'application/javascript' }});
+        __worker = new Worker(URL.createObjectURL(blob));
+        __worker.postMessage({{ type: 'init', index: __searchIndex, stop: STOPWORDS }});
+        __worker.onmessage = function(e) {{
+            if (e.data.seq !== __searchSeq) return; // descartar respuesta obsoleta
+            // Resaltar con los términos de la consulta que generó esta respuesta
+            // (el worker los devuelve), no con __lastQuery, que ya pudo cambiar.
+            __applyRankedOrder(e.data.ids, __tokenize(e.data.q));
+        }};
+    }} catch (err) {{
+        __worker = null;
+    }}
+    return __worker;
+}}
+
+// Ranking inline (camino de respaldo sin worker). Con índice usa BM25 por
+// prefijo; sin índice cae a una coincidencia por subcadena de todos los
+// términos. Devuelve los índices de fila ordenados por relevancia.
+function __rankInline(terms, rows) {{
+    var idx = (typeof __searchIndex !== 'undefined') ? __searchIndex : null;
+    if (idx) {{
+        var k1 = 1.2, b = 0.75, scores = {{}};
+        terms.forEach(function(t) {{
+            for (var k in idx.postings) {{
+                if (k.lastIndexOf(t, 0) !== 0) continue;
+                var post = idx.postings[k], df = post.length;
+                var idfv = Math.log(1 + (idx.N - df + 0.5) / (df + 0.5));
+                post.forEach(function(p) {{
+                    var row = p[0], tf = p[1], dl = idx.docLen[row] || 0;
+                    var denom = tf + k1 * (1 - b + b * dl / (idx.avgdl || 1));
+                    scores[row] = (scores[row] || 0) + idfv * (tf * (k1 + 1)) / (denom || 1);
+                }});
+            }}
+        }});
+        return Object.keys(scores)
+            .map(function(r) {{ return {{ id: parseInt(r, 10), s: scores[r] }}; }})
+            .filter(function(e) {{ return e.s > 0; }})
+            .sort(function(a, b) {{ return b.s - a.s; }})
+            .map(function(e) {{ return e.id; }});
+    }}
+    var ids = [];
+    rows.forEach(function(row) {{
+        var hay = row.textContent.toLowerCase();
+        if (terms.every(function(t) {{ return hay.indexOf(t) >= 0; }})) {{
+            ids.push(parseInt(row.dataset.idx, 10));
+        }}
+    }});
+    return ids;
+}}
+
+// Aplicar al DOM la lista ordenada de índices de fila: mostrar y resaltar las
+// filas coincidentes, ocultar el resto, reordenar el tbody por relevancia
+// (salvo en vista agrupada) y publicar el recuento.
+function __applyRankedOrder(ids, terms) {{
     var tbody = document.querySelector('tbody');
     if (!tbody) return;
-    
     var rows = tbody.querySelectorAll('tr[data-path]');
-    var count = 0;
-    var total = rows.length;
-    var searchLower = query.toLowerCase().trim();
-    
+    var order = {{}};
+    ids.forEach(function(id, i) {{ order[id] = i; }});
+
+    var byId = {{}};
     rows.forEach(function(row) {{
-        if (searchLower === '') {{
+        var ri = parseInt(row.dataset.idx, 10);
+        if (order.hasOwnProperty(ri)) {{
             row.classList.remove('hidden-by-search');
-            row.classList.remove('search-highlight');
-            count++;
+            row.classList.add('search-highlight');
+            __highlightRow(row, terms);
+            byId[ri] = row;
         }} else {{
-            var text = row.textContent.toLowerCase();
-            if (text.includes(searchLower)) {{
-                row.classList.remove('hidden-by-search');
-                row.classList.add('search-highlight');
-                count++;
-            }} else {{
-                row.classList.add('hidden-by-search');
-                row.classList.remove('search-highlight');
-            }}
+            row.classList.add('hidden-by-search');
+            row.classList.remove('search-highlight');
         }}
     }});
-    
-    // Actualizar contador
-    var countEl = document.getElementById('search-count');
-    if (countEl) {{
-        if (searchLower === '') {{
-            countEl.textContent = total + ' items';
-        }} else {{
-            countEl.textContent = count + ' of ' + total + ' items';
-        }}
+
+    var hasGroups = tbody.querySelector('tr.group-header') !== null;
+    __searchHits = [];
+    __searchCurrent = -1;
+    ids.forEach(function(id) {{
+        var r = byId[id];
+        if (!r) return;
+        if (!hasGroups) tbody.appendChild(r);
+        __searchHits.push(r);
+    }});
+
+    // En vista agrupada, ocultar las cabeceras cuyos miembros han quedado
+    // todos descartados por la búsqueda, para no mostrar grupos vacíos.
+    if (hasGroups) {{
+        tbody.querySelectorAll('tr.group-header').forEach(function(header) {{
+            var hasVisibleMember = false;
+            var row = header.nextElementSibling;
+            while (row && !row.classList.contains('group-header')) {{
+                if (!row.classList.contains('hidden-by-search')) {{
+                    hasVisibleMember = true;
+                    break;
+                }}
+                row = row.nextElementSibling;
+            }}
+            header.classList.toggle('hidden-by-search', !hasVisibleMember);
+        }});
+    }}
+
+    __updateCount(__searchHits.length, rows.length);
+}}
+
+// Ejecutar la búsqueda: restaurar el texto de las celdas, tokenizar y, o bien
+// limpiar (consulta vacía), o bien delegar en el worker; si no hay worker se
+// resuelve inline de forma síncrona.
+function __runSearch(query) {{
+    var tbody = document.querySelector('tbody');
+    if (!tbody) return;
+    var rows = tbody.querySelectorAll('tr[data-path]');
+    if (__origOrder === null) {{
+        __origOrder = Array.prototype.slice.call(tbody.children);
+    }}
+
+    // Restaurar el texto original de cada celda antes de re-resaltar.
+    rows.forEach(function(row) {{
+        row.querySelectorAll('td').forEach(function(td) {{
+            if (td.dataset.raw === undefined) td.dataset.raw = td.textContent;
+            td.innerHTML = __escapeHtml(td.dataset.raw);
+        }});
+    }});
+
+    var terms = __tokenize(query);
+    if (terms.length === 0) {{
+        // Invalidar cualquier respuesta del worker en vuelo para que no vuelva
+        // a filtrar la tabla recién limpiada.
+        __searchSeq++;
+        __origOrder.forEach(function(node) {{ tbody.appendChild(node); }});
+        rows.forEach(function(row) {{
+            row.classList.remove('hidden-by-search');
+            row.classList.remove('search-highlight');
+        }});
+        tbody.querySelectorAll('tr.group-header').forEach(function(header) {{
+            header.classList.remove('hidden-by-search');
+        }});
+        __searchHits = [];
+        __searchCurrent = -1;
+        __updateCount(rows.length, rows.length);
+        return;
+    }}
+
+    var w = __initWorker();
+    if (w) {{
+        w.postMessage({{ type: 'query', q: query, seq: ++__searchSeq }});
+        return;
+    }}
+    __applyRankedOrder(__rankInline(terms, rows), terms);
+}}
+
+// Entrada pública: debouncea las pulsaciones rápidas antes de buscar.
+function applySearch(query) {{
+    __lastQuery = query;
+    __saveUiState({{ q: query }});
+    if (__debounceTimer) clearTimeout(__debounceTimer);
+    __debounceTimer = setTimeout(function() {{ __runSearch(__lastQuery); }}, 120);
+}}
+
+// Desplazar el WebView hasta la enésima coincidencia (para next/prev).
+function scrollToHit(index) {{
+    if (__searchHits.length === 0) return;
+    __searchCurrent = ((index % __searchHits.length) + __searchHits.length) % __searchHits.length;
+    __searchHits[__searchCurrent].scrollIntoView({{ block: 'center', behavior: 'smooth' }});
+}}
+
+function nextHit() {{ scrollToHit(__searchCurrent + 1); }}
+function prevHit() {{ scrollToHit(__searchCurrent - 1); }}
+
+// Plegar/desplegar una sección de grupo sin recargar la tabla. El estado se
+// refleja en Rust (groupToggle) para que sobreviva a los re-renderizados.
+function toggleGroup(key) {{
+    var header = document.querySelector('tr.group-header[data-group="' + key + '"]');
+    if (!header) return;
+    var collapsed = header.classList.toggle('collapsed');
+    var row = header.nextElementSibling;
+    while (row && !row.classList.contains('group-header')) {{
+        row.style.display = collapsed ? 'none' : '';
+        row = row.nextElementSibling;
+    }}
+    if (window.webkit && window.webkit.messageHandlers.groupToggle) {{
+        window.webkit.messageHandlers.groupToggle.postMessage(key);
     }}
 }}
 
@@ -1717,10 +3665,39 @@ document.addEventListener('click', function(event) {{
         return;
     }}
     
+    // Clic en un chip de etiqueta: filtrar por ella en lugar de abrir la nota.
+    var tagChip = event.target.closest('.tag-chip');
+    if (tagChip) {{
+        event.preventDefault();
+        event.stopPropagation();
+        if (window.webkit && window.webkit.messageHandlers.tagFilter) {{
+            window.webkit.messageHandlers.tagFilter.postMessage(tagChip.dataset.tag);
+        }}
+        return;
+    }}
+
+    // Clic en una cabecera de grupo: plegar/desplegar.
+    var groupHeader = event.target.closest('tr.group-header');
+    if (groupHeader) {{
+        toggleGroup(groupHeader.dataset.group);
+        return;
+    }}
+
     // Verificar si el clic fue en una fila de la tabla
     var row = event.target.closest('tr[data-path]');
     if (row) {{
-        // Clic en fila - enviar el path de la nota
+        // Ctrl/⌘+clic: alternar selección en lugar de abrir la nota. Rust
+        // mantiene la MultiSelection autoritativa; aquí solo se reenvía el
+        // índice y se refleja la clase .selected de forma optimista.
+        if (event.ctrlKey || event.metaKey) {{
+            event.preventDefault();
+            row.classList.toggle('selected');
+            if (window.webkit && window.webkit.messageHandlers.rowSelect) {{
+                window.webkit.messageHandlers.rowSelect.postMessage(row.dataset.idx);
+            }}
+            return;
+        }}
+        // Clic normal en fila - enviar el path de la nota
         window.webkit.messageHandlers.noteClick.postMessage(row.dataset.path);
     }} else {{
         // Clic fuera de las filas - solo cerrar sidebar
@@ -1728,6 +3705,30 @@ document.addEventListener('click', function(event) {{
     }}
 }});
 
+// Alternar selección desde la casilla de la fila (en vez de Ctrl/⌘+clic):
+// mismo mensaje `rowSelect` que usa Rust como fuente de verdad.
+function selectRowCheckbox(checkbox, idx) {{
+    var row = checkbox.closest('tr[data-path]');
+    if (row) row.classList.toggle('selected', checkbox.checked);
+    if (window.webkit && window.webkit.messageHandlers.rowSelect) {{
+        window.webkit.messageHandlers.rowSelect.postMessage(String(idx));
+    }}
+}}
+
+// Reflejar el conjunto de filas seleccionadas que Rust considera autoritativo.
+// `indices` es la lista de posiciones (data-idx) actualmente seleccionadas.
+function applySelection(indices) {{
+    var set = {{}};
+    indices.forEach(function(i) {{ set[String(i)] = true; }});
+    document.querySelectorAll('tr[data-path]').forEach(function(row) {{
+        var checked = !!set[row.dataset.idx];
+        if (checked) row.classList.add('selected');
+        else row.classList.remove('selected');
+        var cb = row.querySelector('.row-checkbox');
+        if (cb) cb.checked = checked;
+    }});
+}}
+
 // Atajos de teclado
 document.addEventListener('keydown', function(event) {{
     // Ctrl+F o Cmd+F para enfocar búsqueda
@@ -1749,8 +3750,82 @@ document.addEventListener('keydown', function(event) {{
         }}
     }}
 }});
+
+// Persistencia ligera del estado de la vista (consulta de búsqueda y
+// desplazamiento) en localStorage, con espacio de nombres por Base. Se degrada
+// con elegancia si el almacenamiento está deshabilitado (modo privado, cuota
+// agotada) y mantiene un tope de entradas para no crecer sin límite.
+var __baseId = {};
+var __UI_KEY = 'notnative.base.' + __baseId + '.ui';
+var __UI_PREFIX = 'notnative.base.';
+var __UI_CAP = 50;
+
+function __uiLoad() {{
+    try {{
+        var raw = localStorage.getItem(__UI_KEY);
+        return raw ? JSON.parse(raw) : {{}};
+    }} catch (e) {{ return {{}}; }}
+}}
+
+function __saveUiState(patch) {{
+    if (__baseId < 0) return; // Base sin persistir todavía.
+    try {{
+        var cur = __uiLoad();
+        for (var k in patch) cur[k] = patch[k];
+        cur.t = Date.now();
+        localStorage.setItem(__UI_KEY, JSON.stringify(cur));
+        __pruneUiState();
+    }} catch (e) {{ /* almacenamiento no disponible: ignorar */ }}
+}}
+
+// Recortar las entradas más antiguas cuando se supera el tope, para que el
+// historial de Bases visitadas no llene el almacenamiento.
+function __pruneUiState() {{
+    try {{
+        var keys = [];
+        for (var i = 0; i < localStorage.length; i++) {{
+            var k = localStorage.key(i);
+            if (k && k.indexOf(__UI_PREFIX) === 0 && k.slice(-3) === '.ui') keys.push(k);
+        }}
+        if (keys.length <= __UI_CAP) return;
+        keys.sort(function(a, b) {{
+            var ta = 0, tb = 0;
+            try {{ ta = (JSON.parse(localStorage.getItem(a)) || {{}}).t || 0; }} catch (e) {{}}
+            try {{ tb = (JSON.parse(localStorage.getItem(b)) || {{}}).t || 0; }} catch (e) {{}}
+            return ta - tb;
+        }});
+        while (keys.length > __UI_CAP) localStorage.removeItem(keys.shift());
+    }} catch (e) {{}}
+}}
+
+// Restaurar el estado guardado en cuanto el DOM está listo, antes de la primera
+// interacción, y empezar a registrar el desplazamiento (coalescido).
+window.addEventListener('DOMContentLoaded', function() {{
+    var st = __uiLoad();
+    if (st.q) {{
+        var input = document.getElementById('table-search');
+        if (input) input.value = st.q;
+        __runSearch(st.q);
+        // Con una consulta restaurada el ranking reordena/oculta filas de forma
+        // asíncrona (worker), así que el scroll se aplica cuando la maquetación
+        // ya es estable, no contra el layout previo.
+        if (typeof st.scroll === 'number') {{
+            setTimeout(function() {{ window.scrollTo(0, st.scroll); }}, 200);
+        }}
+    }} else if (typeof st.scroll === 'number') {{
+        window.scrollTo(0, st.scroll);
+    }}
+
+    var scrollTimer = null;
+    window.addEventListener('scroll', function() {{
+        if (scrollTimer) clearTimeout(scrollTimer);
+        scrollTimer = setTimeout(function() {{
+            __saveUiState({{ scroll: window.scrollY || window.pageYOffset || 0 }});
+        }}, 200);
+    }});
+}});
 </script>
-"#, css, theme_class);
+"#, language.direction().as_attr(), css, theme_class, Self::search_stopwords_js(), base_id);
         
         if notes.is_empty() {
             html.push_str(&format!(r#"<div class="empty-state">{}</div>"#, no_notes_label));
@@ -1767,38 +3842,169 @@ document.addEventListener('keydown', function(event) {{
 "#, search_placeholder, notes_count, items_label));
             
             html.push_str("<table>\n<thead>\n<tr>\n");
-            
+
+            // Columna de selección (sin cabecera de texto: la marca de
+            // selección vive en cada fila, no en la tabla completa).
+            html.push_str(r#"<th class="select-th"></th>"#);
+
             // Cabeceras
             for col in columns.iter().filter(|c| c.visible) {
                 let header_name = Self::format_column_header(&col.property, language);
                 html.push_str(&format!("<th>{}</th>\n", Self::escape_html(&header_name)));
             }
             html.push_str("</tr>\n</thead>\n<tbody>\n");
-            
-            // Filas de datos
-            for note in notes {
-                let path_attr = Self::escape_html(&note.metadata.path);
-                html.push_str(&format!(r#"<tr data-path="{}">"#, path_attr));
-                
-                for col in columns.iter().filter(|c| c.visible) {
-                    let value = Self::get_property_value(note, &col.property);
-                    let cell_class = match col.property.as_str() {
-                        "title" => "title-cell",
-                        "created" | "modified" => "date-cell",
-                        _ => "property-cell",
-                    };
-                    html.push_str(&format!(r#"<td class="{}">{}</td>"#, cell_class, Self::escape_html(&value)));
+
+            let visible_cols: Vec<&ColumnConfig> = columns.iter().filter(|c| c.visible).collect();
+
+            match group_by {
+                // Sin agrupar: filas planas (comportamiento original).
+                None => {
+                    for (idx, note) in notes.iter().enumerate() {
+                        html.push_str(&Self::render_table_row(idx, note, &visible_cols));
+                    }
+                }
+                // Agrupado: particionar preservando el orden de `notes` (que ya
+                // viene filtrado y ordenado) y emitir una cabecera por grupo.
+                // Se guardan los índices en `notes` para que `data-idx` siga
+                // apuntando a la posición del modelo de selección.
+                Some(property) => {
+                    let mut order: Vec<String> = Vec::new();
+                    let mut groups: std::collections::HashMap<String, Vec<usize>> =
+                        std::collections::HashMap::new();
+                    for (idx, note) in notes.iter().enumerate() {
+                        let key = Self::get_property_value(note, property);
+                        groups.entry(key.clone()).or_insert_with(|| {
+                            order.push(key.clone());
+                            Vec::new()
+                        }).push(idx);
+                    }
+
+                    let count_word = if language == Language::Spanish { "elementos" } else { "items" };
+                    for key in &order {
+                        let members = &groups[key];
+                        let is_collapsed = collapsed.contains(key);
+                        let caret = if is_collapsed { "▸" } else { "▾" };
+                        let header_class = if is_collapsed { "group-header collapsed" } else { "group-header" };
+                        let display_key = if key.is_empty() {
+                            if language == Language::Spanish { "(vacío)" } else { "(empty)" }
+                        } else {
+                            key.as_str()
+                        };
+
+                        html.push_str(&format!(
+                            r#"<tr class="{}" data-group="{}">"#,
+                            header_class,
+                            Self::escape_html(key),
+                        ));
+                        // Celda de selección vacía, para que las columnas de
+                        // la cabecera de grupo sigan alineadas con las filas.
+                        html.push_str(r#"<td class="select-cell"></td>"#);
+                        // Primera celda: caret + valor del grupo + recuento.
+                        html.push_str(&format!(
+                            r#"<td><span class="group-caret">{}</span>{}<span class="group-count">{} {}</span></td>"#,
+                            caret,
+                            Self::escape_html(display_key),
+                            members.len(),
+                            count_word,
+                        ));
+                        // Resto de celdas: suma para columnas numéricas, vacío si no.
+                        let member_notes: Vec<&NoteWithProperties> =
+                            members.iter().map(|&i| &notes[i]).collect();
+                        for col in visible_cols.iter().skip(1) {
+                            let agg = Self::numeric_aggregate(&member_notes, &col.property)
+                                .map(|sum| format!(r#"<span class="group-agg">Σ {}</span>"#, Self::format_number(sum)))
+                                .unwrap_or_default();
+                            html.push_str(&format!("<td>{}</td>", agg));
+                        }
+                        html.push_str("</tr>\n");
+
+                        // Filas del grupo (ocultas si está colapsado).
+                        for &idx in members {
+                            let mut row = Self::render_table_row(idx, &notes[idx], &visible_cols);
+                            if is_collapsed {
+                                row = row.replacen("<tr ", r#"<tr style="display:none" "#, 1);
+                            }
+                            html.push_str(&row);
+                        }
+                    }
                 }
-                html.push_str("</tr>\n");
             }
-            
+
             html.push_str("</tbody>\n</table>\n");
+
+            // Índice invertido BM25 embebido, consumido por applySearch.
+            html.push_str(&format!(
+                "<script>var __searchIndex = {};</script>\n",
+                Self::build_search_index_json(notes, &visible_cols),
+            ));
         }
-        
+
         html.push_str("</body>\n</html>");
         html
     }
     
+    /// Renderizar una fila de datos `<tr data-path=... data-idx=...>` para una nota.
+    ///
+    /// `idx` es la posición de la nota en el modelo de selección (el orden de
+    /// `self.notes` tras filtrar/ordenar); el JS lo reenvía por `rowSelect` para
+    /// que Rust marque la fila correcta en la `MultiSelection`.
+    fn render_table_row(idx: usize, note: &NoteWithProperties, visible_cols: &[&ColumnConfig]) -> String {
+        let mut row = format!(
+            r#"<tr data-path="{}" data-idx="{}">"#,
+            Self::escape_html(&note.metadata.path),
+            idx,
+        );
+        row.push_str(&format!(
+            r#"<td class="select-cell"><input type="checkbox" class="row-checkbox" onclick="event.stopPropagation(); selectRowCheckbox(this, {});"></td>"#,
+            idx,
+        ));
+        for col in visible_cols {
+            let value = Self::get_property_value(note, &col.property);
+            let cell_class = match col.property.as_str() {
+                "title" => "title-cell",
+                "created" | "modified" => "date-cell",
+                _ => "property-cell",
+            };
+            let cell = if col.property == "tags" {
+                Self::render_tag_chips(&value)
+            } else {
+                Self::escape_html(&value)
+            };
+            row.push_str(&format!(r#"<td class="{}">{}</td>"#, cell_class, cell));
+        }
+        row.push_str("</tr>\n");
+        row
+    }
+
+    /// Suma de una propiedad sobre un grupo, si todos sus valores son numéricos.
+    ///
+    /// Devuelve `None` cuando la columna no es numérica (así la cabecera de
+    /// grupo deja la celda vacía en lugar de mostrar un agregado sin sentido).
+    fn numeric_aggregate(members: &[&NoteWithProperties], property: &str) -> Option<f64> {
+        let mut sum = 0.0;
+        let mut any = false;
+        for note in members {
+            let raw = Self::get_property_value(note, property);
+            if raw.is_empty() {
+                continue;
+            }
+            let parsed: f64 = raw.replace(',', "").parse().ok()?;
+            sum += parsed;
+            any = true;
+        }
+        any.then_some(sum)
+    }
+
+    /// Formatear un agregado numérico: sin decimales si es entero, con dos
+    /// decimales fijos en otro caso.
+    fn format_number(value: f64) -> String {
+        if value.fract() == 0.0 {
+            format!("{}", value as i64)
+        } else {
+            format!("{:.2}", value)
+        }
+    }
+
     /// Formatear el nombre de la columna para el header
     fn format_column_header(property: &str, language: Language) -> String {
         match property {
@@ -1833,6 +4039,115 @@ document.addEventListener('keydown', function(event) {{
         }
     }
     
+    /// Expresión para localizar hashtags en el cuerpo de una nota.
+    ///
+    /// El límite previo (inicio, espacio o `>`) evita casar un `#` pegado a
+    /// otra palabra o dentro de una URL; la clase de cola corta la etiqueta en
+    /// la primera puntuación, para que `#nota.` o `#a,b` no se traguen el
+    /// separador.
+    fn hashtag_regex() -> &'static regex::Regex {
+        static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| {
+            regex::Regex::new(r#"(^|\s|>)(#[^\s!@#$%^&*()=+.,\[\]{};:'"?><]+)"#).unwrap()
+        })
+    }
+
+    /// Extraer, ordenar y deduplicar los hashtags del cuerpo de una nota.
+    fn extract_hashtags(content: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Self::hashtag_regex()
+            .captures_iter(content)
+            .filter_map(|c| c.get(2).map(|m| m.as_str().to_string()))
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Fusionar los hashtags hallados en el cuerpo de cada nota con los que ya
+    /// traiga su propiedad `tags` (front-matter, metadatos de BD, etc.), para
+    /// que `get_display`/`get_property_value` los muestren sin distinguir su
+    /// origen.
+    fn merge_content_hashtags(notes: &mut [NoteWithProperties]) {
+        for note in notes.iter_mut() {
+            let Some(content) = note.content.as_deref() else {
+                continue;
+            };
+            let harvested = Self::extract_hashtags(content);
+            if harvested.is_empty() {
+                continue;
+            }
+            let mut tags: Vec<String> = note
+                .properties
+                .get("tags")
+                .map(|v| v.to_display_string())
+                .unwrap_or_default()
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+            tags.extend(harvested);
+            tags.sort();
+            tags.dedup();
+            note.properties
+                .insert("tags".to_string(), PropertyValue::Tags(tags));
+        }
+    }
+
+    /// Conjunto de etiquetas distintas presentes en `notes`, ordenado
+    /// alfabéticamente, para poblar selectores de etiquetas (checkboxes del
+    /// popover de filtros) sin depender de texto libre.
+    fn distinct_tags(notes: &[NoteWithProperties]) -> Vec<String> {
+        let mut tags: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+        for note in notes {
+            if let Some(PropertyValue::Tags(items)) = note.properties.get("tags") {
+                tags.extend(items.iter().cloned());
+            }
+        }
+        tags.into_iter().collect()
+    }
+
+    /// Renderizar el valor de la columna `tags` como chips pulsables: cada uno
+    /// publica `tagFilter` con su etiqueta al hacer clic (ver el listener de
+    /// `document` más abajo), para filtrar la tabla por ella: el manejador
+    /// añade un filtro `tags contains <etiqueta>` a los filtros activos y lo
+    /// muestra como chip en la barra de filtros. El color de cada chip sale de un hash
+    /// de su nombre, así que la misma etiqueta siempre se ve igual.
+    fn render_tag_chips(value: &str) -> String {
+        value
+            .split(',')
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .map(|t| {
+                format!(
+                    r#"<span class="tag-chip" data-tag="{0}" style="{1}">{0}</span>"#,
+                    Self::escape_html(t),
+                    Self::tag_chip_style(t),
+                )
+            })
+            .collect()
+    }
+
+    /// Estilo inline de un chip de etiqueta: el matiz sale de un hash estable
+    /// del nombre (no del orden ni del contenido del resto de la fila), para
+    /// que la misma etiqueta tenga siempre el mismo color.
+    fn tag_chip_style(tag: &str) -> String {
+        let hue = Self::tag_hue(tag);
+        format!(
+            "background-color: hsl({hue}, 65%, 88%); color: hsl({hue}, 55%, 30%);"
+        )
+    }
+
+    /// Matiz (0-359) derivado del nombre de la etiqueta mediante FNV-1a, para
+    /// no depender de ninguna crate de hashing externa.
+    fn tag_hue(tag: &str) -> u16 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in tag.as_bytes() {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        (hash % 360) as u16
+    }
+
     /// Escapar HTML
     fn escape_html(s: &str) -> String {
         s.replace('&', "&amp;")
@@ -1841,45 +4156,462 @@ document.addEventListener('keydown', function(event) {{
          .replace('"', "&quot;")
          .replace('\'', "&#39;")
     }
+
+    /// Palabras vacías descartadas al indexar y al tokenizar la consulta.
+    ///
+    /// Es un conjunto reducido EN/ES: basta para que términos muy comunes no
+    /// dominen el ranking BM25. El mismo conjunto se emite al JS (ver
+    /// [`Self::search_stopwords_js`]) para que índice y consulta coincidan.
+    const SEARCH_STOPWORDS: &'static [&'static str] = &[
+        "the", "a", "an", "and", "or", "of", "to", "in", "is", "it", "for", "on",
+        "with", "as", "at", "by",
+        "el", "la", "los", "las", "de", "y", "o", "un", "una", "en", "es", "por",
+        "con", "para",
+    ];
+
+    /// Tokenizar un texto igual que el buscador JS: minúsculas y corte por
+    /// cualquier carácter no alfanumérico (unicode), descartando palabras
+    /// vacías. Debe permanecer en paralelo con `__tokenize` del script.
+    fn search_tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_lowercase())
+            .filter(|t| !Self::SEARCH_STOPWORDS.contains(&t.as_str()))
+            .collect()
+    }
+
+    /// Construir el índice invertido BM25 de las filas visibles y serializarlo
+    /// como JSON para embeberlo en la página.
+    ///
+    /// Para cada fila se tokenizan los valores de las columnas visibles y se
+    /// registra, por término, la lista de `[índice_fila, frecuencia]`, además
+    /// de la longitud de cada documento, el total `N` y la longitud media
+    /// `avgdl`. El índice de fila coincide con el `data-idx` de
+    /// [`Self::render_table_row`], de modo que sirve igual en vista plana o
+    /// agrupada.
+    fn build_search_index_json(notes: &[NoteWithProperties], visible_cols: &[&ColumnConfig]) -> String {
+        use std::collections::HashMap;
+
+        let n = notes.len();
+        let mut postings: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+        let mut doc_len: Vec<u32> = Vec::with_capacity(n);
+        let mut total_len: u64 = 0;
+
+        for (idx, note) in notes.iter().enumerate() {
+            let mut tf: HashMap<String, u32> = HashMap::new();
+            let mut len = 0u32;
+            for col in visible_cols {
+                let value = Self::get_property_value(note, &col.property);
+                for token in Self::search_tokenize(&value) {
+                    *tf.entry(token).or_insert(0) += 1;
+                    len += 1;
+                }
+            }
+            doc_len.push(len);
+            total_len += len as u64;
+            for (term, freq) in tf {
+                postings.entry(term).or_default().push((idx, freq));
+            }
+        }
+
+        let avgdl = if n > 0 { total_len as f64 / n as f64 } else { 0.0 };
+
+        // Orden estable de los términos para una salida determinista (evita
+        // diffs espurios al re-renderizar el mismo conjunto de notas).
+        let mut terms: Vec<&String> = postings.keys().collect();
+        terms.sort();
+
+        let mut postings_json = String::from("{");
+        for (i, term) in terms.iter().enumerate() {
+            if i > 0 {
+                postings_json.push(',');
+            }
+            postings_json.push_str(&format!("\"{}\":[", Self::json_escape(term)));
+            for (j, (row, freq)) in postings[*term].iter().enumerate() {
+                if j > 0 {
+                    postings_json.push(',');
+                }
+                postings_json.push_str(&format!("[{},{}]", row, freq));
+            }
+            postings_json.push(']');
+        }
+        postings_json.push('}');
+
+        let doc_len_json = doc_len
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"N\":{},\"avgdl\":{},\"docLen\":[{}],\"postings\":{}}}",
+            n, avgdl, doc_len_json, postings_json
+        )
+    }
+
+    /// Emitir las palabras vacías como literal de array JS para el buscador.
+    fn search_stopwords_js() -> String {
+        let items = Self::SEARCH_STOPWORDS
+            .iter()
+            .map(|w| format!("\"{}\"", w))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("[{}]", items)
+    }
+
+    /// Escapar una cadena para incrustarla como string JSON.
+    fn json_escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
     
-    /// Detectar si el tema es oscuro
-    /// Por ahora siempre usamos tema oscuro para consistencia con las notas
-    fn is_dark_theme() -> bool {
-        // Siempre oscuro por defecto (igual que HtmlRenderer)
-        true
+    /// Tema configurado en la Base actual, o `System` si todavía no se ha
+    /// elegido ninguno explícitamente.
+    fn current_theme(&self) -> BaseTheme {
+        self.base
+            .borrow()
+            .as_ref()
+            .and_then(|b| b.theme)
+            .unwrap_or_default()
     }
 
-    /// Actualizar los tabs de vistas
-    fn update_view_tabs(&self, base: &Base) {
-        // Limpiar tabs existentes
-        while let Some(child) = self.view_tabs.first_child() {
-            self.view_tabs.remove(&child);
-        }
-
-        // Crear un tab por cada vista
-        for (i, view) in base.views.iter().enumerate() {
-            let is_active = i == base.active_view;
-
-            let button = gtk::ToggleButton::builder()
-                .label(&view.name)
-                .active(is_active)
-                .css_classes(if is_active { 
-                    vec!["base-view-tab", "active"] 
-                } else { 
-                    vec!["base-view-tab"] 
-                })
+    /// Consultar si el escritorio (GTK/libadwaita) prefiere un tema oscuro,
+    /// usado para resolver `BaseTheme::System`.
+    fn system_prefers_dark() -> bool {
+        gtk::Settings::default()
+            .map(|s| s.is_gtk_application_prefer_dark_theme())
+            .unwrap_or(true)
+    }
+
+    /// (Re)generar el CssProvider de GTK para el tema actual de la Base y
+    /// sustituirlo en el display, para que la barra de herramientas y el
+    /// resto de chrome nativo se actualicen junto con el WebView.
+    fn apply_theme_css(&self) {
+        let palette = self.current_theme().palette();
+        let provider = gtk::CssProvider::new();
+        provider.load_from_string(&base_css(palette));
+
+        if let Some(display) = gdk::Display::default() {
+            if let Some(old) = self.theme_css_provider.borrow_mut().take() {
+                gtk::style_context_remove_provider_for_display(&display, &old);
+            }
+            gtk::style_context_add_provider_for_display(
+                &display,
+                &provider,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+            );
+        }
+        *self.theme_css_provider.borrow_mut() = Some(provider);
+    }
+
+    /// Configurar el popover para elegir el tema (claro/oscuro/sistema) de
+    /// esta Base. El contenido se reconstruye cada vez que se abre, igual
+    /// que el popover de agrupación, para reflejar el tema actualmente
+    /// guardado.
+    fn setup_theme_popover(&self) {
+        let popover = gtk::Popover::builder()
+            .css_classes(["theme-popover"])
+            .has_arrow(true)
+            .build();
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(4)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .width_request(180)
+            .build();
+        popover.set_child(Some(&content));
+        self.theme_btn.set_popover(Some(&popover));
+
+        let base = self.base.clone();
+        let base_id = self.base_id.clone();
+        let notes_db = self.notes_db.clone();
+        let i18n = self.i18n.clone();
+        let theme_btn = self.theme_btn.clone();
+        let container = self.container.clone();
+        let notes = self.notes.clone();
+        let table_webview = self.table_webview.clone();
+        let collapsed = self.collapsed_groups.clone();
+
+        popover.connect_notify_local(Some("visible"), move |pop, _| {
+            if !pop.is_visible() {
+                return;
+            }
+            while let Some(child) = content.first_child() {
+                content.remove(&child);
+            }
+
+            let current = base.borrow().as_ref().and_then(|b| b.theme).unwrap_or_default();
+
+            let light_radio = gtk::CheckButton::builder()
+                .label(&i18n.borrow().t("base_theme_light"))
+                .active(matches!(current, BaseTheme::Light))
+                .build();
+            let dark_radio = gtk::CheckButton::builder()
+                .label(&i18n.borrow().t("base_theme_dark"))
+                .group(&light_radio)
+                .active(matches!(current, BaseTheme::Dark))
                 .build();
+            let system_radio = gtk::CheckButton::builder()
+                .label(&i18n.borrow().t("base_theme_system"))
+                .group(&light_radio)
+                .active(matches!(current, BaseTheme::System))
+                .build();
+            content.append(&light_radio);
+            content.append(&dark_radio);
+            content.append(&system_radio);
+
+            for (radio, new_theme) in [
+                (&light_radio, BaseTheme::Light),
+                (&dark_radio, BaseTheme::Dark),
+                (&system_radio, BaseTheme::System),
+            ] {
+                let (base, base_id, notes_db, i18n, theme_btn, container, notes, table_webview, collapsed, pop) = (
+                    base.clone(), base_id.clone(), notes_db.clone(), i18n.clone(),
+                    theme_btn.clone(), container.clone(), notes.clone(),
+                    table_webview.clone(), collapsed.clone(), pop.clone(),
+                );
+                radio.connect_toggled(move |r| {
+                    if !r.is_active() {
+                        return;
+                    }
+                    Self::change_theme(&base, &base_id, &notes_db, new_theme);
+                    Self::update_theme_icon(&theme_btn, new_theme);
+                    Self::apply_theme_css_to_display(&container, new_theme);
+
+                    let columns = base.borrow().as_ref()
+                        .and_then(|b| b.active_view().map(|v| v.columns.clone()))
+                        .unwrap_or_default();
+                    let group_by = base.borrow().as_ref()
+                        .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+                    let html = Self::render_table_html_static(
+                        &notes.borrow(), &columns, i18n.borrow().current_language(),
+                        group_by.as_deref(), &collapsed.borrow(), base_id.borrow().unwrap_or(-1),
+                        new_theme,
+                    );
+                    table_webview.load_html(&html, None);
+
+                    pop.popdown();
+                });
+            }
+        });
+
+        Self::update_theme_icon(&self.theme_btn, self.current_theme());
+    }
+
+    /// Preparar el popover de exportación: vuelca las notas que se ven ahora
+    /// mismo en la tabla (ya filtradas y ordenadas por `apply_sort_and_refresh`)
+    /// a CSV o Markdown, solo con las columnas visibles, usando un diálogo de
+    /// guardado nativo.
+    fn setup_export_popover(&self) {
+        let popover = gtk::Popover::builder()
+            .css_classes(["export-popover"])
+            .has_arrow(true)
+            .build();
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(4)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build();
+
+        let csv_btn = gtk::Button::builder()
+            .label(&self.i18n.borrow().t("base_export_view_csv"))
+            .css_classes(["flat"])
+            .build();
+        let markdown_btn = gtk::Button::builder()
+            .label(&self.i18n.borrow().t("base_export_view_markdown"))
+            .css_classes(["flat"])
+            .build();
+        content.append(&csv_btn);
+        content.append(&markdown_btn);
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+        let mf2_entry_btn = gtk::Button::builder()
+            .label(&self.i18n.borrow().t("base_export_view_mf2_entry"))
+            .css_classes(["flat"])
+            .build();
+        let mf2_feed_btn = gtk::Button::builder()
+            .label(&self.i18n.borrow().t("base_export_view_mf2_feed"))
+            .css_classes(["flat"])
+            .build();
+        content.append(&mf2_entry_btn);
+        content.append(&mf2_feed_btn);
+        popover.set_child(Some(&content));
+        self.export_btn.set_popover(Some(&popover));
+
+        for (button, format, extension) in [
+            (&csv_btn, ExportFormat::Csv, "csv"),
+            (&markdown_btn, ExportFormat::Markdown, "md"),
+        ] {
+            let notes = self.notes.clone();
+            let base = self.base.clone();
+            let i18n = self.i18n.clone();
+            let container = self.container.clone();
+            let popover = popover.clone();
+            let extension = extension.to_string();
+            button.connect_clicked(move |_| {
+                popover.popdown();
+                let columns = base.borrow().as_ref()
+                    .and_then(|b| b.active_view().map(|v| v.columns.clone()))
+                    .unwrap_or_else(|| vec![
+                        ColumnConfig { property: "title".to_string(), title: None, width: Some(300), visible: true },
+                        ColumnConfig { property: "created".to_string(), title: None, width: Some(150), visible: true },
+                    ]);
+                let contents = export_view(&notes.borrow(), &columns, format);
+                Self::save_export_to_file(&container, &i18n, &extension, contents);
+            });
+        }
+
+        for (button, extension, as_feed) in [
+            (&mf2_entry_btn, "json", false),
+            (&mf2_feed_btn, "json", true),
+        ] {
+            let notes = self.notes.clone();
+            let selection = self.selection.clone();
+            let base = self.base.clone();
+            let i18n = self.i18n.clone();
+            let container = self.container.clone();
+            let popover = popover.clone();
+            let extension = extension.to_string();
+            button.connect_clicked(move |_| {
+                popover.popdown();
+                let export_notes = Self::selected_or_all_notes(&selection, &notes);
+                let visibility_property = base.borrow().as_ref()
+                    .and_then(|b| b.active_view().and_then(|v| v.visibility_property.clone()))
+                    .unwrap_or_else(|| "visibility".to_string());
+                let contents = if as_feed {
+                    export_h_feed(&export_notes, &visibility_property)
+                } else {
+                    match export_notes.first() {
+                        Some(note) => export_h_entry(note, &visibility_property),
+                        None => return,
+                    }
+                };
+                Self::save_export_to_file(&container, &i18n, &extension, contents);
+            });
+        }
+    }
 
-            self.view_tabs.append(&button);
+    /// Notas seleccionadas, o todas las que se muestran ahora si no hay
+    /// ninguna selección: así los botones de exportación de mf2 exportan
+    /// "lo que el usuario ha marcado, o si no, lo que está viendo".
+    fn selected_or_all_notes(
+        selection: &gtk::MultiSelection,
+        notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    ) -> Vec<NoteWithProperties> {
+        let notes = notes.borrow();
+        let selected: Vec<NoteWithProperties> = (0..selection.n_items())
+            .filter(|&i| selection.is_selected(i))
+            .filter_map(|i| notes.get(i as usize).cloned())
+            .collect();
+        if selected.is_empty() {
+            notes.clone()
+        } else {
+            selected
         }
+    }
 
-        // Botón para añadir nueva vista
-        let add_view_btn = gtk::Button::builder()
-            .icon_name("list-add-symbolic")
-            .tooltip_text("Add view")
-            .css_classes(["flat", "base-add-view"])
+    /// Abrir el diálogo nativo de guardado y volcar `contents` en el archivo
+    /// elegido, reportando por stderr si falla (no hay un canal de errores
+    /// dedicado para acciones de exportación todavía).
+    fn save_export_to_file(
+        container: &gtk::Box,
+        i18n: &Rc<RefCell<I18n>>,
+        extension: &str,
+        contents: String,
+    ) {
+        let window = container.root().and_downcast::<gtk::Window>();
+        let dialog = gtk::FileDialog::builder()
+            .title(i18n.borrow().t("base_export_view"))
+            .initial_name(format!("export.{}", extension))
             .build();
-        self.view_tabs.append(&add_view_btn);
+        let i18n = i18n.clone();
+        dialog.save(window.as_ref(), None::<&gio::Cancellable>, move |result| {
+            if let Ok(file) = result {
+                if let Some(path) = file.path() {
+                    if let Err(e) = std::fs::write(&path, &contents) {
+                        eprintln!("{}: {}", i18n.borrow().t("base_export_view_error"), e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Actualizar el icono del botón de tema para reflejar la selección.
+    fn update_theme_icon(btn: &gtk::MenuButton, theme: BaseTheme) {
+        let icon = match theme {
+            BaseTheme::Light => "weather-clear-symbolic",
+            BaseTheme::Dark => "weather-clear-night-symbolic",
+            BaseTheme::System => "preferences-desktop-display-symbolic",
+        };
+        btn.set_icon_name(icon);
+    }
+
+    /// Cambiar el tema de la Base y persistirlo, igual que `change_source_type`.
+    fn change_theme(
+        base: &Rc<RefCell<Option<Base>>>,
+        base_id: &Rc<RefCell<Option<i64>>>,
+        notes_db: &Rc<RefCell<Option<NotesDatabase>>>,
+        new_theme: BaseTheme,
+    ) {
+        let mut base_opt = base.borrow_mut();
+        if let Some(base_data) = base_opt.as_mut() {
+            base_data.theme = Some(new_theme);
+
+            if let (Some(id), Some(db)) = (base_id.borrow().as_ref(), notes_db.borrow().as_ref()) {
+                if let Ok(yaml) = base_data.serialize() {
+                    if let Err(e) = db.update_base(*id, &yaml, base_data.active_view as i32) {
+                        eprintln!("Error saving Base theme: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Regenerar el CssProvider del display para el tema dado, sin depender
+    /// de una instancia de `BaseTableWidget` (usado desde los manejadores de
+    /// los radios, que solo tienen acceso al `container` vía closures).
+    fn apply_theme_css_to_display(_container: &gtk::Box, theme: BaseTheme) {
+        let provider = gtk::CssProvider::new();
+        provider.load_from_string(&base_css(theme.palette()));
+        if let Some(display) = gdk::Display::default() {
+            gtk::style_context_add_provider_for_display(
+                &display,
+                &provider,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+            );
+        }
+    }
+
+    /// Actualizar los tabs de vistas
+    fn update_view_tabs(&self, _base: &Base) {
+        // Las pestañas son objetos de primera clase: cambiar, cerrar, renombrar
+        // y reordenar vistas. Toda la lógica vive en funciones libres que operan
+        // sobre el estado `Rc` para poder reconstruir la barra desde los
+        // manejadores sin pasar por `&self`.
+        let ctx = ViewTabsContext {
+            view_tabs: self.view_tabs.clone(),
+            column_view: self.column_view.clone(),
+            base: self.base.clone(),
+            base_id: self.base_id.clone(),
+            notes_db: self.notes_db.clone(),
+            notes_root: self.notes_root.clone(),
+            active_filters: self.active_filters.clone(),
+            filter_root: self.filter_root.clone(),
+            current_sort: self.current_sort.clone(),
+            all_notes: self.all_notes.clone(),
+            notes: self.notes.clone(),
+            list_store: self.list_store.clone(),
+            selection: self.selection.clone(),
+            status_bar: self.status_bar.clone(),
+            table_webview: self.table_webview.clone(),
+            filters_container: self.filters_container.clone(),
+            i18n: self.i18n.clone(),
+        };
+        rebuild_view_tabs(&ctx);
     }
 
     /// Actualizar la barra de estado
@@ -1908,6 +4640,13 @@ document.addEventListener('keydown', function(event) {{
     pub fn on_note_double_click<F: Fn(&str) + 'static>(&self, callback: F) {
         *self.on_note_double_click.borrow_mut() = Some(Box::new(callback));
     }
+
+    /// Configurar callback para cambios de selección múltiple, recibiendo los
+    /// `id` de las notas seleccionadas (permite acciones en lote externas como
+    /// etiquetado o borrado masivo desde la ventana contenedora).
+    pub fn on_selection_changed<F: Fn(&[String]) + 'static>(&self, callback: F) {
+        *self.on_selection_changed.borrow_mut() = Some(Box::new(callback));
+    }
 }
 
 impl Default for BaseTableWidget {
@@ -1916,8 +4655,90 @@ impl Default for BaseTableWidget {
     }
 }
 
-/// CSS para los widgets de Base
-pub const BASE_CSS: &str = r#"
+/// Tema visual de una Base: claro, oscuro o el que prefiera el sistema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BaseTheme {
+    Light,
+    Dark,
+    System,
+}
+
+impl Default for BaseTheme {
+    fn default() -> Self {
+        BaseTheme::System
+    }
+}
+
+impl BaseTheme {
+    /// Resolver a oscuro/claro, consultando la preferencia del sistema
+    /// (GTK/libadwaita) cuando el tema es `System`.
+    pub fn is_dark(self) -> bool {
+        match self {
+            BaseTheme::Dark => true,
+            BaseTheme::Light => false,
+            BaseTheme::System => BaseTableWidget::system_prefers_dark(),
+        }
+    }
+
+    /// Paleta de colores a usar para este tema, una vez resuelto.
+    pub fn palette(self) -> &'static BasePalette {
+        if self.is_dark() { &DARK_PALETTE } else { &LIGHT_PALETTE }
+    }
+}
+
+/// Paleta de colores de los temas claro/oscuro de la Base. De ella el CSS de
+/// GTK ([`base_css`]) solo toma `graph_canvas`; el HTML del WebView de la
+/// tabla no la lee y repite sus colores en las reglas `:root` (oscuro) y
+/// `body.light`, elegidas por la clase del `<body>`.
+#[derive(Debug, Clone, Copy)]
+pub struct BasePalette {
+    pub bg_primary: &'static str,
+    pub bg_secondary: &'static str,
+    pub bg_tertiary: &'static str,
+    pub fg_primary: &'static str,
+    pub fg_secondary: &'static str,
+    pub fg_muted: &'static str,
+    pub accent: &'static str,
+    pub border: &'static str,
+    pub graph_canvas: &'static str,
+}
+
+/// Paleta oscura (Catppuccin Mocha), la que usaba la app antes de que el
+/// tema fuera configurable.
+pub const DARK_PALETTE: BasePalette = BasePalette {
+    bg_primary: "#1e1e2e",
+    bg_secondary: "#313244",
+    bg_tertiary: "#45475a",
+    fg_primary: "#cdd6f4",
+    fg_secondary: "#a6adc8",
+    fg_muted: "#6c7086",
+    accent: "#89b4fa",
+    border: "#45475a",
+    graph_canvas: "#1e1e22",
+};
+
+/// Paleta clara (Catppuccin Latte).
+pub const LIGHT_PALETTE: BasePalette = BasePalette {
+    bg_primary: "#eff1f5",
+    bg_secondary: "#e6e9ef",
+    bg_tertiary: "#ccd0da",
+    fg_primary: "#4c4f69",
+    fg_secondary: "#5c5f77",
+    fg_muted: "#8c8fa1",
+    accent: "#1e66f5",
+    border: "#bcc0cc",
+    graph_canvas: "#e6e9ef",
+};
+
+/// Generar el CSS para los widgets de Base a partir de una paleta. La
+/// mayoría de reglas usan colores simbólicos de GTK (`@theme_fg_color`, ...)
+/// que ya siguen el tema del escritorio; solo el lienzo del grafo necesita un
+/// color fijo, que toma aquí de la paleta resuelta.
+pub fn base_css(palette: &BasePalette) -> String {
+    BASE_CSS_TEMPLATE.replace("__GRAPH_CANVAS__", palette.graph_canvas)
+}
+
+const BASE_CSS_TEMPLATE: &str = r#"
 .base-table-container {
     background: @theme_bg_color;
 }
@@ -1966,6 +4787,31 @@ pub const BASE_CSS: &str = r#"
     color: @theme_fg_color;
 }
 
+.base-view-tab-label {
+    padding: 4px 4px;
+    background: transparent;
+    border: none;
+    font-weight: 500;
+}
+
+.base-view-tab-close {
+    min-height: 18px;
+    min-width: 18px;
+    padding: 0;
+    opacity: 0;
+    border-radius: 4px;
+}
+
+.base-view-tab:hover .base-view-tab-close,
+.base-view-tab.active .base-view-tab-close {
+    opacity: 0.7;
+}
+
+.base-view-tab-close:hover {
+    opacity: 1;
+    background: alpha(@theme_fg_color, 0.12);
+}
+
 /* Tabla principal */
 .base-table {
     background: transparent;
@@ -2059,6 +4905,24 @@ pub const BASE_CSS: &str = r#"
     background: alpha(@theme_fg_color, 0.15);
 }
 
+/* Paleta de comandos */
+.command-palette {
+    padding: 8px;
+    background-color: @theme_bg_color;
+    border: 1px solid alpha(@theme_fg_color, 0.1);
+    border-radius: 12px;
+    box-shadow: 0 4px 12px alpha(black, 0.15);
+}
+
+.command-palette-list {
+    background: transparent;
+}
+
+.command-palette-list row:selected {
+    background: alpha(@accent_bg_color, 0.15);
+    border-radius: 6px;
+}
+
 /* Filter popover */
 .filter-popover {
     padding: 16px;
@@ -2200,7 +5064,7 @@ pub const BASE_CSS: &str = r#"
 
 /* Graph view styles */
 .base-graph-view {
-    background: #1e1e22;
+    background: __GRAPH_CANVAS__;
     min-height: 400px;
 }
 "#;
@@ -2249,6 +5113,394 @@ pub fn create_filter_chip(filter: &Filter, _index: usize) -> gtk::Box {
     chip
 }
 
+/// Renderizar un árbol de filtros como chips anidados dentro de `container`.
+///
+/// La raíz es habitualmente un grupo `All`, por lo que sus hijos se dibujan en
+/// línea (el caso plano no se envuelve en una caja). Cada chip de hoja y cada
+/// cabecera de subgrupo lleva un botón de cierre que elimina ese nodo del árbol
+/// por su ruta, sincroniza el espejo plano `active_filters`, re-filtra la tabla
+/// y vuelve a dibujar los chips. Es una función libre (y no un método) para
+/// poder re-invocarse a sí misma desde los callbacks de borrado.
+#[allow(clippy::too_many_arguments)]
+fn render_filter_tree_chips(
+    container: &gtk::Box,
+    filter_root: &Rc<RefCell<FilterNode>>,
+    active_filters: &Rc<RefCell<Vec<Filter>>>,
+    current_sort: &Rc<RefCell<Vec<SortConfig>>>,
+    all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    list_store: &gio::ListStore,
+    status_bar: &gtk::Box,
+    table_webview: &webkit6::WebView,
+    base: &Rc<RefCell<Option<Base>>>,
+    base_id: &Rc<RefCell<Option<i64>>>,
+    i18n: &Rc<RefCell<I18n>>,
+    selection: &gtk::MultiSelection,
+) {
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+
+    let root = filter_root.borrow();
+    if root.is_empty() {
+        let placeholder = gtk::Label::builder()
+            .label(&i18n.borrow().t("base_no_filters"))
+            .css_classes(["dim-label"])
+            .build();
+        container.append(&placeholder);
+        return;
+    }
+
+    let children = match &*root {
+        FilterNode::All(children) | FilterNode::Any(children) => children.clone(),
+        other => vec![other.clone()],
+    };
+    drop(root);
+
+    for (i, child) in children.iter().enumerate() {
+        let chip = build_filter_node_widget_wired(
+            child,
+            vec![i],
+            filter_root,
+            active_filters,
+            current_sort,
+            all_notes,
+            notes,
+            list_store,
+            status_bar,
+            table_webview,
+            base,
+            base_id,
+            i18n,
+            container,
+            selection,
+        );
+        container.append(&chip);
+    }
+}
+
+/// Construir el widget de un nodo con sus botones de cierre ya cableados.
+///
+/// Las hojas usan [`create_filter_chip`]; los grupos `All`/`Any`/`Not` se
+/// dibujan como una caja vertical encabezada por su combinador (con botón de
+/// cierre del grupo) seguida de sus hijos indentados, renderizados
+/// recursivamente. Cada botón elimina su nodo del árbol y refresca.
+#[allow(clippy::too_many_arguments)]
+fn build_filter_node_widget_wired(
+    node: &FilterNode,
+    path: Vec<usize>,
+    filter_root: &Rc<RefCell<FilterNode>>,
+    active_filters: &Rc<RefCell<Vec<Filter>>>,
+    current_sort: &Rc<RefCell<Vec<SortConfig>>>,
+    all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    list_store: &gio::ListStore,
+    status_bar: &gtk::Box,
+    table_webview: &webkit6::WebView,
+    base: &Rc<RefCell<Option<Base>>>,
+    base_id: &Rc<RefCell<Option<i64>>>,
+    i18n: &Rc<RefCell<I18n>>,
+    container: &gtk::Box,
+    selection: &gtk::MultiSelection,
+) -> gtk::Widget {
+    match node {
+        FilterNode::Leaf(filter) => {
+            let chip = create_filter_chip(filter, *path.last().unwrap_or(&0));
+            wire_filter_node_close(
+                &chip, path, filter_root, active_filters, current_sort, all_notes,
+                notes, list_store, status_bar, table_webview, base, base_id, i18n, container,
+                selection,
+            );
+            chip.upcast()
+        }
+        FilterNode::All(_) | FilterNode::Any(_) | FilterNode::Not(_) => {
+            let group = gtk::Box::builder()
+                .orientation(gtk::Orientation::Vertical)
+                .spacing(4)
+                .css_classes(["base-filter-group"])
+                .build();
+
+            // Cabecera: combinador + botón de cierre del grupo.
+            let header = gtk::Box::builder()
+                .orientation(gtk::Orientation::Horizontal)
+                .spacing(4)
+                .build();
+            if matches!(node, FilterNode::All(_) | FilterNode::Any(_)) {
+                // `All`/`Any` se pueden alternar entre sí con un clic; `Not`
+                // no tiene un opuesto booleano con el que alternar.
+                let toggle_btn = gtk::Button::builder()
+                    .label(filter_node_label(node))
+                    .css_classes(["flat", "base-filter-group-toggle"])
+                    .tooltip_text(&i18n.borrow().t("base_filter_toggle_group"))
+                    .build();
+                wire_filter_group_toggle(
+                    &toggle_btn, path.clone(), filter_root, active_filters, current_sort,
+                    all_notes, notes, list_store, status_bar, table_webview, base, base_id,
+                    i18n, container, selection,
+                );
+                header.append(&toggle_btn);
+            } else {
+                let label = gtk::Label::builder()
+                    .label(filter_node_label(node))
+                    .css_classes(["dim-label"])
+                    .build();
+                header.append(&label);
+            }
+            let close_btn = gtk::Button::builder()
+                .icon_name("window-close-symbolic")
+                .css_classes(["flat", "circular"])
+                .tooltip_text("Remove group")
+                .build();
+            header.append(&close_btn);
+            wire_filter_node_close(
+                &header, path.clone(), filter_root, active_filters, current_sort,
+                all_notes, notes, list_store, status_bar, table_webview, base, base_id, i18n,
+                container, selection,
+            );
+            group.append(&header);
+
+            // Hijos indentados.
+            let body = gtk::Box::builder()
+                .orientation(gtk::Orientation::Horizontal)
+                .spacing(4)
+                .margin_start(12)
+                .build();
+            let child_nodes: Vec<FilterNode> = match node {
+                FilterNode::All(children) | FilterNode::Any(children) => children.clone(),
+                FilterNode::Not(child) => vec![(**child).clone()],
+                FilterNode::Leaf(_) => Vec::new(),
+            };
+            for (i, child) in child_nodes.iter().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(i);
+                let child_widget = build_filter_node_widget_wired(
+                    child, child_path, filter_root, active_filters, current_sort,
+                    all_notes, notes, list_store, status_bar, table_webview, base,
+                    base_id, i18n, container, selection,
+                );
+                body.append(&child_widget);
+            }
+            group.append(&body);
+            group.upcast()
+        }
+    }
+}
+
+/// Conectar el último botón de cierre de `chip` para borrar `path` del árbol.
+#[allow(clippy::too_many_arguments)]
+fn wire_filter_node_close(
+    chip: &gtk::Box,
+    path: Vec<usize>,
+    filter_root: &Rc<RefCell<FilterNode>>,
+    active_filters: &Rc<RefCell<Vec<Filter>>>,
+    current_sort: &Rc<RefCell<Vec<SortConfig>>>,
+    all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    list_store: &gio::ListStore,
+    status_bar: &gtk::Box,
+    table_webview: &webkit6::WebView,
+    base: &Rc<RefCell<Option<Base>>>,
+    base_id: &Rc<RefCell<Option<i64>>>,
+    i18n: &Rc<RefCell<I18n>>,
+    container: &gtk::Box,
+    selection: &gtk::MultiSelection,
+) {
+    let Some(close_btn) = chip.last_child().and_downcast::<gtk::Button>() else {
+        return;
+    };
+    let filter_root = filter_root.clone();
+    let active_filters = active_filters.clone();
+    let current_sort = current_sort.clone();
+    let all_notes = all_notes.clone();
+    let notes = notes.clone();
+    let list_store = list_store.clone();
+    let status_bar = status_bar.clone();
+    let table_webview = table_webview.clone();
+    let base = base.clone();
+    let base_id = base_id.clone();
+    let i18n = i18n.clone();
+    let container = container.clone();
+    let selection = selection.clone();
+    close_btn.connect_clicked(move |_| {
+        {
+            let mut root = filter_root.borrow_mut();
+            if !root.remove_at(&path) {
+                return;
+            }
+            // Podar los grupos que han quedado sin hojas tras el borrado.
+            root.prune_empty();
+        }
+        *active_filters.borrow_mut() =
+            filter_root.borrow().leaves().into_iter().cloned().collect();
+        apply_sort_and_refresh(
+            &current_sort, &all_notes, &notes, &filter_root,
+            &list_store, &status_bar, &table_webview, &base, &base_id, &selection,
+        );
+        render_filter_tree_chips(
+            &container, &filter_root, &active_filters, &current_sort, &all_notes,
+            &notes, &list_store, &status_bar, &table_webview, &base, &base_id, &i18n,
+            &selection,
+        );
+    });
+}
+
+/// Conectar el botón de combinador de un grupo para alternar entre `All`
+/// (Y) y `Any` (O) al pulsarlo, re-evaluando y redibujando los chips.
+#[allow(clippy::too_many_arguments)]
+fn wire_filter_group_toggle(
+    toggle_btn: &gtk::Button,
+    path: Vec<usize>,
+    filter_root: &Rc<RefCell<FilterNode>>,
+    active_filters: &Rc<RefCell<Vec<Filter>>>,
+    current_sort: &Rc<RefCell<Vec<SortConfig>>>,
+    all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    list_store: &gio::ListStore,
+    status_bar: &gtk::Box,
+    table_webview: &webkit6::WebView,
+    base: &Rc<RefCell<Option<Base>>>,
+    base_id: &Rc<RefCell<Option<i64>>>,
+    i18n: &Rc<RefCell<I18n>>,
+    container: &gtk::Box,
+    selection: &gtk::MultiSelection,
+) {
+    let filter_root = filter_root.clone();
+    let active_filters = active_filters.clone();
+    let current_sort = current_sort.clone();
+    let all_notes = all_notes.clone();
+    let notes = notes.clone();
+    let list_store = list_store.clone();
+    let status_bar = status_bar.clone();
+    let table_webview = table_webview.clone();
+    let base = base.clone();
+    let base_id = base_id.clone();
+    let i18n = i18n.clone();
+    let container = container.clone();
+    let selection = selection.clone();
+    toggle_btn.connect_clicked(move |_| {
+        {
+            let mut root = filter_root.borrow_mut();
+            let Some(node) = root.node_at_mut(&path) else { return };
+            *node = match std::mem::take(node) {
+                FilterNode::All(children) => FilterNode::Any(children),
+                FilterNode::Any(children) => FilterNode::All(children),
+                other => other,
+            };
+        }
+        apply_sort_and_refresh(
+            &current_sort, &all_notes, &notes, &filter_root,
+            &list_store, &status_bar, &table_webview, &base, &base_id, &selection,
+        );
+        render_filter_tree_chips(
+            &container, &filter_root, &active_filters, &current_sort, &all_notes,
+            &notes, &list_store, &status_bar, &table_webview, &base, &base_id, &i18n,
+            &selection,
+        );
+    });
+}
+
+/// Etiqueta del combinador de un grupo de filtros para su cabecera.
+fn filter_node_label(node: &FilterNode) -> &'static str {
+    match node {
+        FilterNode::All(_) => "ALL",
+        FilterNode::Any(_) => "ANY",
+        FilterNode::Not(_) => "NOT",
+        FilterNode::Leaf(_) => "",
+    }
+}
+
+/// Puntuación de coincidencia difusa por subsecuencia.
+///
+/// Una consulta casa con un candidato si sus caracteres aparecen en orden
+/// (no necesariamente contiguos). La puntuación recompensa las coincidencias
+/// consecutivas y las que caen en un límite de palabra (tras un separador o
+/// un salto camelCase). Devuelve `None` si la consulta no es subsecuencia del
+/// candidato. La comparación es insensible a mayúsculas.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let cand_chars: Vec<char> = candidate.chars().collect();
+    let query_lower: Vec<char> = query.to_lowercase().chars().collect();
+
+    let mut score = 0i32;
+    let mut q = 0usize;
+    let mut prev_matched = false;
+
+    for (i, &c) in cand_chars.iter().enumerate() {
+        if q >= query_lower.len() {
+            break;
+        }
+        let c_lower = c.to_lowercase().next().unwrap_or(c);
+        if c_lower == query_lower[q] {
+            // Puntuación base por carácter casado.
+            score += 1;
+
+            // Bonus por coincidencias consecutivas.
+            if prev_matched {
+                score += 5;
+            }
+
+            // Bonus por límite de palabra: inicio, tras separador o camelCase.
+            let at_boundary = i == 0
+                || {
+                    let prev = cand_chars[i - 1];
+                    !prev.is_alphanumeric()
+                        || (prev.is_lowercase() && c.is_uppercase())
+                };
+            if at_boundary {
+                score += 10;
+            }
+
+            q += 1;
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+    }
+
+    if q == query_lower.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+/// Acción ejecutable desde la paleta de comandos.
+#[derive(Debug, Clone)]
+pub enum PaletteAction {
+    /// Cambiar a la vista con el índice dado.
+    SwitchView(usize),
+    /// Alternar la vista de grafo.
+    ToggleGraph,
+    /// Abrir el popover de filtros.
+    AddFilter,
+    /// Abrir el popover de ordenamiento.
+    Sort,
+    /// Abrir el popover de columnas.
+    Columns,
+    /// Cambiar el modo de origen de datos.
+    ToggleSourceType,
+}
+
+/// Candidato de la paleta con su etiqueta visible y la acción asociada.
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub action: PaletteAction,
+}
+
+/// Filtrar y ordenar candidatos de la paleta por puntuación difusa descendente.
+pub fn rank_palette_entries(entries: &[PaletteEntry], query: &str) -> Vec<PaletteEntry> {
+    let mut scored: Vec<(i32, PaletteEntry)> = entries
+        .iter()
+        .filter_map(|e| fuzzy_score(query, &e.label).map(|s| (s, e.clone())))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, e)| e).collect()
+}
+
 /// Convertir operador a símbolo visual
 fn operator_to_symbol(op: &FilterOperator) -> &'static str {
     match op {
@@ -2264,6 +5516,7 @@ fn operator_to_symbol(op: &FilterOperator) -> &'static str {
         FilterOperator::EndsWith => "ends with",
         FilterOperator::IsEmpty => "is empty",
         FilterOperator::IsNotEmpty => "is not empty",
+        FilterOperator::Matches => "matches",
     }
 }
 
@@ -2282,6 +5535,7 @@ fn index_to_operator(index: usize) -> FilterOperator {
         9 => FilterOperator::EndsWith,
         10 => FilterOperator::IsEmpty,
         11 => FilterOperator::IsNotEmpty,
+        12 => FilterOperator::Matches,
         _ => FilterOperator::Contains,
     }
 }
@@ -2307,29 +5561,19 @@ fn parse_filter_value(text: &str) -> PropertyValue {
     PropertyValue::Text(trimmed.to_string())
 }
 
-/// Actualizar los chips de filtros en el contenedor
-fn update_filter_chips_in_container(container: &gtk::Box, filters: &[Filter]) {
-    // Limpiar chips existentes
-    while let Some(child) = container.first_child() {
-        container.remove(&child);
-    }
-    
-    if filters.is_empty() {
-        let placeholder = gtk::Label::builder()
-            .label("No filters")
-            .css_classes(["dim-label"])
-            .build();
-        container.append(&placeholder);
-    } else {
-        for (i, filter) in filters.iter().enumerate() {
-            let chip = create_filter_chip(filter, i);
-            container.append(&chip);
-        }
-    }
-}
-
 /// Crear el popover para añadir filtros (devuelve referencias a los widgets)
-pub fn create_filter_popover_with_refs(properties: &[String], i18n: &I18n) -> (gtk::Popover, gtk::DropDown, gtk::DropDown, gtk::Entry) {
+///
+/// Cuando la propiedad seleccionada es `tags` y hay etiquetas conocidas en
+/// `distinct_tags`, el campo de valor libre se sustituye por una lista de
+/// casillas (una por etiqueta distinta), para no depender de texto escrito a
+/// mano. El widget de casillas se devuelve junto al resto de referencias;
+/// `setup_filter_popover` decide en el botón Apply cuál de los dos leer según
+/// cuál esté visible.
+pub fn create_filter_popover_with_refs(
+    properties: &[String],
+    distinct_tags: &[String],
+    i18n: &I18n,
+) -> (gtk::Popover, gtk::DropDown, gtk::DropDown, gtk::Entry, gtk::DropDown, gtk::Box) {
     let popover = gtk::Popover::builder()
         .css_classes(["filter-popover"])
         .build();
@@ -2387,6 +5631,7 @@ pub fn create_filter_popover_with_refs(properties: &[String], i18n: &I18n) -> (g
         i18n.t("filter_op_ends_with"),
         i18n.t("filter_op_is_empty"),
         i18n.t("filter_op_is_not_empty"),
+        i18n.t("filter_op_matches"),
     ];
     let op_strs: Vec<&str> = operators.iter().map(|s| s.as_str()).collect();
     let op_combo = gtk::DropDown::from_strings(&op_strs);
@@ -2404,7 +5649,52 @@ pub fn create_filter_popover_with_refs(properties: &[String], i18n: &I18n) -> (g
         .placeholder_text(&i18n.t("base_filter_value_placeholder"))
         .build();
     content.append(&value_entry);
-    
+
+    // Casillas de etiquetas conocidas, alternativa al campo de valor libre
+    // cuando la propiedad elegida es `tags`. Oculto por defecto.
+    let tags_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(2)
+        .visible(false)
+        .build();
+    for tag in distinct_tags {
+        let check = gtk::CheckButton::builder().label(tag).build();
+        tags_box.append(&check);
+    }
+    content.append(&tags_box);
+
+    if !distinct_tags.is_empty() {
+        let value_label = value_label.clone();
+        let value_entry = value_entry.clone();
+        let tags_box = tags_box.clone();
+        let properties: Vec<String> = properties.to_vec();
+        prop_combo.connect_selected_notify(move |combo| {
+            let is_tags = properties
+                .get(combo.selected() as usize)
+                .is_some_and(|p| p == "tags");
+            value_label.set_visible(!is_tags);
+            value_entry.set_visible(!is_tags);
+            tags_box.set_visible(is_tags);
+        });
+    }
+
+    // Combinador: cómo se une este filtro con los ya existentes. "Y" lo añade
+    // al alta rápida plana de siempre; "O" envuelve el árbol actual y esta
+    // hoja en un grupo `Any` nuevo, para expresar consultas tipo
+    // "a = 1 (Y existente) O b = 2".
+    let combine_label = gtk::Label::builder()
+        .label(&i18n.t("base_filter_combine_with"))
+        .xalign(0.0)
+        .css_classes(["dim-label"])
+        .build();
+    content.append(&combine_label);
+
+    let combine_combo = gtk::DropDown::from_strings(&[
+        &i18n.t("base_filter_combine_and"),
+        &i18n.t("base_filter_combine_or"),
+    ]);
+    content.append(&combine_combo);
+
     // Botones
     let buttons = gtk::Box::builder()
         .orientation(gtk::Orientation::Horizontal)
@@ -2433,8 +5723,8 @@ pub fn create_filter_popover_with_refs(properties: &[String], i18n: &I18n) -> (g
     content.append(&buttons);
     
     popover.set_child(Some(&content));
-    
-    (popover, prop_combo, op_combo, value_entry)
+
+    (popover, prop_combo, op_combo, value_entry, combine_combo, tags_box)
 }
 
 /// Crear el popover para añadir filtros
@@ -2539,22 +5829,25 @@ pub fn create_filter_popover(properties: &[String]) -> gtk::Popover {
 }
 
 /// Crear el popover de ordenamiento con callbacks conectados
-pub fn create_sort_popover_with_callbacks(
+/// Construir el contenido del popover de ordenamiento para `properties` (las
+/// columnas visibles de la vista activa), reutilizando `popover` ya existente
+/// solo para poder cerrarlo (`popdown`) desde los botones.
+#[allow(clippy::too_many_arguments)]
+pub fn build_sort_popover_content(
     properties: &[String],
-    current_sort: Rc<RefCell<Option<SortConfig>>>,
+    popover: &gtk::Popover,
+    current_sort: Rc<RefCell<Vec<SortConfig>>>,
     all_notes: Rc<RefCell<Vec<NoteWithProperties>>>,
     notes: Rc<RefCell<Vec<NoteWithProperties>>>,
-    active_filters: Rc<RefCell<Vec<Filter>>>,
+    filter_root: Rc<RefCell<FilterNode>>,
     list_store: gio::ListStore,
     status_bar: gtk::Box,
     table_webview: webkit6::WebView,
     base: Rc<RefCell<Option<Base>>>,
+    base_id: Rc<RefCell<Option<i64>>>,
     i18n: &I18n,
-) -> gtk::Popover {
-    let popover = gtk::Popover::builder()
-        .css_classes(["sort-popover"])
-        .build();
-    
+    selection: gtk::MultiSelection,
+) -> gtk::Box {
     let content = gtk::Box::builder()
         .orientation(gtk::Orientation::Vertical)
         .spacing(4)
@@ -2569,46 +5862,223 @@ pub fn create_sort_popover_with_callbacks(
         .label(&i18n.t("base_sort_by"))
         .css_classes(["heading"])
         .xalign(0.0)
-        .margin_bottom(8)
-        .build();
-    content.append(&title);
-    
-    // Opción para quitar ordenamiento
-    let none_btn = gtk::Button::builder()
-        .label(&i18n.t("base_no_sorting"))
-        .css_classes(["flat"])
-        .hexpand(true)
+        .margin_bottom(8)
+        .build();
+    content.append(&title);
+    
+    // Opción para quitar ordenamiento
+    let none_btn = gtk::Button::builder()
+        .label(&i18n.t("base_no_sorting"))
+        .css_classes(["flat"])
+        .hexpand(true)
+        .build();
+
+    {
+        let current_sort = current_sort.clone();
+        let all_notes = all_notes.clone();
+        let notes = notes.clone();
+        let filter_root = filter_root.clone();
+        let list_store = list_store.clone();
+        let status_bar = status_bar.clone();
+        let table_webview = table_webview.clone();
+        let base = base.clone();
+        let base_id = base_id.clone();
+        let popover = popover.clone();
+        let selection = selection.clone();
+
+        none_btn.connect_clicked(move |_| {
+            current_sort.borrow_mut().clear();
+            apply_sort_and_refresh(
+                &current_sort, &all_notes, &notes, &filter_root,
+                &list_store, &status_bar, &table_webview, &base, &base_id, &selection
+            );
+            popover.popdown();
+        });
+    }
+    content.append(&none_btn);
+
+    content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+    // Niveles activos: el primero es el primario (resaltado), los siguientes
+    // solo desempatan. Cada uno se puede reordenar, invertir o quitar.
+    let levels = current_sort.borrow().clone();
+    if !levels.is_empty() {
+        let levels_title = gtk::Label::builder()
+            .label(&i18n.t("base_sort_active_levels"))
+            .css_classes(["dim-label"])
+            .xalign(0.0)
+            .build();
+        content.append(&levels_title);
+
+        for (i, level) in levels.iter().enumerate() {
+            let row = gtk::Box::builder()
+                .orientation(gtk::Orientation::Horizontal)
+                .spacing(4)
+                .css_classes(["sort-row"])
+                .margin_top(2)
+                .margin_bottom(2)
+                .build();
+
+            let level_label = gtk::Label::builder()
+                .label(format!(
+                    "{}. {} {}",
+                    i + 1,
+                    level.property,
+                    if level.direction == SortDirection::Asc { "↑" } else { "↓" }
+                ))
+                .hexpand(true)
+                .xalign(0.0)
+                .css_classes(if i == 0 { vec!["sort-level-primary"] } else { vec![] })
+                .build();
+            row.append(&level_label);
+
+            let up_btn = gtk::Button::builder()
+                .icon_name("go-up-symbolic")
+                .css_classes(["flat", "circular"])
+                .sensitive(i > 0)
+                .tooltip_text(&i18n.t("base_sort_move_up"))
+                .build();
+            {
+                let current_sort = current_sort.clone();
+                let all_notes = all_notes.clone();
+                let notes = notes.clone();
+                let filter_root = filter_root.clone();
+                let list_store = list_store.clone();
+                let status_bar = status_bar.clone();
+                let table_webview = table_webview.clone();
+                let base = base.clone();
+                let base_id = base_id.clone();
+                let popover = popover.clone();
+                let selection = selection.clone();
+                up_btn.connect_clicked(move |_| {
+                    if i > 0 {
+                        current_sort.borrow_mut().swap(i, i - 1);
+                    }
+                    apply_sort_and_refresh(
+                        &current_sort, &all_notes, &notes, &filter_root,
+                        &list_store, &status_bar, &table_webview, &base, &base_id, &selection
+                    );
+                    popover.popdown();
+                });
+            }
+            row.append(&up_btn);
+
+            let down_btn = gtk::Button::builder()
+                .icon_name("go-down-symbolic")
+                .css_classes(["flat", "circular"])
+                .sensitive(i + 1 < levels.len())
+                .tooltip_text(&i18n.t("base_sort_move_down"))
+                .build();
+            {
+                let current_sort = current_sort.clone();
+                let all_notes = all_notes.clone();
+                let notes = notes.clone();
+                let filter_root = filter_root.clone();
+                let list_store = list_store.clone();
+                let status_bar = status_bar.clone();
+                let table_webview = table_webview.clone();
+                let base = base.clone();
+                let base_id = base_id.clone();
+                let popover = popover.clone();
+                let levels_len = levels.len();
+                let selection = selection.clone();
+                down_btn.connect_clicked(move |_| {
+                    if i + 1 < levels_len {
+                        current_sort.borrow_mut().swap(i, i + 1);
+                    }
+                    apply_sort_and_refresh(
+                        &current_sort, &all_notes, &notes, &filter_root,
+                        &list_store, &status_bar, &table_webview, &base, &base_id, &selection
+                    );
+                    popover.popdown();
+                });
+            }
+            row.append(&down_btn);
+
+            let toggle_dir_btn = gtk::Button::builder()
+                .icon_name("object-flip-vertical-symbolic")
+                .css_classes(["flat", "circular"])
+                .tooltip_text(&i18n.t("base_sort_toggle_direction"))
+                .build();
+            {
+                let current_sort = current_sort.clone();
+                let all_notes = all_notes.clone();
+                let notes = notes.clone();
+                let filter_root = filter_root.clone();
+                let list_store = list_store.clone();
+                let status_bar = status_bar.clone();
+                let table_webview = table_webview.clone();
+                let base = base.clone();
+                let base_id = base_id.clone();
+                let popover = popover.clone();
+                let selection = selection.clone();
+                toggle_dir_btn.connect_clicked(move |_| {
+                    if let Some(level) = current_sort.borrow_mut().get_mut(i) {
+                        level.direction = match level.direction {
+                            SortDirection::Asc => SortDirection::Desc,
+                            SortDirection::Desc => SortDirection::Asc,
+                        };
+                    }
+                    apply_sort_and_refresh(
+                        &current_sort, &all_notes, &notes, &filter_root,
+                        &list_store, &status_bar, &table_webview, &base, &base_id, &selection
+                    );
+                    popover.popdown();
+                });
+            }
+            row.append(&toggle_dir_btn);
+
+            let remove_btn = gtk::Button::builder()
+                .icon_name("window-close-symbolic")
+                .css_classes(["flat", "circular"])
+                .tooltip_text(&i18n.t("base_sort_remove_level"))
+                .build();
+            {
+                let current_sort = current_sort.clone();
+                let all_notes = all_notes.clone();
+                let notes = notes.clone();
+                let filter_root = filter_root.clone();
+                let list_store = list_store.clone();
+                let status_bar = status_bar.clone();
+                let table_webview = table_webview.clone();
+                let base = base.clone();
+                let base_id = base_id.clone();
+                let popover = popover.clone();
+                let selection = selection.clone();
+                remove_btn.connect_clicked(move |_| {
+                    let mut levels = current_sort.borrow_mut();
+                    if i < levels.len() {
+                        levels.remove(i);
+                    }
+                    drop(levels);
+                    apply_sort_and_refresh(
+                        &current_sort, &all_notes, &notes, &filter_root,
+                        &list_store, &status_bar, &table_webview, &base, &base_id, &selection
+                    );
+                    popover.popdown();
+                });
+            }
+            row.append(&remove_btn);
+
+            content.append(&row);
+        }
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+    }
+
+    // Añadir un nivel nuevo (desempate) por cada propiedad que todavía no
+    // forme parte de los niveles activos.
+    let add_level_title = gtk::Label::builder()
+        .label(&i18n.t(if levels.is_empty() { "base_sort_by" } else { "base_sort_add_level" }))
+        .css_classes(["dim-label"])
+        .xalign(0.0)
         .build();
-    
-    {
-        let current_sort = current_sort.clone();
-        let all_notes = all_notes.clone();
-        let notes = notes.clone();
-        let active_filters = active_filters.clone();
-        let list_store = list_store.clone();
-        let status_bar = status_bar.clone();
-        let table_webview = table_webview.clone();
-        let base = base.clone();
-        let popover = popover.clone();
-        
-        none_btn.connect_clicked(move |_| {
-            *current_sort.borrow_mut() = None;
-            apply_sort_and_refresh(
-                &current_sort, &all_notes, &notes, &active_filters, 
-                &list_store, &status_bar, &table_webview, &base
-            );
-            popover.popdown();
-        });
-    }
-    content.append(&none_btn);
-    
-    content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
-    
-    // Una fila por cada propiedad
+    content.append(&add_level_title);
+
     let t_sort_asc = i18n.t("base_sort_ascending");
     let t_sort_desc = i18n.t("base_sort_descending");
-    
-    for prop in properties {
+
+    for prop in properties.iter().filter(|p| !levels.iter().any(|l| &l.property == *p)) {
         let row = gtk::Box::builder()
             .orientation(gtk::Orientation::Horizontal)
             .spacing(8)
@@ -2616,143 +6086,651 @@ pub fn create_sort_popover_with_callbacks(
             .margin_top(2)
             .margin_bottom(2)
             .build();
-        
+
         let prop_label = gtk::Label::builder()
             .label(prop)
             .hexpand(true)
             .xalign(0.0)
             .build();
         row.append(&prop_label);
-        
+
         // Botón ascendente
         let asc_btn = gtk::Button::builder()
             .icon_name("view-sort-ascending-symbolic")
             .tooltip_text(&t_sort_asc)
             .css_classes(["flat", "circular"])
             .build();
-        
+
         {
             let prop = prop.clone();
             let current_sort = current_sort.clone();
             let all_notes = all_notes.clone();
             let notes = notes.clone();
-            let active_filters = active_filters.clone();
+            let filter_root = filter_root.clone();
             let list_store = list_store.clone();
             let status_bar = status_bar.clone();
             let table_webview = table_webview.clone();
             let base = base.clone();
+            let base_id = base_id.clone();
             let popover = popover.clone();
-            
+            let selection = selection.clone();
+
             asc_btn.connect_clicked(move |_| {
-                *current_sort.borrow_mut() = Some(SortConfig {
+                current_sort.borrow_mut().push(SortConfig {
                     property: prop.clone(),
                     direction: SortDirection::Asc,
+                    null_order: NullOrder::default(),
                 });
                 apply_sort_and_refresh(
-                    &current_sort, &all_notes, &notes, &active_filters,
-                    &list_store, &status_bar, &table_webview, &base
+                    &current_sort, &all_notes, &notes, &filter_root,
+                    &list_store, &status_bar, &table_webview, &base, &base_id, &selection
                 );
                 popover.popdown();
             });
         }
         row.append(&asc_btn);
-        
+
         // Botón descendente
         let desc_btn = gtk::Button::builder()
             .icon_name("view-sort-descending-symbolic")
             .tooltip_text(&t_sort_desc)
             .css_classes(["flat", "circular"])
             .build();
-        
+
         {
             let prop = prop.clone();
             let current_sort = current_sort.clone();
             let all_notes = all_notes.clone();
             let notes = notes.clone();
-            let active_filters = active_filters.clone();
+            let filter_root = filter_root.clone();
             let list_store = list_store.clone();
             let status_bar = status_bar.clone();
             let table_webview = table_webview.clone();
             let base = base.clone();
+            let base_id = base_id.clone();
             let popover = popover.clone();
-            
+            let selection = selection.clone();
+
             desc_btn.connect_clicked(move |_| {
-                *current_sort.borrow_mut() = Some(SortConfig {
+                current_sort.borrow_mut().push(SortConfig {
                     property: prop.clone(),
                     direction: SortDirection::Desc,
+                    null_order: NullOrder::default(),
                 });
                 apply_sort_and_refresh(
-                    &current_sort, &all_notes, &notes, &active_filters,
-                    &list_store, &status_bar, &table_webview, &base
+                    &current_sort, &all_notes, &notes, &filter_root,
+                    &list_store, &status_bar, &table_webview, &base, &base_id, &selection
                 );
                 popover.popdown();
             });
         }
         row.append(&desc_btn);
-        
+
         content.append(&row);
     }
-    
-    popover.set_child(Some(&content));
-    popover
+
+    content
+}
+
+/// Estado compartido para (re)construir la barra de pestañas de vistas.
+///
+/// Agrupa las referencias `Rc` que necesitan los manejadores de cada pestaña
+/// (cambiar, cerrar, renombrar y reordenar vistas) para poder reconstruir la
+/// barra sin tomar prestado `&self`.
+#[derive(Clone)]
+struct ViewTabsContext {
+    view_tabs: gtk::Box,
+    column_view: gtk::ColumnView,
+    base: Rc<RefCell<Option<Base>>>,
+    base_id: Rc<RefCell<Option<i64>>>,
+    notes_db: Rc<RefCell<Option<NotesDatabase>>>,
+    notes_root: Rc<RefCell<Option<std::path::PathBuf>>>,
+    active_filters: Rc<RefCell<Vec<Filter>>>,
+    filter_root: Rc<RefCell<FilterNode>>,
+    current_sort: Rc<RefCell<Vec<SortConfig>>>,
+    all_notes: Rc<RefCell<Vec<NoteWithProperties>>>,
+    notes: Rc<RefCell<Vec<NoteWithProperties>>>,
+    list_store: gio::ListStore,
+    selection: gtk::MultiSelection,
+    status_bar: gtk::Box,
+    table_webview: webkit6::WebView,
+    filters_container: gtk::Box,
+    i18n: Rc<RefCell<I18n>>,
+}
+
+/// Reconstruir por completo la barra de pestañas desde `base.views`.
+fn rebuild_view_tabs(ctx: &ViewTabsContext) {
+    while let Some(child) = ctx.view_tabs.first_child() {
+        ctx.view_tabs.remove(&child);
+    }
+
+    let (views_len, active) = match ctx.base.borrow().as_ref() {
+        Some(base) => (base.views.len(), base.active_view),
+        None => return,
+    };
+
+    for i in 0..views_len {
+        let name = ctx
+            .base
+            .borrow()
+            .as_ref()
+            .and_then(|b| b.views.get(i).map(|v| v.name.clone()))
+            .unwrap_or_default();
+        let is_active = i == active;
+
+        // Contenedor de la pestaña: etiqueta + botón de cierre.
+        let tab = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(2)
+            .css_classes(if is_active {
+                vec!["base-view-tab", "active"]
+            } else {
+                vec!["base-view-tab"]
+            })
+            .build();
+
+        let name_btn = gtk::Button::builder()
+            .label(&name)
+            .css_classes(["flat", "base-view-tab-label"])
+            .build();
+        {
+            let ctx = ctx.clone();
+            name_btn.connect_clicked(move |_| switch_view(&ctx, i));
+        }
+
+        // Doble clic para renombrar en el sitio.
+        let rename_gesture = gtk::GestureClick::new();
+        rename_gesture.set_button(gdk::BUTTON_PRIMARY);
+        {
+            let ctx = ctx.clone();
+            let name_btn = name_btn.clone();
+            rename_gesture.connect_pressed(move |gesture, n_press, _, _| {
+                if n_press >= 2 {
+                    gesture.set_state(gtk::EventSequenceState::Claimed);
+                    show_rename_popover(&ctx, i, &name_btn);
+                }
+            });
+        }
+        name_btn.add_controller(rename_gesture);
+        tab.append(&name_btn);
+
+        let close_btn = gtk::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text(&ctx.i18n.borrow().t("base_view_close"))
+            .css_classes(["flat", "base-view-tab-close"])
+            .build();
+        // No permitir cerrar la última vista: una Base siempre tiene ≥1 vista.
+        close_btn.set_sensitive(views_len > 1);
+        {
+            let ctx = ctx.clone();
+            close_btn.connect_clicked(move |btn| confirm_and_close_view(&ctx, i, btn));
+        }
+        tab.append(&close_btn);
+
+        // Reordenar mediante arrastrar y soltar; el índice origen viaja en el
+        // ContentProvider y el destino lo aporta la pestaña que recibe el drop.
+        let drag = gtk::DragSource::new();
+        drag.set_actions(gdk::DragAction::MOVE);
+        drag.connect_prepare(move |_, _, _| {
+            Some(gdk::ContentProvider::for_value(&(i as i32).to_value()))
+        });
+        tab.add_controller(drag);
+
+        let drop = gtk::DropTarget::new(i32::static_type(), gdk::DragAction::MOVE);
+        {
+            let ctx = ctx.clone();
+            drop.connect_drop(move |_, value, _, _| {
+                if let Ok(from) = value.get::<i32>() {
+                    reorder_views(&ctx, from as usize, i);
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+        tab.add_controller(drop);
+
+        ctx.view_tabs.append(&tab);
+    }
+
+    // Botón final para crear una vista nueva.
+    let add_view_btn = gtk::Button::builder()
+        .icon_name("list-add-symbolic")
+        .tooltip_text(&ctx.i18n.borrow().t("base_add_view"))
+        .css_classes(["flat", "base-add-view"])
+        .build();
+    {
+        let ctx = ctx.clone();
+        add_view_btn.connect_clicked(move |_| add_view(&ctx));
+    }
+    ctx.view_tabs.append(&add_view_btn);
+}
+
+/// Activar la vista `index`: re-ejecutar su query, cargar sus filtros/orden y
+/// refrescar la tabla. Mantiene `base.active_view` coherente.
+fn switch_view(ctx: &ViewTabsContext, index: usize) {
+    let (view, source_folder) = {
+        let mut base = ctx.base.borrow_mut();
+        let Some(base) = base.as_mut() else { return };
+        if index >= base.views.len() {
+            return;
+        }
+        base.active_view = index;
+        (base.views[index].clone(), base.source_folder.clone())
+    };
+
+    // Reconstruir el árbol de filtros (y su espejo plano) para la nueva vista,
+    // envolviendo una configuración antigua sin árbol en un grupo `All`.
+    let root = view
+        .filter
+        .node
+        .clone()
+        .unwrap_or_else(|| FilterNode::from_filters(view.filter.filters.clone()));
+    *ctx.active_filters.borrow_mut() = root.leaves().into_iter().cloned().collect();
+    *ctx.filter_root.borrow_mut() = root;
+    *ctx.current_sort.borrow_mut() = view.sort.clone();
+
+    if let (Some(db), Some(root)) = (ctx.notes_db.borrow().as_ref(), ctx.notes_root.borrow().as_ref()) {
+        let engine = BaseQueryEngine::new(db, root);
+        match engine.query_view(&view, source_folder.as_deref()) {
+            Ok(mut notes) => {
+                BaseTableWidget::merge_content_hashtags(&mut notes);
+                *ctx.all_notes.borrow_mut() = notes;
+            }
+            Err(e) => eprintln!("Error executing Base query: {}", e),
+        }
+    }
+
+    BaseTableWidget::rebuild_column_view(
+        &ctx.column_view,
+        &view.columns,
+        &ctx.base,
+        &ctx.base_id,
+        &ctx.notes_db,
+    );
+    // Cambiar de vista descarta la selección: pertenecía a las notas de la
+    // vista anterior, que ya no son las que se muestran.
+    ctx.selection.unselect_all();
+    apply_sort_and_refresh(
+        &ctx.current_sort,
+        &ctx.all_notes,
+        &ctx.notes,
+        &ctx.filter_root,
+        &ctx.list_store,
+        &ctx.status_bar,
+        &ctx.table_webview,
+        &ctx.base,
+        &ctx.base_id,
+        &ctx.selection,
+    );
+
+    // Los chips de filtro viven fuera de la consulta: sin esto mostrarían los
+    // filtros de la vista anterior hasta la siguiente edición manual.
+    render_filter_tree_chips(
+        &ctx.filters_container, &ctx.filter_root, &ctx.active_filters,
+        &ctx.current_sort, &ctx.all_notes, &ctx.notes, &ctx.list_store,
+        &ctx.status_bar, &ctx.table_webview, &ctx.base, &ctx.base_id, &ctx.i18n,
+        &ctx.selection,
+    );
+
+    persist_base(ctx);
+    // Re-pintar las pestañas para reflejar la nueva activa.
+    rebuild_view_tabs(ctx);
+}
+
+/// Pedir confirmación antes de eliminar una vista.
+fn confirm_and_close_view(ctx: &ViewTabsContext, index: usize, anchor: &gtk::Button) {
+    let window = anchor.root().and_downcast::<gtk::Window>();
+    let (cancel, delete) = {
+        let i18n = ctx.i18n.borrow();
+        (i18n.t("cancel"), i18n.t("delete"))
+    };
+    let dialog = gtk::AlertDialog::builder()
+        .message(ctx.i18n.borrow().t("base_view_delete_confirm"))
+        .modal(true)
+        .cancel_button(0)
+        .default_button(1)
+        .build();
+    dialog.set_buttons(&[cancel.as_str(), delete.as_str()]);
+
+    let ctx = ctx.clone();
+    dialog.choose(window.as_ref(), None::<&gio::Cancellable>, move |res| {
+        if res == Ok(1) {
+            delete_view(&ctx, index);
+        }
+    });
+}
+
+/// Eliminar la vista `index`, ajustando `active_view` y recargando.
+fn delete_view(ctx: &ViewTabsContext, index: usize) {
+    {
+        let mut base = ctx.base.borrow_mut();
+        let Some(base) = base.as_mut() else { return };
+        if base.views.len() <= 1 || index >= base.views.len() {
+            return;
+        }
+        base.views.remove(index);
+        if base.active_view >= base.views.len() {
+            base.active_view = base.views.len() - 1;
+        } else if index < base.active_view {
+            base.active_view -= 1;
+        }
+    }
+    let active = ctx.base.borrow().as_ref().map(|b| b.active_view).unwrap_or(0);
+    switch_view(ctx, active);
+}
+
+/// Crear una vista nueva clonando la activa como plantilla y cambiar a ella.
+fn add_view(ctx: &ViewTabsContext) {
+    let new_index = {
+        let mut base = ctx.base.borrow_mut();
+        let Some(base) = base.as_mut() else { return };
+        let Some(mut view) = base
+            .views
+            .get(base.active_view)
+            .or_else(|| base.views.first())
+            .cloned()
+        else {
+            return;
+        };
+        view.name = ctx.i18n.borrow().t("base_view_new");
+        view.filter.filters.clear();
+        view.sort = Vec::new();
+        view.group_by = None;
+        base.views.push(view);
+        base.views.len() - 1
+    };
+    switch_view(ctx, new_index);
+}
+
+/// Mover la vista `from` a la posición `to`, preservando cuál queda activa.
+fn reorder_views(ctx: &ViewTabsContext, from: usize, to: usize) {
+    if from == to {
+        return;
+    }
+    {
+        let mut base = ctx.base.borrow_mut();
+        let Some(base) = base.as_mut() else { return };
+        if from >= base.views.len() || to >= base.views.len() {
+            return;
+        }
+        let view = base.views.remove(from);
+        base.views.insert(to, view);
+
+        // Mantener `active_view` apuntando a la misma vista lógica tras el
+        // remove+insert.
+        let active = base.active_view;
+        base.active_view = if active == from {
+            to
+        } else {
+            let mut a = active;
+            if from < a {
+                a -= 1;
+            }
+            if to <= a {
+                a += 1;
+            }
+            a
+        };
+    }
+    let active = ctx.base.borrow().as_ref().map(|b| b.active_view).unwrap_or(0);
+    switch_view(ctx, active);
+}
+
+/// Renombrar una vista en el sitio mediante un pequeño popover con un `Entry`.
+fn show_rename_popover(ctx: &ViewTabsContext, index: usize, anchor: &gtk::Button) {
+    let current = ctx
+        .base
+        .borrow()
+        .as_ref()
+        .and_then(|b| b.views.get(index).map(|v| v.name.clone()))
+        .unwrap_or_default();
+
+    let popover = gtk::Popover::builder().has_arrow(true).build();
+    let entry = gtk::Entry::builder().text(&current).build();
+    popover.set_child(Some(&entry));
+    popover.set_parent(anchor);
+
+    {
+        let ctx = ctx.clone();
+        let popover = popover.clone();
+        entry.connect_activate(move |e| {
+            let name = e.text().trim().to_string();
+            if !name.is_empty() {
+                if let Some(base) = ctx.base.borrow_mut().as_mut() {
+                    if let Some(view) = base.views.get_mut(index) {
+                        view.name = name;
+                    }
+                }
+                persist_base(&ctx);
+                rebuild_view_tabs(&ctx);
+            }
+            popover.popdown();
+        });
+    }
+    popover.connect_closed(|popover| popover.unparent());
+    popover.popup();
+    entry.grab_focus();
+}
+
+/// Persistir la Base actual en la BD (vistas, orden y vista activa).
+fn persist_base(ctx: &ViewTabsContext) {
+    if let (Some(id), Some(db), Some(base)) = (
+        ctx.base_id.borrow().as_ref(),
+        ctx.notes_db.borrow().as_ref(),
+        ctx.base.borrow().as_ref(),
+    ) {
+        if let Ok(yaml) = base.serialize() {
+            if let Err(e) = db.update_base(*id, &yaml, base.active_view as i32) {
+                eprintln!("Error saving Base config: {}", e);
+            }
+        }
+    }
+}
+
+/// Recompilar la búsqueda y refrescar la tabla (versión libre para closures).
+///
+/// Combina el predicado de búsqueda (ANDed) con `filter_root`, reutilizando
+/// el mismo pipeline de filtrado/orden que `apply_filters_and_sort`.
+#[allow(clippy::too_many_arguments)]
+fn apply_search_and_refresh(
+    search_query: &Rc<RefCell<String>>,
+    search_options: &Rc<RefCell<SearchOptions>>,
+    search_regex: &Rc<RefCell<Option<Result<regex::Regex, String>>>>,
+    current_sort: &Rc<RefCell<Vec<SortConfig>>>,
+    all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
+    filter_root: &Rc<RefCell<FilterNode>>,
+    list_store: &gio::ListStore,
+    status_bar: &gtk::Box,
+    table_webview: &webkit6::WebView,
+    base: &Rc<RefCell<Option<Base>>>,
+    base_id: &Rc<RefCell<Option<i64>>>,
+    i18n: &Rc<RefCell<I18n>>,
+    selection: &gtk::MultiSelection,
+) {
+    // Recompilar el Regex según consulta + opciones.
+    {
+        let query = search_query.borrow().clone();
+        if query.trim().is_empty() {
+            *search_regex.borrow_mut() = None;
+        } else {
+            let opts = search_options.borrow().clone();
+            let mut pattern = if opts.regex { query } else { regex::escape(&query) };
+            if opts.whole_word {
+                pattern = format!(r"\b(?:{})\b", pattern);
+            }
+            let compiled = regex::RegexBuilder::new(&pattern)
+                .case_insensitive(!opts.case_sensitive)
+                .build()
+                .map_err(|e| e.to_string());
+            *search_regex.borrow_mut() = Some(compiled);
+        }
+    }
+
+    let columns: Vec<ColumnConfig> = base
+        .borrow()
+        .as_ref()
+        .and_then(|b| b.active_view().map(|v| v.columns.clone()))
+        .unwrap_or_default();
+    let search = search_regex.borrow();
+    let invalid_pattern = matches!(search.as_ref(), Some(Err(_)));
+
+    let all = all_notes.borrow();
+    let filter_root = filter_root.borrow();
+    let sort = current_sort.borrow();
+
+    let mut filtered: Vec<NoteWithProperties> = all
+        .iter()
+        .filter(|note| filter_root.evaluate(&note.properties))
+        .filter(|note| BaseTableWidget::note_matches_search(note, &columns, search.as_ref()))
+        .cloned()
+        .collect();
+
+    if !sort.is_empty() {
+        filtered.sort_by(|a, b| compare_by_sort_levels(a, b, &sort));
+    }
+
+    drop(all);
+    drop(filter_root);
+    drop(sort);
+    drop(search);
+
+    let selected_ids = selected_note_ids_before_refresh(selection, &notes.borrow());
+    *notes.borrow_mut() = filtered.clone();
+
+    list_store.remove_all();
+    for note in &filtered {
+        list_store.append(&glib::BoxedAnyObject::new(note.clone()));
+    }
+
+    let group_by = base.borrow().as_ref().and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+    let theme = base.borrow().as_ref().and_then(|b| b.theme).unwrap_or_default();
+    let html = BaseTableWidget::render_table_html_static(
+        &filtered, &columns, i18n.borrow().current_language(),
+        group_by.as_deref(), &std::collections::HashSet::new(),
+        base_id.borrow().unwrap_or(-1), theme,
+    );
+    table_webview.load_html(&html, None);
+    restore_selection_by_identity(selection, &selected_ids, &filtered);
+
+    if let Some(label) = status_bar.first_child().and_downcast::<gtk::Label>() {
+        if invalid_pattern {
+            label.set_text(&i18n.borrow().t("base_search_invalid"));
+        } else if filtered.len() == 1 {
+            label.set_text("1 note");
+        } else {
+            label.set_text(&format!("{} notes", filtered.len()));
+        }
+    }
+}
+
+/// Comparar dos notas según una lista ordenada de niveles de ordenamiento.
+///
+/// El primer nivel es el primario; los siguientes solo desempatan entre
+/// notas que el nivel anterior considerase iguales (pliegue estable con
+/// `then_with`, que no vuelve a comparar si ya hay un orden decidido).
+fn compare_by_sort_levels(
+    a: &NoteWithProperties,
+    b: &NoteWithProperties,
+    levels: &[SortConfig],
+) -> std::cmp::Ordering {
+    levels.iter().fold(std::cmp::Ordering::Equal, |acc, level| {
+        acc.then_with(|| {
+            let key_a = a
+                .properties
+                .get(&level.property)
+                .map(SortKey::from_property)
+                .unwrap_or(SortKey::Empty);
+            let key_b = b
+                .properties
+                .get(&level.property)
+                .map(SortKey::from_property)
+                .unwrap_or(SortKey::Empty);
+            let ordering = compare_sort_keys(&key_a, &key_b, level.null_order);
+            match level.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            }
+        })
+    })
+}
+
+/// Recordar qué notas están seleccionadas identificándolas por `id` (no por
+/// posición), ya que `list_store.remove_all()` olvida toda selección basada
+/// en índice al reconstruir la tabla tras un filtro/orden/búsqueda.
+fn selected_note_ids_before_refresh(
+    selection: &gtk::MultiSelection,
+    old_notes: &[NoteWithProperties],
+) -> std::collections::HashSet<i64> {
+    (0..old_notes.len() as u32)
+        .filter(|&i| selection.is_selected(i))
+        .map(|i| old_notes[i as usize].metadata.id)
+        .collect()
+}
+
+/// Reaplicar la selección recordada por `selected_note_ids_before_refresh`
+/// sobre las notas recién filtradas/ordenadas, buscando cada nota por su
+/// identidad en vez de asumir que conserva su posición anterior.
+fn restore_selection_by_identity(
+    selection: &gtk::MultiSelection,
+    selected_ids: &std::collections::HashSet<i64>,
+    new_notes: &[NoteWithProperties],
+) {
+    selection.unselect_all();
+    if selected_ids.is_empty() {
+        return;
+    }
+    for (idx, note) in new_notes.iter().enumerate() {
+        if selected_ids.contains(&note.metadata.id) {
+            selection.select_item(idx as u32, false);
+        }
+    }
 }
 
 /// Aplicar ordenamiento y refrescar la UI
 fn apply_sort_and_refresh(
-    current_sort: &Rc<RefCell<Option<SortConfig>>>,
+    current_sort: &Rc<RefCell<Vec<SortConfig>>>,
     all_notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
     notes: &Rc<RefCell<Vec<NoteWithProperties>>>,
-    active_filters: &Rc<RefCell<Vec<Filter>>>,
+    filter_root: &Rc<RefCell<FilterNode>>,
     list_store: &gio::ListStore,
     status_bar: &gtk::Box,
     table_webview: &webkit6::WebView,
     base: &Rc<RefCell<Option<Base>>>,
+    base_id: &Rc<RefCell<Option<i64>>>,
+    selection: &gtk::MultiSelection,
 ) {
     let all = all_notes.borrow();
-    let filters = active_filters.borrow();
+    let filter_root = filter_root.borrow();
     let sort = current_sort.borrow();
-    
+
     // Filtrar
     let mut filtered: Vec<NoteWithProperties> = all
         .iter()
-        .filter(|note| {
-            filters.iter().all(|f| f.evaluate(&note.properties))
-        })
+        .filter(|note| filter_root.evaluate(&note.properties))
         .cloned()
         .collect();
-    
-    // Ordenar
-    if let Some(sort_config) = sort.as_ref() {
-        filtered.sort_by(|a, b| {
-            let key_a = a.properties
-                .get(&sort_config.property)
-                .map(|v| v.sort_key())
-                .unwrap_or_default();
-            let key_b = b.properties
-                .get(&sort_config.property)
-                .map(|v| v.sort_key())
-                .unwrap_or_default();
 
-            match sort_config.direction {
-                SortDirection::Asc => key_a.cmp(&key_b),
-                SortDirection::Desc => key_b.cmp(&key_a),
-            }
-        });
+    // Ordenar
+    if !sort.is_empty() {
+        filtered.sort_by(|a, b| compare_by_sort_levels(a, b, &sort));
     }
-    
+
     drop(all);
-    drop(filters);
+    drop(filter_root);
     drop(sort);
-    
+
+    let selected_ids = selected_note_ids_before_refresh(selection, &notes.borrow());
     *notes.borrow_mut() = filtered.clone();
-    
+
     // Actualizar UI (list_store para lógica)
     list_store.remove_all();
     for note in &filtered {
         let boxed = glib::BoxedAnyObject::new(note.clone());
         list_store.append(&boxed);
     }
-    
+
     // Actualizar WebView
     let columns = if let Some(base) = base.borrow().as_ref() {
         if let Some(view) = base.views.get(base.active_view) {
@@ -2769,9 +6747,17 @@ fn apply_sort_and_refresh(
             ColumnConfig { property: "created".to_string(), title: None, width: Some(150), visible: true },
         ]
     };
-    let html = BaseTableWidget::render_table_html_static(&filtered, &columns, Language::from_env());
+    let group_by = base.borrow().as_ref()
+        .and_then(|b| b.active_view().and_then(|v| v.group_by.clone()));
+    let theme = base.borrow().as_ref().and_then(|b| b.theme).unwrap_or_default();
+    let html = BaseTableWidget::render_table_html_static(
+        &filtered, &columns, Language::from_env(),
+        group_by.as_deref(), &std::collections::HashSet::new(),
+        base_id.borrow().unwrap_or(-1), theme,
+    );
     table_webview.load_html(&html, None);
-    
+    restore_selection_by_identity(selection, &selected_ids, &filtered);
+
     // Actualizar status
     if let Some(label) = status_bar.first_child().and_downcast::<gtk::Label>() {
         let text = if filtered.len() == 1 {
@@ -2911,4 +6897,20 @@ mod tests {
         let widget = BaseTableWidget::new(i18n);
         assert!(widget.widget().is_visible() || !widget.widget().is_visible()); // Just verify it compiles
     }
+
+    #[test]
+    fn test_format_number_integers_drop_decimals() {
+        assert_eq!(BaseTableWidget::format_number(42.0), "42");
+        assert_eq!(BaseTableWidget::format_number(3.5), "3.50");
+    }
+
+    #[test]
+    fn test_fuzzy_score_subsequence_and_boundaries() {
+        // No subsequence -> None.
+        assert!(fuzzy_score("xyz", "Group by").is_none());
+        // Coincidencia en límites de palabra puntúa más que en medio.
+        let boundary = fuzzy_score("gb", "Group By").unwrap();
+        let middle = fuzzy_score("ou", "Group By").unwrap();
+        assert!(boundary > middle);
+    }
 }
@@ -0,0 +1,427 @@
+//! Lectura de metadatos de pistas de audio (ID3v2, comentarios Vorbis de
+//! FLAC, átomos MP4) para el panel "reproduciendo ahora" del reproductor de
+//! música.
+//!
+//! El parseo es de solo lectura y best-effort: una cabecera corrupta o un
+//! formato no soportado simplemente deja los campos en `None`, sin un tipo
+//! de error. No cubre el contenedor Ogg (solo el comentario Vorbis embebido
+//! en FLAC), porque el demultiplexado de páginas Ogg es un parser aparte
+//! que ningún llamador de este chunk necesita todavía.
+
+use std::fs;
+use std::path::Path;
+
+/// Metadatos de una pista. Todos los campos son opcionales porque cualquier
+/// tag puede faltar; las entradas de YouTube no pasan por aquí, su
+/// metadata llega ya resuelta desde la búsqueda.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackMetadata {
+    pub file: Option<String>,
+    pub artist: Option<String>,
+    pub title: Option<String>,
+    pub album: Option<String>,
+    pub date: Option<String>,
+    pub duration_secs: Option<u32>,
+}
+
+/// Lee los metadatos disponibles de un archivo de audio local, eligiendo el
+/// parser según la extensión.
+pub fn read_tags(path: &Path) -> TrackMetadata {
+    let Ok(bytes) = fs::read(path) else {
+        return TrackMetadata::default();
+    };
+
+    let mut meta = match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()) {
+        Some(ext) if ext == "mp3" => parse_id3v2(&bytes),
+        Some(ext) if ext == "flac" => parse_flac_vorbis_comments(&bytes),
+        Some(ext) if ext == "m4a" || ext == "mp4" || ext == "m4b" => parse_mp4_atoms(&bytes),
+        _ => TrackMetadata::default(),
+    };
+    meta.file = path.file_name().map(|n| n.to_string_lossy().into_owned());
+    meta
+}
+
+/// Formatea segundos como `mm:ss` (minutos sin ceros a la izquierda,
+/// segundos siempre a dos dígitos: `9:05`, no `09:05`).
+pub fn format_duration(secs: u32) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Las filas del panel "reproduciendo ahora", en orden, con la clave de
+/// traducción que debe usar el llamador (`base_ui`, vía `I18n::t`) para la
+/// etiqueta de cada una. Las filas sin valor se omiten.
+pub fn now_playing_rows(meta: &TrackMetadata) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if let Some(file) = meta.file.as_ref().filter(|v| !v.is_empty()) {
+        rows.push(("music_meta_file", file.clone()));
+    }
+    if let Some(artist) = meta.artist.as_ref().filter(|v| !v.is_empty()) {
+        rows.push(("music_meta_artist", artist.clone()));
+    }
+    if let Some(title) = meta.title.as_ref().filter(|v| !v.is_empty()) {
+        rows.push(("music_meta_title", title.clone()));
+    }
+    if let Some(album) = meta.album.as_ref().filter(|v| !v.is_empty()) {
+        rows.push(("music_meta_album", album.clone()));
+    }
+    if let Some(date) = meta.date.as_ref().filter(|v| !v.is_empty()) {
+        rows.push(("music_meta_date", date.clone()));
+    }
+    if let Some(duration) = meta.duration_secs {
+        rows.push(("music_meta_duration", format_duration(duration)));
+    }
+    rows
+}
+
+// ---------------------------------------------------------------------
+// ID3v2
+// ---------------------------------------------------------------------
+
+fn parse_id3v2(bytes: &[u8]) -> TrackMetadata {
+    let mut meta = TrackMetadata::default();
+    if bytes.len() < 10 || &bytes[0..3] != b"ID3" {
+        return meta;
+    }
+    let version = bytes[3];
+    let size = synchsafe_to_u32(&bytes[6..10]) as usize;
+    let frames_end = (10 + size).min(bytes.len());
+    let mut i = 10;
+
+    while i + 10 <= frames_end {
+        let frame_id = &bytes[i..i + 4];
+        if frame_id == [0, 0, 0, 0] {
+            break;
+        }
+        let frame_size = if version >= 4 {
+            synchsafe_to_u32(&bytes[i + 4..i + 8]) as usize
+        } else {
+            u32::from_be_bytes([bytes[i + 4], bytes[i + 5], bytes[i + 6], bytes[i + 7]]) as usize
+        };
+        let data_start = i + 10;
+        let data_end = (data_start + frame_size).min(frames_end);
+        if data_start >= data_end {
+            break;
+        }
+        let data = &bytes[data_start..data_end];
+
+        match frame_id {
+            b"TIT2" => meta.title = decode_id3_text(data),
+            b"TPE1" => meta.artist = decode_id3_text(data),
+            b"TALB" => meta.album = decode_id3_text(data),
+            b"TYER" | b"TDRC" => meta.date = decode_id3_text(data),
+            b"TLEN" => {
+                meta.duration_secs = decode_id3_text(data)
+                    .and_then(|ms| ms.parse::<u32>().ok())
+                    .map(|ms| ms / 1000);
+            }
+            _ => {}
+        }
+
+        i = data_end;
+    }
+
+    meta
+}
+
+fn synchsafe_to_u32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, &b| (acc << 7) | (b & 0x7f) as u32)
+}
+
+/// Decodifica el contenido de un frame de texto ID3v2: el primer byte es la
+/// codificación (0 = Latin-1, 1 = UTF-16 con BOM, 2 = UTF-16BE, 3 = UTF-8),
+/// seguido del texto sin el terminador nulo final.
+fn decode_id3_text(data: &[u8]) -> Option<String> {
+    let (encoding, text) = data.split_first()?;
+    let text = trim_trailing_nulls(text);
+    if text.is_empty() {
+        return None;
+    }
+    let decoded = match encoding {
+        1 => decode_utf16_with_bom(text),
+        2 => decode_utf16_be(text),
+        _ => String::from_utf8_lossy(text).into_owned(),
+    };
+    let trimmed = decoded.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn trim_trailing_nulls(data: &[u8]) -> &[u8] {
+    let end = data.iter().rposition(|&b| b != 0).map(|i| i + 1).unwrap_or(0);
+    &data[..end]
+}
+
+fn decode_utf16_with_bom(data: &[u8]) -> String {
+    if data.len() >= 2 && data[0] == 0xFF && data[1] == 0xFE {
+        decode_utf16_le(&data[2..])
+    } else if data.len() >= 2 && data[0] == 0xFE && data[1] == 0xFF {
+        decode_utf16_be(&data[2..])
+    } else {
+        decode_utf16_le(data)
+    }
+}
+
+fn decode_utf16_le(data: &[u8]) -> String {
+    let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn decode_utf16_be(data: &[u8]) -> String {
+    let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+// ---------------------------------------------------------------------
+// Comentarios Vorbis (bloque METADATA_BLOCK_VORBIS_COMMENT de FLAC)
+// ---------------------------------------------------------------------
+
+fn parse_flac_vorbis_comments(bytes: &[u8]) -> TrackMetadata {
+    let mut meta = TrackMetadata::default();
+    if bytes.len() < 4 || &bytes[0..4] != b"fLaC" {
+        return meta;
+    }
+    let mut i = 4;
+    loop {
+        if i + 4 > bytes.len() {
+            break;
+        }
+        let is_last = bytes[i] & 0x80 != 0;
+        let block_type = bytes[i] & 0x7f;
+        let length = u32::from_be_bytes([0, bytes[i + 1], bytes[i + 2], bytes[i + 3]]) as usize;
+        let block_start = i + 4;
+        let block_end = (block_start + length).min(bytes.len());
+        if block_type == 4 {
+            apply_vorbis_comments(&bytes[block_start..block_end], &mut meta);
+        }
+        i = block_end;
+        if is_last || i >= bytes.len() {
+            break;
+        }
+    }
+    meta
+}
+
+fn apply_vorbis_comments(block: &[u8], meta: &mut TrackMetadata) {
+    let Some(comments) = read_vorbis_comment_pairs(block) else {
+        return;
+    };
+    for (key, value) in comments {
+        match key.to_uppercase().as_str() {
+            "ARTIST" => meta.artist = Some(value),
+            "TITLE" => meta.title = Some(value),
+            "ALBUM" => meta.album = Some(value),
+            "DATE" => meta.date = Some(value),
+            _ => {}
+        }
+    }
+}
+
+/// El bloque de comentario Vorbis: longitud(4 LE) + cadena del vendor,
+/// luego la cantidad de comentarios(4 LE), y por cada uno longitud(4 LE) +
+/// `"CLAVE=valor"`.
+fn read_vorbis_comment_pairs(block: &[u8]) -> Option<Vec<(String, String)>> {
+    let mut i = 0;
+    let vendor_len = read_u32_le(block, i)? as usize;
+    i += 4 + vendor_len;
+    let count = read_u32_le(block, i)? as usize;
+    i += 4;
+
+    let mut out = Vec::with_capacity(count);
+    for _ in 0..count {
+        let len = read_u32_le(block, i)? as usize;
+        i += 4;
+        let entry = block.get(i..i + len)?;
+        i += len;
+        let text = String::from_utf8_lossy(entry);
+        if let Some((key, value)) = text.split_once('=') {
+            out.push((key.to_string(), value.to_string()));
+        }
+    }
+    Some(out)
+}
+
+fn read_u32_le(data: &[u8], at: usize) -> Option<u32> {
+    let bytes = data.get(at..at + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+// ---------------------------------------------------------------------
+// MP4 / M4A (átomos ilst + mvhd)
+// ---------------------------------------------------------------------
+
+fn parse_mp4_atoms(bytes: &[u8]) -> TrackMetadata {
+    let mut meta = TrackMetadata::default();
+    walk_mp4_atoms(bytes, &mut meta);
+    meta
+}
+
+/// Recorre una lista de átomos MP4 (`size(4 BE) + fourcc(4) + payload`),
+/// bajando recursivamente por los contenedores relevantes (`moov`, `udta`,
+/// `meta`, `ilst`) hasta encontrar `mvhd` (duración) y las etiquetas de
+/// texto (`©nam`, `©ART`, `©alb`, `©day`).
+fn walk_mp4_atoms(bytes: &[u8], meta: &mut TrackMetadata) {
+    let mut i = 0;
+    while i + 8 <= bytes.len() {
+        let size = u32::from_be_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]) as usize;
+        let fourcc = &bytes[i + 4..i + 8];
+        let (header_len, atom_size) = if size == 1 {
+            if i + 16 > bytes.len() {
+                break;
+            }
+            let size64 = u64::from_be_bytes(bytes[i + 8..i + 16].try_into().unwrap()) as usize;
+            (16, size64)
+        } else if size == 0 {
+            (8, bytes.len() - i)
+        } else {
+            (8, size)
+        };
+        let end = (i + atom_size).min(bytes.len());
+        if end <= i + header_len {
+            break;
+        }
+        let content = &bytes[i + header_len..end];
+
+        match fourcc {
+            b"moov" | b"udta" | b"ilst" => walk_mp4_atoms(content, meta),
+            b"meta" if content.len() > 4 => walk_mp4_atoms(&content[4..], meta),
+            b"mvhd" => apply_mvhd(content, meta),
+            b"\xa9nam" if meta.title.is_none() => meta.title = read_mp4_ilst_text(content),
+            b"\xa9ART" if meta.artist.is_none() => meta.artist = read_mp4_ilst_text(content),
+            b"\xa9alb" if meta.album.is_none() => meta.album = read_mp4_ilst_text(content),
+            b"\xa9day" if meta.date.is_none() => meta.date = read_mp4_ilst_text(content),
+            _ => {}
+        }
+
+        i = end;
+    }
+}
+
+/// El contenido de una etiqueta `©xxx` es un átomo hijo `data`:
+/// `size(4)+"data"(4)+type_flags(4)+locale(4)+texto`.
+fn read_mp4_ilst_text(content: &[u8]) -> Option<String> {
+    if content.len() < 16 || &content[4..8] != b"data" {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&content[16..]).trim().to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+fn apply_mvhd(content: &[u8], meta: &mut TrackMetadata) {
+    if content.is_empty() {
+        return;
+    }
+    let version = content[0];
+    let (timescale, duration) = if version == 1 {
+        if content.len() < 32 {
+            return;
+        }
+        let timescale = u32::from_be_bytes(content[20..24].try_into().unwrap());
+        let duration = u64::from_be_bytes(content[24..32].try_into().unwrap());
+        (timescale, duration)
+    } else {
+        if content.len() < 20 {
+            return;
+        }
+        let timescale = u32::from_be_bytes(content[12..16].try_into().unwrap());
+        let duration = u32::from_be_bytes(content[16..20].try_into().unwrap()) as u64;
+        (timescale, duration)
+    };
+    if timescale > 0 {
+        meta.duration_secs = Some((duration / timescale as u64) as u32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id3_text_frame(id: &[u8; 4], text: &str) -> Vec<u8> {
+        let mut payload = vec![3u8]; // UTF-8
+        payload.extend_from_slice(text.as_bytes());
+        let mut frame = id.to_vec();
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&[0, 0]); // flags
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    fn id3v2_file(frames: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = frames.iter().flatten().copied().collect();
+        let mut out = b"ID3".to_vec();
+        out.extend_from_slice(&[4, 0, 0]); // version 2.4.0, flags
+        let size = body.len() as u32;
+        out.extend_from_slice(&[
+            ((size >> 21) & 0x7f) as u8,
+            ((size >> 14) & 0x7f) as u8,
+            ((size >> 7) & 0x7f) as u8,
+            (size & 0x7f) as u8,
+        ]);
+        out.extend_from_slice(&body);
+        out
+    }
+
+    #[test]
+    fn test_parses_id3v2_title_and_artist() {
+        let bytes = id3v2_file(&[
+            id3_text_frame(b"TIT2", "Bohemian Rhapsody"),
+            id3_text_frame(b"TPE1", "Queen"),
+        ]);
+        let meta = parse_id3v2(&bytes);
+        assert_eq!(meta.title.as_deref(), Some("Bohemian Rhapsody"));
+        assert_eq!(meta.artist.as_deref(), Some("Queen"));
+    }
+
+    #[test]
+    fn test_id3v2_tlen_converts_ms_to_seconds() {
+        let bytes = id3v2_file(&[id3_text_frame(b"TLEN", "213000")]);
+        assert_eq!(parse_id3v2(&bytes).duration_secs, Some(213));
+    }
+
+    #[test]
+    fn test_non_id3_file_returns_empty_metadata() {
+        assert_eq!(parse_id3v2(b"not an id3 file"), TrackMetadata::default());
+    }
+
+    #[test]
+    fn test_format_duration_pads_seconds() {
+        assert_eq!(format_duration(65), "1:05");
+        assert_eq!(format_duration(9), "0:09");
+    }
+
+    #[test]
+    fn test_now_playing_rows_omits_empty_fields() {
+        let meta = TrackMetadata {
+            file: Some("song.mp3".to_string()),
+            artist: None,
+            title: Some("Title".to_string()),
+            album: None,
+            date: None,
+            duration_secs: Some(90),
+        };
+        let rows = now_playing_rows(&meta);
+        let keys: Vec<&str> = rows.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec!["music_meta_file", "music_meta_title", "music_meta_duration"]);
+        assert_eq!(rows.last().unwrap().1, "1:30");
+    }
+
+    #[test]
+    fn test_vorbis_comment_pairs_parse_key_value() {
+        let mut block = Vec::new();
+        block.extend_from_slice(&6u32.to_le_bytes());
+        block.extend_from_slice(b"vendor");
+        block.extend_from_slice(&1u32.to_le_bytes());
+        let comment = b"ARTIST=Queen";
+        block.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+        block.extend_from_slice(comment);
+
+        let pairs = read_vorbis_comment_pairs(&block).unwrap();
+        assert_eq!(pairs, vec![("ARTIST".to_string(), "Queen".to_string())]);
+    }
+}
@@ -0,0 +1,242 @@
+//! Cifrado en reposo para notas sensibles
+//!
+//! El formato en disco es un pequeño encabezado binario seguido del
+//! ciphertext: `magic(4) | m_cost(4 LE) | t_cost(4 LE) | p_cost(4 LE) |
+//! salt(16) | nonce(24) | ciphertext`. La clave de 256 bits se deriva de la
+//! passphrase del vault con Argon2id (los costes quedan guardados junto al
+//! salt para que cambiar los parámetros por defecto en una versión futura no
+//! rompa notas cifradas con los antiguos), y el payload se sella con
+//! XChaCha20-Poly1305, cuyo nonce de 192 bits es lo bastante grande para
+//! generarse al azar en cada guardado sin riesgo realista de reutilización.
+//!
+//! Este módulo no está enganchado a `NoteFile` ni a `NotesConfig`: ningún
+//! punto de guardado lo llama. [`is_encrypted`] detecta el magic de
+//! cabecera y [`encrypt`]/[`decrypt`] son las dos funciones de entrada.
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+const MAGIC: &[u8; 4] = b"NNE1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const HEADER_LEN: usize = 4 + 4 + 4 + 4 + SALT_LEN + NONCE_LEN;
+
+/// Parámetros de coste de Argon2id usados para derivar la clave. Se guardan
+/// junto al salt en cada nota para que cambiar los valores por defecto no
+/// invalide las notas ya cifradas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // Recomendado por el RFC de Argon2 para uso interactivo: 19 MiB,
+        // 2 iteraciones, 1 hilo.
+        KdfParams { m_cost: 19 * 1024, t_cost: 2, p_cost: 1 }
+    }
+}
+
+#[derive(Debug)]
+pub enum CryptoError {
+    NotEncrypted,
+    TruncatedHeader,
+    WrongPassphrase,
+    Kdf(String),
+}
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::NotEncrypted => write!(f, "el archivo no tiene cabecera de cifrado"),
+            CryptoError::TruncatedHeader => write!(f, "cabecera de cifrado incompleta"),
+            CryptoError::WrongPassphrase => write!(f, "passphrase incorrecta o archivo corrupto"),
+            CryptoError::Kdf(msg) => write!(f, "error derivando la clave: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// `true` si `data` empieza con el magic de cabecera de este formato.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() >= MAGIC.len() && &data[..MAGIC.len()] == MAGIC
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: KdfParams) -> Result<[u8; KEY_LEN], CryptoError> {
+    let argon2_params = argon2::Params::new(params.m_cost, params.t_cost, params.p_cost, Some(KEY_LEN))
+        .map_err(|e| CryptoError::Kdf(e.to_string()))?;
+    let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, argon2_params);
+
+    let mut key = [0u8; KEY_LEN];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| CryptoError::Kdf(e.to_string()))?;
+    Ok(key)
+}
+
+/// Cifra `plaintext` con una clave derivada de `passphrase`, generando un
+/// salt y un nonce nuevos. El resultado es el contenido completo a escribir
+/// en disco (cabecera + ciphertext).
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, CryptoError> {
+    encrypt_with_params(plaintext, passphrase, KdfParams::default())
+}
+
+pub fn encrypt_with_params(
+    plaintext: &[u8],
+    passphrase: &str,
+    params: KdfParams,
+) -> Result<Vec<u8>, CryptoError> {
+    let mut rng = rand::thread_rng();
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt, params)?;
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let nonce = XNonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|_| CryptoError::WrongPassphrase)?;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&params.m_cost.to_le_bytes());
+    out.extend_from_slice(&params.t_cost.to_le_bytes());
+    out.extend_from_slice(&params.p_cost.to_le_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Descifra un archivo producido por [`encrypt`]. Una passphrase incorrecta
+/// o un archivo corrupto devuelven [`CryptoError::WrongPassphrase`] en vez
+/// de un plaintext corrupto, para que el llamador muestre un error claro en
+/// lugar de sobrescribir la nota con basura.
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, CryptoError> {
+    if !is_encrypted(data) {
+        return Err(CryptoError::NotEncrypted);
+    }
+    if data.len() < HEADER_LEN {
+        return Err(CryptoError::TruncatedHeader);
+    }
+
+    let mut offset = MAGIC.len();
+    let read_u32 = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().unwrap());
+
+    let m_cost = read_u32(&data[offset..offset + 4]);
+    offset += 4;
+    let t_cost = read_u32(&data[offset..offset + 4]);
+    offset += 4;
+    let p_cost = read_u32(&data[offset..offset + 4]);
+    offset += 4;
+
+    let salt = &data[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce_bytes = &data[offset..offset + NONCE_LEN];
+    offset += NONCE_LEN;
+    let ciphertext = &data[offset..];
+
+    let params = KdfParams { m_cost, t_cost, p_cost };
+    let key = derive_key(passphrase, salt, params)?;
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let nonce = XNonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| CryptoError::WrongPassphrase)
+}
+
+/// Regla para decidir si una nota debe cifrarse: por un tag que lleve, o por
+/// un glob sobre su ruta relativa al directorio de notas. Cada elemento de
+/// `EncryptionConfig::policies` es una de estas reglas.
+#[derive(Debug, Clone)]
+pub enum EncryptionPolicy {
+    Tag(String),
+    PathGlob(String),
+}
+
+impl EncryptionPolicy {
+    /// Evalúa la regla contra una nota dada su ruta relativa y sus tags.
+    pub fn matches(&self, relative_path: &str, tags: &[String]) -> bool {
+        match self {
+            EncryptionPolicy::Tag(tag) => tags.iter().any(|t| t == tag),
+            EncryptionPolicy::PathGlob(glob) => crate::core::matches_glob(glob, relative_path),
+        }
+    }
+}
+
+/// Configuración de cifrado del vault: bajo qué reglas se aplica
+/// ([`EncryptionConfig::should_encrypt`]) y con qué parámetros de Argon2id
+/// se deriva la clave. `NotesConfig` no tiene un campo de este tipo.
+#[derive(Debug, Clone)]
+pub struct EncryptionConfig {
+    pub policies: Vec<EncryptionPolicy>,
+    pub kdf_params: KdfParams,
+}
+
+impl EncryptionConfig {
+    pub fn should_encrypt(&self, relative_path: &str, tags: &[String]) -> bool {
+        self.policies.iter().any(|policy| policy.matches(relative_path, tags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_with_correct_passphrase() {
+        let plaintext = b"# Secreto\nNo compartir.";
+        let encrypted = encrypt(plaintext, "correct horse battery staple").unwrap();
+        assert!(is_encrypted(&encrypted));
+        let decrypted = decrypt(&encrypted, "correct horse battery staple").unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails_cleanly() {
+        let encrypted = encrypt(b"contenido", "passphrase-correcta").unwrap();
+        let result = decrypt(&encrypted, "passphrase-incorrecta");
+        assert!(matches!(result, Err(CryptoError::WrongPassphrase)));
+    }
+
+    #[test]
+    fn test_is_encrypted_rejects_plain_markdown() {
+        assert!(!is_encrypted(b"# Just a regular note\n"));
+    }
+
+    #[test]
+    fn test_decrypt_plaintext_is_not_encrypted_error() {
+        let result = decrypt(b"# Just a regular note\n", "anything");
+        assert!(matches!(result, Err(CryptoError::NotEncrypted)));
+    }
+
+    #[test]
+    fn test_each_encryption_uses_a_fresh_salt_and_nonce() {
+        let a = encrypt(b"same content", "same passphrase").unwrap();
+        let b = encrypt(b"same content", "same passphrase").unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_policy_matches_by_tag() {
+        let policy = EncryptionPolicy::Tag("private".to_string());
+        assert!(policy.matches("notes/diary.md", &["private".to_string()]));
+        assert!(!policy.matches("notes/diary.md", &["public".to_string()]));
+    }
+
+    #[test]
+    fn test_policy_matches_by_path_glob() {
+        let policy = EncryptionPolicy::PathGlob("vault/private/*".to_string());
+        assert!(policy.matches("vault/private/diary.md", &[]));
+        assert!(!policy.matches("vault/public/diary.md", &[]));
+    }
+}
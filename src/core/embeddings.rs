@@ -0,0 +1,220 @@
+//! Subsistema de embeddings para búsqueda semántica de notas
+//!
+//! Permite rankear notas por significado en lugar de coincidencias exactas de
+//! propiedades. El flujo es:
+//! - Al indexar una nota, su contenido se parte en *chunks* solapados
+//!   (~200–500 tokens) y cada chunk se convierte en un vector `Vec<f32>`
+//!   mediante un [`EmbeddingProvider`] conectable.
+//! - Este módulo no persiste los vectores: no escribe en `NotesDatabase`, y
+//!   el llamador conserva los [`NoteEmbedding`] que quiera puntuar.
+//! - En consulta, se embebe el texto (o una nota de referencia) y se puntúa
+//!   cada nota candidata por la máxima similitud coseno entre sus chunks.
+//!
+//! Invariantes: se ignoran vectores de norma cero, se rechazan dimensiones
+//! incompatibles y una nota sin embeddings simplemente no aparece en el
+//! resultado, en lugar de provocar un panic.
+
+/// Proveedor de embeddings conectable (OpenRouter, local, etc.)
+///
+/// Se mantiene mínimo a propósito: una implementación concreta vive en el
+/// subsistema de IA y solo necesita transformar texto en vectores.
+pub trait EmbeddingProvider {
+    /// Dimensión de los vectores que produce este proveedor.
+    fn dimension(&self) -> usize;
+
+    /// Generar el embedding de un fragmento de texto.
+    fn embed(&self, text: &str) -> Result<Vec<f32>, EmbeddingError>;
+}
+
+/// Errores del subsistema de embeddings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbeddingError {
+    /// El proveedor devolvió una dimensión distinta a la esperada.
+    DimensionMismatch { expected: usize, got: usize },
+    /// Fallo del proveedor al generar el vector.
+    Provider(String),
+}
+
+impl std::fmt::Display for EmbeddingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmbeddingError::DimensionMismatch { expected, got } => {
+                write!(f, "dimensión incompatible: esperaba {expected}, recibió {got}")
+            }
+            EmbeddingError::Provider(msg) => write!(f, "error del proveedor: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for EmbeddingError {}
+
+/// Embedding persistido de un chunk de una nota
+#[derive(Debug, Clone)]
+pub struct NoteEmbedding {
+    /// Id de la nota a la que pertenece el chunk.
+    pub note_id: i64,
+    /// Índice del chunk dentro de la nota (0-indexed).
+    pub chunk_index: usize,
+    /// Vector del chunk.
+    pub vector: Vec<f32>,
+}
+
+/// Una nota puntuada por similitud semántica.
+#[derive(Debug, Clone)]
+pub struct ScoredNote {
+    pub note_id: i64,
+    /// Máxima similitud coseno entre los chunks de la nota y la consulta.
+    pub score: f32,
+}
+
+/// Partir un texto en chunks solapados de aproximadamente `chunk_tokens`
+/// tokens con un solapamiento de `overlap` tokens entre chunks consecutivos.
+///
+/// Se usa una tokenización por espacios (suficiente para el solapamiento, el
+/// proveedor hace su propia tokenización real). Un `overlap >= chunk_tokens`
+/// se satura para evitar bucles infinitos.
+pub fn chunk_text(content: &str, chunk_tokens: usize, overlap: usize) -> Vec<String> {
+    let tokens: Vec<&str> = content.split_whitespace().collect();
+    if tokens.is_empty() || chunk_tokens == 0 {
+        return Vec::new();
+    }
+
+    let overlap = overlap.min(chunk_tokens.saturating_sub(1));
+    let step = chunk_tokens - overlap;
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < tokens.len() {
+        let end = (start + chunk_tokens).min(tokens.len());
+        chunks.push(tokens[start..end].join(" "));
+        if end == tokens.len() {
+            break;
+        }
+        start += step;
+    }
+    chunks
+}
+
+/// Similitud coseno entre dos vectores.
+///
+/// Devuelve `None` si las dimensiones no coinciden o si alguno tiene norma
+/// cero (para poder saltarlo en lugar de emitir NaN).
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+
+    Some(dot / (norm_a.sqrt() * norm_b.sqrt()))
+}
+
+/// Rankear notas candidatas por máxima similitud coseno con la consulta.
+///
+/// `embeddings` puede contener varios chunks por nota; se toma el máximo por
+/// nota. Se devuelven las `top_k` notas con puntuación `>= threshold`,
+/// ordenadas de mayor a menor. Las notas sin embeddings simplemente no
+/// aparecen en el resultado.
+pub fn rank_by_similarity(
+    query: &[f32],
+    embeddings: &[NoteEmbedding],
+    top_k: usize,
+    threshold: f32,
+) -> Vec<ScoredNote> {
+    use std::collections::HashMap;
+
+    let mut best: HashMap<i64, f32> = HashMap::new();
+    for emb in embeddings {
+        if let Some(sim) = cosine_similarity(query, &emb.vector) {
+            let entry = best.entry(emb.note_id).or_insert(f32::MIN);
+            if sim > *entry {
+                *entry = sim;
+            }
+        }
+    }
+
+    let mut scored: Vec<ScoredNote> = best
+        .into_iter()
+        .filter(|(_, score)| *score >= threshold)
+        .map(|(note_id, score)| ScoredNote { note_id, score })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    scored.truncate(top_k);
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text_overlap() {
+        let content = "uno dos tres cuatro cinco seis";
+        let chunks = chunk_text(content, 3, 1);
+        assert_eq!(chunks[0], "uno dos tres");
+        assert_eq!(chunks[1], "tres cuatro cinco");
+        assert_eq!(chunks[2], "cinco seis");
+    }
+
+    #[test]
+    fn test_chunk_text_empty() {
+        assert!(chunk_text("", 3, 1).is_empty());
+        assert!(chunk_text("hola", 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_cosine_identical() {
+        let v = vec![1.0, 2.0, 3.0];
+        let sim = cosine_similarity(&v, &v).unwrap();
+        assert!((sim - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_orthogonal() {
+        let sim = cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).unwrap();
+        assert!(sim.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_zero_norm_skipped() {
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), None);
+    }
+
+    #[test]
+    fn test_cosine_dimension_mismatch() {
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn test_rank_top_k_and_threshold() {
+        let query = vec![1.0, 0.0];
+        let embeddings = vec![
+            NoteEmbedding { note_id: 1, chunk_index: 0, vector: vec![1.0, 0.0] },
+            NoteEmbedding { note_id: 1, chunk_index: 1, vector: vec![0.0, 1.0] },
+            NoteEmbedding { note_id: 2, chunk_index: 0, vector: vec![0.9, 0.1] },
+            NoteEmbedding { note_id: 3, chunk_index: 0, vector: vec![0.0, 1.0] },
+        ];
+        let ranked = rank_by_similarity(&query, &embeddings, 10, 0.5);
+        // La nota 3 (ortogonal) queda por debajo del umbral.
+        assert_eq!(ranked.len(), 2);
+        // La nota 1 toma el máximo de sus dos chunks (1.0) y gana.
+        assert_eq!(ranked[0].note_id, 1);
+        assert_eq!(ranked[1].note_id, 2);
+    }
+}
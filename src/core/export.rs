@@ -0,0 +1,183 @@
+//! Exportar el contenido de una vista de Base a texto plano
+//!
+//! `export_view` recibe las notas ya filtradas y ordenadas (las mismas que
+//! `apply_sort_and_refresh` vuelca en la tabla) y las vuelca a CSV o a una
+//! tabla Markdown, respetando solo las columnas visibles y el orden en el
+//! que llegan. No conoce GTK ni diálogos de archivo: esa parte vive en
+//! `base_ui`, que solo necesita escribir el `String` resultante a disco.
+
+use crate::core::{ColumnConfig, NoteWithProperties};
+
+/// Formato de salida soportado por [`export_view`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+}
+
+/// Volcar `notes` a `format`, usando solo las columnas marcadas `visible` en
+/// `columns` y en el orden en que aparecen.
+pub fn export_view(notes: &[NoteWithProperties], columns: &[ColumnConfig], format: ExportFormat) -> String {
+    let visible: Vec<&ColumnConfig> = columns.iter().filter(|c| c.visible).collect();
+    match format {
+        ExportFormat::Csv => export_csv(notes, &visible),
+        ExportFormat::Markdown => export_markdown(notes, &visible),
+    }
+}
+
+/// Valor de una columna para una nota, con los mismos alias especiales
+/// (`title`/`created`/`modified`) que `BaseTableWidget::get_property_value`.
+fn column_value(note: &NoteWithProperties, column: &ColumnConfig) -> String {
+    match column.property.as_str() {
+        "title" => note.metadata.name.clone(),
+        "created" => note.metadata.created_at.format("%Y-%m-%d %H:%M").to_string(),
+        "modified" => note.metadata.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+        other => note
+            .properties
+            .get(other)
+            .map(|v| v.to_display_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Serializar a CSV (RFC 4180): salto de línea `\r\n` y comillas dobladas
+/// para los campos que contengan comas, comillas o saltos de línea.
+fn export_csv(notes: &[NoteWithProperties], columns: &[&ColumnConfig]) -> String {
+    let mut out = String::new();
+    out.push_str(
+        &columns
+            .iter()
+            .map(|c| csv_field(&c.display_title()))
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+    out.push_str("\r\n");
+    for note in notes {
+        out.push_str(
+            &columns
+                .iter()
+                .map(|c| csv_field(&column_value(note, c)))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Serializar a una tabla Markdown con sabor GitHub.
+fn export_markdown(notes: &[NoteWithProperties], columns: &[&ColumnConfig]) -> String {
+    let mut out = String::new();
+    out.push_str("| ");
+    out.push_str(
+        &columns
+            .iter()
+            .map(|c| escape_markdown(&c.display_title()))
+            .collect::<Vec<_>>()
+            .join(" | "),
+    );
+    out.push_str(" |\n|");
+    out.push_str(&" --- |".repeat(columns.len()));
+    out.push('\n');
+    for note in notes {
+        out.push_str("| ");
+        out.push_str(
+            &columns
+                .iter()
+                .map(|c| escape_markdown(&column_value(note, c)))
+                .collect::<Vec<_>>()
+                .join(" | "),
+        );
+        out.push_str(" |\n");
+    }
+    out
+}
+
+/// Escapar `|` (delimitador de celda) y aplanar saltos de línea, que
+/// romperían la fila en una tabla Markdown.
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::NoteMetadata;
+    use crate::core::PropertyValue;
+    use std::collections::HashMap;
+
+    fn note(id: i64, name: &str, tag: &str) -> NoteWithProperties {
+        let mut properties = HashMap::new();
+        properties.insert("tags".to_string(), PropertyValue::Text(tag.to_string()));
+        NoteWithProperties {
+            metadata: NoteMetadata {
+                id,
+                name: name.to_string(),
+                path: String::new(),
+                folder: None,
+                order_index: 0,
+                icon: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            },
+            properties,
+            content: None,
+        }
+    }
+
+    fn columns() -> Vec<ColumnConfig> {
+        vec![
+            ColumnConfig { property: "title".to_string(), title: None, width: None, visible: true },
+            ColumnConfig { property: "tags".to_string(), title: None, width: None, visible: true },
+            ColumnConfig { property: "hidden".to_string(), title: None, width: None, visible: false },
+        ]
+    }
+
+    #[test]
+    fn test_csv_skips_hidden_columns() {
+        let notes = vec![note(1, "A", "x")];
+        let csv = export_view(&notes, &columns(), ExportFormat::Csv);
+        let header = csv.lines().next().unwrap();
+        assert!(!header.to_lowercase().contains("hidden"));
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_with_commas() {
+        let notes = vec![note(1, "A, B", "x")];
+        let csv = export_view(&notes, &columns(), ExportFormat::Csv);
+        assert!(csv.contains("\"A, B\""));
+    }
+
+    #[test]
+    fn test_csv_row_order_follows_input_order() {
+        let notes = vec![note(1, "First", "x"), note(2, "Second", "y")];
+        let csv = export_view(&notes, &columns(), ExportFormat::Csv);
+        let first_pos = csv.find("First").unwrap();
+        let second_pos = csv.find("Second").unwrap();
+        assert!(first_pos < second_pos);
+    }
+
+    #[test]
+    fn test_markdown_has_header_and_separator_row() {
+        let notes = vec![note(1, "A", "x")];
+        let md = export_view(&notes, &columns(), ExportFormat::Markdown);
+        let mut lines = md.lines();
+        assert!(lines.next().unwrap().starts_with("| "));
+        assert!(lines.next().unwrap().contains("---"));
+    }
+
+    #[test]
+    fn test_markdown_escapes_pipe_in_value() {
+        let notes = vec![note(1, "A | B", "x")];
+        let md = export_view(&notes, &columns(), ExportFormat::Markdown);
+        assert!(md.contains("A \\| B"));
+    }
+}
@@ -0,0 +1,317 @@
+//! Árbol de filtros con lógica booleana anidada para las vistas de Base
+//!
+//! El filtrado clásico de una vista es una lista plana de [`Filter`] unida con
+//! AND. Este módulo lo generaliza a un árbol que admite grupos `All`/`Any` y la
+//! negación `Not`, de modo que se pueden expresar consultas como
+//! «(estado = Hecho O estado = Archivado) Y NO prioridad = Baja».
+//!
+//! El árbol se evalúa recursivamente contra las propiedades de una nota
+//! delegando cada hoja en [`Filter::evaluate`]. Para mantener la
+//! compatibilidad con las configuraciones antiguas, una lista plana de filtros
+//! se envuelve en un nodo [`FilterNode::All`] mediante [`FilterNode::from_filters`].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Filter, PropertyValue};
+
+/// Nodo de un árbol de filtros booleano.
+///
+/// Las hojas son filtros individuales; los nodos internos combinan a sus hijos
+/// con AND (`All`), OR (`Any`) o negación (`Not`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FilterNode {
+    /// Un filtro individual sobre una propiedad.
+    Leaf(Filter),
+    /// Todos los hijos deben cumplirse (AND).
+    All(Vec<FilterNode>),
+    /// Al menos un hijo debe cumplirse (OR).
+    Any(Vec<FilterNode>),
+    /// El hijo no debe cumplirse (NOT).
+    Not(Box<FilterNode>),
+}
+
+impl FilterNode {
+    /// Evaluar el árbol contra las propiedades de una nota.
+    ///
+    /// Se respeta la semántica booleana vacía: un `All` vacío es verdadero (no
+    /// restringe, igual que `iter().all(...)`) y un `Any` vacío es falso (no
+    /// hay ninguna alternativa que satisfacer). La raíz por defecto es un `All`
+    /// vacío, de modo que una vista sin filtros muestra todas las notas.
+    pub fn evaluate(&self, properties: &HashMap<String, PropertyValue>) -> bool {
+        match self {
+            FilterNode::Leaf(filter) => filter.evaluate(properties),
+            FilterNode::All(children) => children.iter().all(|c| c.evaluate(properties)),
+            FilterNode::Any(children) => children.iter().any(|c| c.evaluate(properties)),
+            FilterNode::Not(child) => !child.evaluate(properties),
+        }
+    }
+
+    /// Envolver una lista plana de filtros (configuración antigua) en un grupo
+    /// `All`, preservando la semántica AND que tenían.
+    pub fn from_filters(filters: Vec<Filter>) -> FilterNode {
+        FilterNode::All(filters.into_iter().map(FilterNode::Leaf).collect())
+    }
+
+    /// Aplanar el árbol a las hojas que contiene, en orden de aparición.
+    ///
+    /// Sirve para los consumidores que todavía razonan sobre una lista de
+    /// [`Filter`] (por ejemplo los chips planos heredados).
+    pub fn leaves(&self) -> Vec<&Filter> {
+        let mut out = Vec::new();
+        self.collect_leaves(&mut out);
+        out
+    }
+
+    fn collect_leaves<'a>(&'a self, out: &mut Vec<&'a Filter>) {
+        match self {
+            FilterNode::Leaf(filter) => out.push(filter),
+            FilterNode::All(children) | FilterNode::Any(children) => {
+                for child in children {
+                    child.collect_leaves(out);
+                }
+            }
+            FilterNode::Not(child) => child.collect_leaves(out),
+        }
+    }
+
+    /// `true` si el árbol no contiene ninguna hoja (no filtra nada).
+    pub fn is_empty(&self) -> bool {
+        self.leaves().is_empty()
+    }
+
+    /// Referencia al nodo en la ruta dada (índices de hijos desde la raíz), o
+    /// `None` si la ruta no existe.
+    pub fn node_at(&self, path: &[usize]) -> Option<&FilterNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((&i, rest)) => match self {
+                FilterNode::All(children) | FilterNode::Any(children) => {
+                    children.get(i).and_then(|n| n.node_at(rest))
+                }
+                FilterNode::Not(child) if i == 0 => child.node_at(rest),
+                _ => None,
+            },
+        }
+    }
+
+    /// Eliminar el nodo en la ruta dada. El último índice de la ruta es la
+    /// posición del nodo dentro de su grupo padre (`All`/`Any`). Devuelve
+    /// `true` si se eliminó algo; la raíz (`path` vacío) no se puede eliminar.
+    pub fn remove_at(&mut self, path: &[usize]) -> bool {
+        match path.split_last() {
+            None => false,
+            Some((&last, parent)) => match self.node_at_mut(parent) {
+                Some(FilterNode::All(children)) | Some(FilterNode::Any(children))
+                    if last < children.len() =>
+                {
+                    children.remove(last);
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Eliminar recursivamente los subgrupos `All`/`Any` que se han quedado sin
+    /// hojas. Así, borrar la última hoja de un grupo elimina el grupo entero en
+    /// lugar de dejar un `Any` vacío (que no casaría con nada) colgando del
+    /// árbol. La raíz nunca se elimina, aunque quede vacía.
+    pub fn prune_empty(&mut self) {
+        match self {
+            FilterNode::All(children) | FilterNode::Any(children) => {
+                for child in children.iter_mut() {
+                    child.prune_empty();
+                }
+                children.retain(|c| {
+                    !matches!(c, FilterNode::All(_) | FilterNode::Any(_)) || !c.is_empty()
+                });
+            }
+            FilterNode::Not(child) => child.prune_empty(),
+            FilterNode::Leaf(_) => {}
+        }
+    }
+
+    /// Referencia mutable al nodo en la ruta dada (índices de hijos desde la
+    /// raíz), o `None` si la ruta no existe. Un grupo `Not` tiene un único hijo
+    /// en el índice `0`.
+    pub fn node_at_mut(&mut self, path: &[usize]) -> Option<&mut FilterNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((&i, rest)) => match self {
+                FilterNode::All(children) | FilterNode::Any(children) => {
+                    children.get_mut(i).and_then(|n| n.node_at_mut(rest))
+                }
+                FilterNode::Not(child) if i == 0 => child.node_at_mut(rest),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl Default for FilterNode {
+    fn default() -> Self {
+        FilterNode::All(Vec::new())
+    }
+}
+
+/// Comprobar si `value` casa con un patrón glob (`*` como comodín), sin
+/// depender de la crate `regex`.
+///
+/// El patrón se divide en segmentos por `*`. Sin `*` se exige igualdad
+/// exacta. Si no, el primer segmento debe ser prefijo de `value` (salvo que
+/// el patrón empiece por `*`), el último debe ser sufijo (salvo que termine
+/// en `*`), y cada segmento interior no vacío debe aparecer en orden dentro
+/// del resto de `value`, avanzando el cursor tras cada coincidencia para que
+/// los segmentos no se solapen. Los segmentos vacíos (de `**` consecutivos)
+/// se ignoran.
+pub fn matches_glob(pattern: &str, value: &str) -> bool {
+    if !pattern.contains('*') {
+        return pattern == value;
+    }
+
+    let segments: Vec<&str> = pattern.split('*').collect();
+    let first = segments.first().copied().unwrap_or("");
+    let last = segments.last().copied().unwrap_or("");
+
+    if !pattern.starts_with('*') && !value.starts_with(first) {
+        return false;
+    }
+    if !pattern.ends_with('*') && !value.ends_with(last) {
+        return false;
+    }
+
+    let mut cursor = if pattern.starts_with('*') { 0 } else { first.len() };
+    let end = value.len().saturating_sub(if pattern.ends_with('*') { 0 } else { last.len() });
+    if cursor > end {
+        return false;
+    }
+
+    for segment in &segments[1..segments.len().saturating_sub(1)] {
+        if segment.is_empty() {
+            continue;
+        }
+        match value[cursor..end].find(segment) {
+            Some(pos) => cursor += pos + segment.len(),
+            None => return false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Filter, FilterOperator, PropertyValue};
+    use std::collections::HashMap;
+
+    fn leaf(property: &str, value: &str) -> FilterNode {
+        FilterNode::Leaf(Filter {
+            property: property.to_string(),
+            operator: FilterOperator::Equals,
+            value: PropertyValue::Text(value.to_string()),
+        })
+    }
+
+    fn props(pairs: &[(&str, &str)]) -> HashMap<String, PropertyValue> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), PropertyValue::Text(v.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_all_matches_everything() {
+        assert!(FilterNode::All(Vec::new()).evaluate(&props(&[])));
+    }
+
+    #[test]
+    fn test_empty_any_matches_nothing() {
+        assert!(!FilterNode::Any(Vec::new()).evaluate(&props(&[])));
+    }
+
+    #[test]
+    fn test_any_is_or() {
+        let node = FilterNode::Any(vec![leaf("status", "Done"), leaf("status", "Archived")]);
+        assert!(node.evaluate(&props(&[("status", "Archived")])));
+        assert!(!node.evaluate(&props(&[("status", "Open")])));
+    }
+
+    #[test]
+    fn test_nested_all_any_not() {
+        // (status = Done OR status = Archived) AND NOT priority = Low
+        let node = FilterNode::All(vec![
+            FilterNode::Any(vec![leaf("status", "Done"), leaf("status", "Archived")]),
+            FilterNode::Not(Box::new(leaf("priority", "Low"))),
+        ]);
+        assert!(node.evaluate(&props(&[("status", "Done"), ("priority", "High")])));
+        assert!(!node.evaluate(&props(&[("status", "Done"), ("priority", "Low")])));
+        assert!(!node.evaluate(&props(&[("status", "Open"), ("priority", "High")])));
+    }
+
+    #[test]
+    fn test_from_filters_wraps_in_all() {
+        let node = FilterNode::from_filters(vec![Filter {
+            property: "a".to_string(),
+            operator: FilterOperator::Equals,
+            value: PropertyValue::Text("1".to_string()),
+        }]);
+        assert!(matches!(node, FilterNode::All(ref c) if c.len() == 1));
+        assert_eq!(node.leaves().len(), 1);
+    }
+
+    #[test]
+    fn test_node_at_mut_navigates() {
+        let mut node = FilterNode::All(vec![FilterNode::Any(vec![leaf("x", "1")])]);
+        assert!(node.node_at_mut(&[0, 0]).is_some());
+        assert!(node.node_at_mut(&[0, 5]).is_none());
+    }
+
+    #[test]
+    fn test_prune_empty_drops_empty_groups() {
+        let mut node = FilterNode::All(vec![FilterNode::Any(vec![]), leaf("a", "1")]);
+        node.prune_empty();
+        assert_eq!(node, FilterNode::All(vec![leaf("a", "1")]));
+    }
+
+    #[test]
+    fn test_remove_at_drops_child() {
+        let mut node = FilterNode::All(vec![leaf("a", "1"), leaf("b", "2")]);
+        assert!(node.remove_at(&[0]));
+        assert_eq!(node.leaves().len(), 1);
+        assert!(!node.remove_at(&[]));
+        assert!(!node.remove_at(&[9]));
+    }
+
+    #[test]
+    fn test_matches_glob_no_wildcard_requires_exact_match() {
+        assert!(matches_glob("foo", "foo"));
+        assert!(!matches_glob("foo", "foobar"));
+    }
+
+    #[test]
+    fn test_matches_glob_leading_and_trailing_wildcard() {
+        assert!(matches_glob("*2024*", "/projects/notes-2024.md"));
+        assert!(!matches_glob("*2024*", "/projects/notes-2023.md"));
+    }
+
+    #[test]
+    fn test_matches_glob_prefix_and_suffix_anchors() {
+        assert!(matches_glob("/projects/*.md", "/projects/notes.md"));
+        assert!(!matches_glob("/projects/*.md", "/archive/notes.md"));
+        assert!(!matches_glob("/projects/*.md", "/projects/notes.txt"));
+    }
+
+    #[test]
+    fn test_matches_glob_multiple_interior_segments_in_order() {
+        assert!(matches_glob("*/projects/*2024*", "/home/me/projects/report-2024.md"));
+        assert!(!matches_glob("*/projects/*2024*", "/home/me/2024/projects/report.md"));
+    }
+
+    #[test]
+    fn test_matches_glob_consecutive_wildcards_skip_empty_segments() {
+        assert!(matches_glob("a**b", "axxxb"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Registro de iconos de nota: nombre canónico -> shortcode -> glifo
+//!
+//! Sigue el mismo patrón que los shortcodes de emoji de Slack/Discord: cada
+//! icono tiene un nombre canónico y un `:shortcode:` que es lo que se guarda
+//! en la nota (`NoteMetadata::icon`), para que sobreviva al round-trip y se
+//! renderice igual en el sidebar sin importar qué picker se usó para
+//! elegirlo. El picker de la UI vive en `base_ui`; este módulo solo conoce
+//! el registro y la búsqueda por nombre.
+
+use std::collections::HashMap;
+
+/// Un icono disponible en el picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IconDef {
+    pub name: &'static str,
+    pub shortcode: &'static str,
+    pub glyph: &'static str,
+}
+
+const ICONS: &[IconDef] = &[
+    IconDef { name: "bear", shortcode: ":bear:", glyph: "🐻" },
+    IconDef { name: "terminal", shortcode: ":alacritty:", glyph: "🖥️" },
+    IconDef { name: "book", shortcode: ":book:", glyph: "📖" },
+    IconDef { name: "star", shortcode: ":star:", glyph: "⭐" },
+    IconDef { name: "idea", shortcode: ":bulb:", glyph: "💡" },
+    IconDef { name: "calendar", shortcode: ":calendar:", glyph: "📅" },
+    IconDef { name: "rocket", shortcode: ":rocket:", glyph: "🚀" },
+    IconDef { name: "folder", shortcode: ":folder:", glyph: "📁" },
+    IconDef { name: "fire", shortcode: ":fire:", glyph: "🔥" },
+    IconDef { name: "music", shortcode: ":music:", glyph: "🎵" },
+];
+
+/// Todos los iconos del registro, en el orden en que se definieron.
+pub fn all_icons() -> &'static [IconDef] {
+    ICONS
+}
+
+/// Resuelve un shortcode guardado en una nota (p. ej. `:bear:`) a su glifo.
+/// Si el shortcode no está en el registro se devuelve tal cual, para que un
+/// emoji Unicode ya literal (guardado antes de que existiera el registro)
+/// también se muestre.
+pub fn glyph_for_shortcode(shortcode: &str) -> &str {
+    ICONS
+        .iter()
+        .find(|icon| icon.shortcode == shortcode)
+        .map(|icon| icon.glyph)
+        .unwrap_or(shortcode)
+}
+
+/// Filtra el registro por una búsqueda de texto que puede coincidir con el
+/// nombre canónico (`"bear"`) o con su nombre localizado, recibido en
+/// `localized_names` (nombre canónico -> nombre traducido por `I18n`).
+pub fn search_icons(
+    query: &str,
+    localized_names: &HashMap<&'static str, String>,
+) -> Vec<&'static IconDef> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return ICONS.iter().collect();
+    }
+    ICONS
+        .iter()
+        .filter(|icon| {
+            icon.name.to_lowercase().contains(&query)
+                || localized_names
+                    .get(icon.name)
+                    .map(|localized| localized.to_lowercase().contains(&query))
+                    .unwrap_or(false)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_for_shortcode_resolves_known_icon() {
+        assert_eq!(glyph_for_shortcode(":bear:"), "🐻");
+    }
+
+    #[test]
+    fn test_glyph_for_shortcode_passes_through_unknown() {
+        assert_eq!(glyph_for_shortcode("🦊"), "🦊");
+    }
+
+    #[test]
+    fn test_search_matches_canonical_name() {
+        let results = search_icons("bear", &HashMap::new());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].shortcode, ":bear:");
+    }
+
+    #[test]
+    fn test_search_matches_localized_name() {
+        let mut localized = HashMap::new();
+        localized.insert("terminal", "Consola".to_string());
+        let results = search_icons("consol", &localized);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "terminal");
+    }
+
+    #[test]
+    fn test_empty_query_returns_all_icons() {
+        assert_eq!(search_icons("", &HashMap::new()).len(), ICONS.len());
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive() {
+        let results = search_icons("BEAR", &HashMap::new());
+        assert_eq!(results.len(), 1);
+    }
+}
@@ -11,14 +11,28 @@
 //!   Las propiedades agrupadas comparten un group_id y forman un "registro"
 //! - Propiedades ocultas: [campo:::valor] con triple dos puntos no se muestra visualmente
 //!   pero sigue almacenándose en la base de datos
+//!
+//! El parseo no usa regex: recorre el contenido byte a byte (ver
+//! `InlinePropertyParser::scan_bracket_spans` y `scan_field_pairs`), lo que
+//! hace que reparsear una nota grande tras un solo cambio sea barato. Para
+//! editores que solo quieren revalidar la región que se acaba de tocar,
+//! [`PropertyValidator`] ofrece una API incremental al estilo `feed`.
+//!
+//! Con la feature `serde` activada, `InlineProperty` (y `PropertyValue` en
+//! `core::property`, que también lleva el mismo `#[cfg_attr]`) se pueden
+//! serializar directamente; `InlinePropertyParser::to_records` además
+//! colapsa las propiedades agrupadas en un único objeto JSON por grupo.
 
-use regex::Regex;
-use std::sync::LazyLock;
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use super::property::PropertyValue;
 
 /// Una propiedad inline extraída del contenido
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InlineProperty {
     /// Nombre del campo
     pub key: String,
@@ -40,28 +54,100 @@ pub struct InlineProperty {
     /// Si es true, la propiedad usa ::: y no se muestra visualmente
     /// pero sigue almacenándose en la base de datos
     pub hidden: bool,
+    /// Parámetros estilo vCard/iCalendar adjuntos al campo, p. ej.
+    /// `[telefono;tipo=trabajo;pref=1::555-1234]` → `{"tipo": "trabajo", "pref": "1"}`.
+    /// Vacío si el campo no lleva parámetros.
+    pub params: HashMap<String, String>,
 }
 
 impl InlineProperty {
     /// Obtener el texto completo de la propiedad como aparece en el archivo
     pub fn full_text(&self) -> String {
         let separator = if self.hidden { ":::" } else { "::" };
-        format!("[{}{}{}]", self.key, separator, self.raw_value)
+        format!(
+            "[{}{}{}{}]",
+            self.key,
+            Self::params_text(&self.params),
+            separator,
+            self.raw_value
+        )
+    }
+
+    /// Serializar `params` de vuelta a su forma `;clave=valor`, ordenando las
+    /// claves alfabéticamente para que el resultado sea determinista (un
+    /// `HashMap` no conserva el orden de inserción).
+    fn params_text(params: &HashMap<String, String>) -> String {
+        if params.is_empty() {
+            return String::new();
+        }
+        let mut pairs: Vec<(&String, &String)> = params.iter().collect();
+        pairs.sort_by(|a, b| a.0.cmp(b.0));
+        pairs
+            .into_iter()
+            .map(|(k, v)| format!(";{}={}", k, v))
+            .collect()
     }
 }
 
-// Regex para detectar [campo::valor] o [campo1::val1, campo2::val2]
-// Captura todo el contenido entre [ y ]
-// Soporta caracteres Unicode en nombres de campo (ej: año, título)
-static INLINE_PROPERTY_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]").unwrap());
+/// Tipo esperado para una clave declarada en un [`PropertySchema`], usado
+/// por `InlinePropertyParser::parse_with_schema` para forzar una coerción
+/// estricta en vez de adivinar el tipo como hace `parse_value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedType {
+    Number,
+    Date,
+    DateTime,
+    Checkbox,
+    Link,
+    List,
+    Tags,
+    Text,
+}
+
+/// Mapa de clave de campo a tipo esperado. Los campos que no aparecen aquí
+/// se siguen tipando por inferencia, igual que con `parse`.
+pub type PropertySchema = HashMap<String, ExpectedType>;
 
-// Regex para detectar un par campo::valor o campo:::valor dentro del contenido
-// Soporta Unicode y permite espacios alrededor del :: o :::
-// Grupo 1: nombre del campo
-// Grupo 2: separador (:: o :::)
-static PROPERTY_PAIR_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(\p{L}[\p{L}\p{N}_]*)\s*(:::?)\s*").unwrap());
+/// Fallo al coercer el valor de un campo declarado en un `PropertySchema` a
+/// su tipo esperado.
+#[derive(Debug, Clone)]
+pub struct PropertyError {
+    pub key: String,
+    pub line_number: usize,
+    pub char_start: usize,
+    pub raw_value: String,
+    pub expected: ExpectedType,
+    pub message: String,
+}
+
+/// Un par `campo::valor` (o `campo;parámetros::valor`) reconocido por
+/// [`InlinePropertyParser::scan_field_pairs`] dentro del contenido de un
+/// corchete. `start`/`end` son offsets en bytes dentro del `&str` escaneado;
+/// `end` es el primer byte del valor (tras el separador y los espacios que
+/// lo siguen), igual que antes devolvía `cap.get(0).unwrap().end()`.
+struct FieldPairMatch {
+    start: usize,
+    end: usize,
+    key: String,
+    params: String,
+    hidden: bool,
+}
+
+/// Parsear un segmento de parámetros `;clave=valor;clave2=valor2` (tal cual
+/// lo captura `InlinePropertyParser::scan_field_pairs` en `FieldPairMatch::params`)
+/// en un mapa clave→valor.
+fn parse_params(segment: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    for part in segment.split(';') {
+        if part.is_empty() {
+            continue;
+        }
+        if let Some((key, value)) = part.split_once('=') {
+            params.insert(key.to_string(), value.to_string());
+        }
+    }
+    params
+}
 
 /// Parser de propiedades inline
 pub struct InlinePropertyParser;
@@ -79,12 +165,8 @@ impl InlinePropertyParser {
             .chain(content.match_indices('\n').map(|(i, _)| i + 1))
             .collect();
 
-        for cap in INLINE_PROPERTY_REGEX.captures_iter(content) {
-            let full_match = cap.get(0).unwrap();
-            let inner_content = cap.get(1).unwrap().as_str();
-
-            let char_start = full_match.start();
-            let char_end = full_match.end();
+        for (char_start, char_end, inner_start, inner_end) in Self::scan_bracket_spans(content) {
+            let inner_content = &content[inner_start..inner_end];
 
             // Calcular número de línea
             let line_number = line_offsets
@@ -115,6 +197,308 @@ impl InlinePropertyParser {
         properties
     }
 
+    /// Parsear `content` igual que [`parse`](Self::parse), pero coercionando
+    /// estrictamente los campos declarados en `schema` a su tipo esperado en
+    /// vez de adivinarlo. Cuando un campo declarado no coerciona, se excluye
+    /// del resultado y se reporta un [`PropertyError`] en su lugar; los
+    /// campos sin entrada en el esquema se comportan exactamente como en
+    /// `parse`.
+    pub fn parse_with_schema(
+        content: &str,
+        schema: &PropertySchema,
+    ) -> (Vec<InlineProperty>, Vec<PropertyError>) {
+        let mut valid = Vec::new();
+        let mut errors = Vec::new();
+
+        for mut prop in Self::parse(content) {
+            match schema.get(&prop.key) {
+                Some(expected) => match Self::coerce(*expected, &prop.raw_value) {
+                    Ok(value) => {
+                        prop.value = value;
+                        valid.push(prop);
+                    }
+                    Err(message) => errors.push(PropertyError {
+                        key: prop.key,
+                        line_number: prop.line_number,
+                        char_start: prop.char_start,
+                        raw_value: prop.raw_value,
+                        expected: *expected,
+                        message,
+                    }),
+                },
+                None => valid.push(prop),
+            }
+        }
+
+        (valid, errors)
+    }
+
+    /// Coercionar `raw_value` al tipo `expected`, sin caer de vuelta a texto
+    /// si no encaja: a diferencia de `parse_value`, un fallo aquí es un
+    /// error, no una propiedad de tipo `Text`.
+    fn coerce(expected: ExpectedType, raw_value: &str) -> Result<PropertyValue, String> {
+        let trimmed = raw_value.trim();
+        match expected {
+            ExpectedType::Number => trimmed
+                .parse::<f64>()
+                .map(PropertyValue::Number)
+                .map_err(|_| format!("\"{trimmed}\" no es un número")),
+            ExpectedType::Date => {
+                if Self::is_date(trimmed) {
+                    Ok(PropertyValue::Date(trimmed.to_string()))
+                } else {
+                    Err(format!("\"{trimmed}\" no tiene forma de fecha (AAAA-MM-DD)"))
+                }
+            }
+            ExpectedType::DateTime => {
+                if Self::is_datetime(trimmed) {
+                    Ok(PropertyValue::DateTime(trimmed.to_string()))
+                } else {
+                    Err(format!(
+                        "\"{trimmed}\" no tiene forma de fecha y hora (AAAA-MM-DDTHH:MM:SS)"
+                    ))
+                }
+            }
+            ExpectedType::Checkbox => {
+                if trimmed.eq_ignore_ascii_case("true") {
+                    Ok(PropertyValue::Checkbox(true))
+                } else if trimmed.eq_ignore_ascii_case("false") {
+                    Ok(PropertyValue::Checkbox(false))
+                } else {
+                    Err(format!("\"{trimmed}\" no es true/false"))
+                }
+            }
+            ExpectedType::Link => match trimmed.strip_prefix('@') {
+                Some(note_name) if !note_name.is_empty() => {
+                    Ok(PropertyValue::Link(note_name.to_string()))
+                }
+                _ => Err(format!("\"{trimmed}\" no es una relación (@nota)")),
+            },
+            ExpectedType::List => {
+                let items: Vec<String> = trimmed
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if items.is_empty() {
+                    Err(format!("\"{trimmed}\" no es una lista"))
+                } else {
+                    Ok(PropertyValue::List(items))
+                }
+            }
+            ExpectedType::Tags => {
+                let items: Vec<&str> = trimmed.split_whitespace().collect();
+                if !items.is_empty() && items.iter().all(|s| s.starts_with('#')) {
+                    Ok(PropertyValue::Tags(
+                        items
+                            .iter()
+                            .map(|s| s.trim_start_matches('#').to_string())
+                            .collect(),
+                    ))
+                } else {
+                    Err(format!("\"{trimmed}\" no son etiquetas (#tag)"))
+                }
+            }
+            ExpectedType::Text => Ok(PropertyValue::Text(trimmed.to_string())),
+        }
+    }
+
+    /// Agrupar `properties` en objetos JSON: las que comparten `group_id` se
+    /// combinan en un único objeto (una clave por propiedad del grupo, en el
+    /// orden de primera aparición del grupo), y las individuales se emiten
+    /// como un objeto de una sola clave. Los valores conservan su tipo
+    /// (`Number` como número JSON, `Checkbox` como booleano, etc.) en vez de
+    /// aplanarse todos a texto.
+    #[cfg(feature = "serde")]
+    pub fn to_records(properties: &[InlineProperty]) -> Vec<serde_json::Value> {
+        let mut records: Vec<serde_json::Value> = Vec::new();
+        let mut group_index: HashMap<usize, usize> = HashMap::new();
+
+        for prop in properties {
+            let value = Self::value_to_json(&prop.value);
+            match prop.group_id {
+                Some(group_id) => match group_index.get(&group_id) {
+                    Some(&idx) => {
+                        if let serde_json::Value::Object(map) = &mut records[idx] {
+                            map.insert(prop.key.clone(), value);
+                        }
+                    }
+                    None => {
+                        let mut map = serde_json::Map::new();
+                        map.insert(prop.key.clone(), value);
+                        group_index.insert(group_id, records.len());
+                        records.push(serde_json::Value::Object(map));
+                    }
+                },
+                None => {
+                    let mut map = serde_json::Map::new();
+                    map.insert(prop.key.clone(), value);
+                    records.push(serde_json::Value::Object(map));
+                }
+            }
+        }
+
+        records
+    }
+
+    /// Convertir un `PropertyValue` ya tipado a su JSON equivalente, sin
+    /// pasar por su representación de texto (`to_display_string`).
+    #[cfg(feature = "serde")]
+    fn value_to_json(value: &PropertyValue) -> serde_json::Value {
+        let string_array = |items: &[String]| {
+            serde_json::Value::Array(
+                items.iter().map(|s| serde_json::Value::String(s.clone())).collect(),
+            )
+        };
+
+        match value {
+            PropertyValue::Text(s) => serde_json::Value::String(s.clone()),
+            PropertyValue::Number(n) => serde_json::Number::from_f64(*n)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            PropertyValue::Checkbox(b) => serde_json::Value::Bool(*b),
+            PropertyValue::Date(d) => serde_json::Value::String(d.clone()),
+            PropertyValue::DateTime(d) => serde_json::Value::String(d.clone()),
+            PropertyValue::Link(note) => serde_json::Value::String(note.clone()),
+            PropertyValue::Links(items) => string_array(items),
+            PropertyValue::Tags(items) => string_array(items),
+            PropertyValue::List(items) => string_array(items),
+            PropertyValue::DateRange { start, end } => {
+                serde_json::json!({ "start": start, "end": end })
+            }
+            PropertyValue::RecurringDate { date, repeater } => {
+                serde_json::json!({ "date": date, "repeater": repeater })
+            }
+        }
+    }
+
+    /// Recorrer `content` byte a byte en busca de spans `[...]`, equivalente
+    /// a lo que antes hacía `INLINE_PROPERTY_REGEX` (`\[([^\]]+)\]`): un
+    /// corchete de apertura, al menos un byte que no sea `]`, y el primer `]`
+    /// que aparezca (los corchetes no anidan). Devuelve, por cada span,
+    /// `(inicio_corchete, fin_corchete, inicio_interior, fin_interior)` en
+    /// offsets de bytes — siempre caen en límites de carácter válidos porque
+    /// `[` y `]` son ASCII y nunca forman parte de un byte de continuación
+    /// UTF-8.
+    fn scan_bracket_spans(content: &str) -> Vec<(usize, usize, usize, usize)> {
+        let bytes = content.as_bytes();
+        let mut spans = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'[' {
+                let inner_start = i + 1;
+                match bytes[inner_start..].iter().position(|&b| b == b']') {
+                    Some(rel_close) => {
+                        let inner_end = inner_start + rel_close;
+                        let bracket_end = inner_end + 1;
+                        if inner_end > inner_start {
+                            spans.push((i, bracket_end, inner_start, inner_end));
+                        }
+                        i = bracket_end;
+                    }
+                    None => break,
+                }
+            } else {
+                i += 1;
+            }
+        }
+        spans
+    }
+
+    /// Un byte que puede abrir un nombre de campo: letra ASCII, o cualquier
+    /// byte >= 0x80 (parte de un carácter UTF-8 multibyte, como `ñ` o `á`) —
+    /// se acepta sin comprobar la categoría Unicode exacta, igual que antes
+    /// aceptaba cualquier `\p{L}`.
+    fn is_name_start_byte(b: u8) -> bool {
+        b.is_ascii_alphabetic() || b >= 0x80
+    }
+
+    /// Un byte que puede continuar un nombre de campo o de parámetro ya
+    /// empezado: alfanumérico ASCII, `_`, o byte de un carácter UTF-8
+    /// multibyte.
+    fn is_name_continue_byte(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+    }
+
+    /// Recorrer `s` byte a byte buscando pares `campo::valor`,
+    /// `campo;clave=valor::valor` o `campo:::valor`: equivalente a lo que
+    /// antes hacía `PROPERTY_PAIR_REGEX.find_iter`, sin regex. `start`/`end`
+    /// de cada `FieldPairMatch` son offsets dentro de `s`.
+    fn scan_field_pairs(s: &str) -> Vec<FieldPairMatch> {
+        let bytes = s.as_bytes();
+        let mut out = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if !Self::is_name_start_byte(bytes[i]) {
+                i += 1;
+                continue;
+            }
+
+            let key_start = i;
+            i += 1;
+            while i < bytes.len() && Self::is_name_continue_byte(bytes[i]) {
+                i += 1;
+            }
+            let key_end = i;
+
+            // Parámetros vCard-style: (;clave=valor)*
+            let params_start = i;
+            while i < bytes.len() && bytes[i] == b';' {
+                let semi = i;
+                i += 1;
+                let param_key_start = i;
+                while i < bytes.len() && Self::is_name_continue_byte(bytes[i]) {
+                    i += 1;
+                }
+                if i == param_key_start || i >= bytes.len() || bytes[i] != b'=' {
+                    // No era un parámetro válido: no consumir el `;`.
+                    i = semi;
+                    break;
+                }
+                i += 1; // consumir '='
+                while i < bytes.len() && bytes[i] != b';' && bytes[i] != b':' {
+                    i += 1;
+                }
+            }
+            let params_end = i;
+
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+
+            // Separador :: o ::: (como `:::?` en la regex anterior: se
+            // consumen como máximo 3 dos-puntos, cualquier resto se deja
+            // como parte del texto siguiente).
+            let colon_run_start = i;
+            while i < bytes.len() && bytes[i] == b':' {
+                i += 1;
+            }
+            let colon_count = i - colon_run_start;
+            if colon_count < 2 {
+                // No es un separador válido: no es un par campo::valor aquí.
+                i = key_start + 1;
+                continue;
+            }
+            let hidden = colon_count >= 3;
+            i = colon_run_start + if hidden { 3 } else { 2 };
+
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+
+            out.push(FieldPairMatch {
+                start: key_start,
+                end: i,
+                key: s[key_start..key_end].to_string(),
+                params: s[params_start..params_end].to_string(),
+                hidden,
+            });
+        }
+
+        out
+    }
+
     /// Parsear el contenido dentro de los corchetes [...]
     /// Detecta si es una propiedad simple o un grupo de propiedades
     fn parse_bracket_content(
@@ -125,75 +509,64 @@ impl InlinePropertyParser {
     ) -> Vec<InlineProperty> {
         let mut properties = Vec::new();
 
-        // Verificar si contiene al menos un campo::valor
-        if !PROPERTY_PAIR_REGEX.is_match(inner) {
-            return properties; // No es una propiedad inline, ignorar
-        }
-
         // Detectar si es agrupada: buscar ", campo::" patrón
         // Primero, reemplazar \, temporalmente para no confundir con separadores
         let escaped = inner.replace("\\,", "\x00ESCAPED_COMMA\x00");
 
         // Buscar todas las posiciones de campo::
-        let pairs: Vec<_> = PROPERTY_PAIR_REGEX.find_iter(&escaped).collect();
+        let pairs = Self::scan_field_pairs(&escaped);
+
+        if pairs.is_empty() {
+            return properties; // No es una propiedad inline, ignorar
+        }
 
         if pairs.len() == 1 {
             // Propiedad simple [campo::valor] o [campo:::valor]
-            if let Some(cap) = PROPERTY_PAIR_REGEX.captures(&escaped) {
-                let key = cap.get(1).unwrap().as_str().to_string();
-                // Detectar si usa ::: (hidden) o :: (visible)
-                let separator = cap.get(2).unwrap().as_str();
-                let hidden = separator == ":::";
-                let value_start = cap.get(0).unwrap().end();
-                // Mantener el marcador para que parse_value no confunda con lista
-                let value_with_marker = escaped[value_start..].trim().to_string();
-
-                let (value, linked_note) = Self::parse_value(&value_with_marker);
-
-                // raw_value sí tiene la coma restaurada (para display)
-                let raw_value = value_with_marker.replace("\x00ESCAPED_COMMA\x00", ",");
-
-                properties.push(InlineProperty {
-                    key,
-                    value,
-                    raw_value,
-                    line_number,
-                    char_start,
-                    char_end,
-                    linked_note,
-                    group_id: None,
-                    hidden,
-                });
-            }
+            let pair = &pairs[0];
+            let params = parse_params(&pair.params);
+            // Mantener el marcador para que parse_value no confunda con lista
+            let value_with_marker = escaped[pair.end..].trim().to_string();
+
+            let (value, linked_note) = Self::parse_value(&value_with_marker);
+
+            // raw_value sí tiene la coma restaurada (para display)
+            let raw_value = value_with_marker.replace("\x00ESCAPED_COMMA\x00", ",");
+
+            properties.push(InlineProperty {
+                key: pair.key.clone(),
+                value,
+                raw_value,
+                line_number,
+                char_start,
+                char_end,
+                linked_note,
+                group_id: None,
+                hidden: pair.hidden,
+                params,
+            });
         } else {
             // Múltiples propiedades agrupadas [campo1::val1, campo2::val2]
             // Parsear cada par campo::valor
             for i in 0..pairs.len() {
-                let key_match = PROPERTY_PAIR_REGEX
-                    .captures(&escaped[pairs[i].start()..])
-                    .unwrap();
-                let key = key_match.get(1).unwrap().as_str().to_string();
-                // Detectar si usa ::: (hidden) o :: (visible)
-                let separator = key_match.get(2).unwrap().as_str();
-                let hidden = separator == ":::";
-                let value_start_in_inner = pairs[i].start() + key_match.get(0).unwrap().end();
+                let pair = &pairs[i];
+                let params = parse_params(&pair.params);
 
                 // El valor termina donde empieza el siguiente campo:: (menos la coma)
                 // o al final del string
                 let value_end = if i + 1 < pairs.len() {
                     // Buscar la coma antes del siguiente campo
-                    let next_start = pairs[i + 1].start();
+                    let next_start = pairs[i + 1].start;
                     // Encontrar la última coma antes de next_start
-                    escaped[value_start_in_inner..next_start]
+                    escaped[pair.end..next_start]
                         .rfind(',')
-                        .map(|pos| value_start_in_inner + pos)
+                        .map(|pos| pair.end + pos)
                         .unwrap_or(next_start)
                 } else {
                     escaped.len()
                 };
 
                 // Mantener el marcador para parse_value
-                let value_with_marker = escaped[value_start_in_inner..value_end].trim().to_string();
+                let value_with_marker = escaped[pair.end..value_end].trim().to_string();
 
                 let (value, linked_note) = Self::parse_value(&value_with_marker);
 
@@ -201,7 +574,7 @@ impl InlinePropertyParser {
                 let raw_value = value_with_marker.replace("\x00ESCAPED_COMMA\x00", ",");
 
                 properties.push(InlineProperty {
-                    key,
+                    key: pair.key.clone(),
                     value,
                     raw_value,
                     line_number,
@@ -209,7 +582,8 @@ impl InlinePropertyParser {
                     char_end,
                     linked_note,
                     group_id: None, // Se asigna después en parse()
-                    hidden,
+                    hidden: pair.hidden,
+                    params,
                 });
             }
         }
@@ -258,6 +632,13 @@ impl InlinePropertyParser {
             return (PropertyValue::DateTime(trimmed.to_string()), None);
         }
 
+        // 5.5 Temporal enriquecido estilo org-mode: rangos de fecha (--),
+        // repetidores/avisos (+1w, -2d, .+1m) y horas o rangos horarios tras
+        // la fecha. Solo se alcanza si no era ya una fecha/datetime simple.
+        if let Some(value) = Self::parse_temporal(trimmed) {
+            return (value, None);
+        }
+
         // 6. Lista: valores separados por coma
         if trimmed.contains(',') {
             let items: Vec<String> = trimmed
@@ -322,6 +703,103 @@ impl InlinePropertyParser {
         s.contains('T') && s.len() >= 19 && Self::is_date(&s[..10])
     }
 
+    /// Verificar si es una hora HH:MM
+    fn is_time(s: &str) -> bool {
+        s.len() == 5
+            && s.as_bytes()[2] == b':'
+            && s[..2].chars().all(|c| c.is_ascii_digit())
+            && s[3..].chars().all(|c| c.is_ascii_digit())
+    }
+
+    /// Reconocer un valor temporal enriquecido estilo org-mode: un rango de
+    /// fecha/hora separado por `--`, un repetidor/aviso al final
+    /// (`+1w`, `-2d`, `.+1m`), o una fecha con hora o rango horario detrás
+    /// (`2025-11-29 10:00` / `2025-11-29 10:00-12:00`). Devuelve `None` si
+    /// `trimmed` no encaja en ninguna de estas formas, para que `parse_value`
+    /// siga probando los demás tipos.
+    fn parse_temporal(trimmed: &str) -> Option<PropertyValue> {
+        if let Some((body, repeater)) = Self::strip_repeater(trimmed) {
+            let date = Self::parse_timestamp_text(body.trim())?;
+            return Some(PropertyValue::RecurringDate { date, repeater });
+        }
+
+        if let Some((left, right)) = trimmed.split_once("--") {
+            let start = Self::parse_timestamp_text(left.trim())?;
+            let end = Self::parse_timestamp_text(right.trim())?;
+            return Some(PropertyValue::DateRange { start, end });
+        }
+
+        let (date_part, time_part) = trimmed.split_once(' ')?;
+        if !Self::is_date(date_part) {
+            return None;
+        }
+        if let Some((start_time, end_time)) = time_part.split_once('-') {
+            if Self::is_time(start_time) && Self::is_time(end_time) {
+                return Some(PropertyValue::DateRange {
+                    start: format!("{date_part}T{start_time}:00"),
+                    end: format!("{date_part}T{end_time}:00"),
+                });
+            }
+            return None;
+        }
+        if Self::is_time(time_part) {
+            return Some(PropertyValue::DateTime(format!("{date_part}T{time_part}:00")));
+        }
+        None
+    }
+
+    /// Separar un repetidor/aviso (`+1w`, `-2d`, `.+1m`) del final de un
+    /// valor temporal, si lo lleva. Devuelve `(resto, token)`.
+    fn strip_repeater(trimmed: &str) -> Option<(&str, String)> {
+        let (body, token) = trimmed.rsplit_once(' ')?;
+        if Self::is_repeater(token) {
+            Some((body, token.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Verificar si `token` es un repetidor/aviso org-mode: `+1w` (cada
+    /// semana), `.+1m` (reinicia desde hoy cada mes) o `-2d` (periodo de
+    /// aviso), equivalente a `^(?:\.\+|\+|-)\d+[hdwmy]$`.
+    fn is_repeater(token: &str) -> bool {
+        let bytes = token.as_bytes();
+        let rest = if let Some(r) = bytes.strip_prefix(b".+") {
+            r
+        } else if let Some(r) = bytes.strip_prefix(b"+") {
+            r
+        } else if let Some(r) = bytes.strip_prefix(b"-") {
+            r
+        } else {
+            return false;
+        };
+
+        if rest.len() < 2 {
+            return false;
+        }
+        let (digits, unit) = rest.split_at(rest.len() - 1);
+        !digits.is_empty()
+            && digits.iter().all(|b| b.is_ascii_digit())
+            && matches!(unit[0], b'h' | b'd' | b'w' | b'm' | b'y')
+    }
+
+    /// Normalizar un único timestamp (`YYYY-MM-DD`, `YYYY-MM-DDTHH:MM:SS` o
+    /// `YYYY-MM-DD HH:MM`) a su forma `Date`/`DateTime`, para usar como lado de
+    /// un `DateRange` o como `date` de un `RecurringDate`.
+    fn parse_timestamp_text(s: &str) -> Option<String> {
+        if Self::is_datetime(s) {
+            return Some(s.to_string());
+        }
+        if Self::is_date(s) {
+            return Some(s.to_string());
+        }
+        let (date_part, time_part) = s.split_once(' ')?;
+        if Self::is_date(date_part) && Self::is_time(time_part) {
+            return Some(format!("{date_part}T{time_part}:00"));
+        }
+        None
+    }
+
     /// Reemplazar el valor de una propiedad en el contenido
     pub fn replace_property(content: &str, prop: &InlineProperty, new_value: &str) -> String {
         let mut result = String::with_capacity(content.len());
@@ -350,6 +828,54 @@ impl InlinePropertyParser {
     }
 }
 
+/// Validador incremental de propiedades inline, para que un editor pueda
+/// revalidar solo la región que acaba de cambiar en vez de volver a parsear
+/// la nota entera. Se le va alimentando texto con [`feed`](Self::feed) y
+/// decide, corchete a corchete, si lo que tiene delante es una propiedad
+/// completa y válida.
+#[derive(Default)]
+pub struct PropertyValidator {
+    buffer: String,
+}
+
+impl PropertyValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Añadir `input` al buffer interno e intentar reconocer un `[...]`
+    /// completo al principio de lo acumulado hasta ahora.
+    ///
+    /// - `Some(n)` con `n > 0`: los primeros `n` bytes forman una propiedad
+    ///   válida; ese tramo se descarta del buffer.
+    /// - `Some(0)`: el primer `[...]` que se cierra no es una propiedad
+    ///   reconocible y debe tratarse como texto literal; también se descarta
+    ///   del buffer para poder seguir avanzando.
+    /// - `None`: el buffer no empieza por `[`, o el corchete todavía no se
+    ///   ha cerrado — hace falta más texto.
+    pub fn feed(&mut self, input: &str) -> Option<usize> {
+        self.buffer.push_str(input);
+
+        if !self.buffer.starts_with('[') {
+            return None;
+        }
+
+        let bytes = self.buffer.as_bytes();
+        let inner_start = 1;
+        let rel_close = bytes[inner_start..].iter().position(|&b| b == b']')?;
+        let inner_end = inner_start + rel_close;
+        let bracket_end = inner_end + 1;
+
+        let is_valid = inner_end > inner_start
+            && !InlinePropertyParser::scan_field_pairs(&self.buffer[inner_start..inner_end])
+                .is_empty();
+
+        let consumed = if is_valid { bracket_end } else { 0 };
+        self.buffer.drain(..bracket_end);
+        Some(consumed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,6 +919,64 @@ mod tests {
         assert!(matches!(&props[0].value, PropertyValue::Date(d) if d == "2025-11-29"));
     }
 
+    #[test]
+    fn test_parse_time_range_on_same_day() {
+        let content = "[cita::2025-11-29 10:00-12:00]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert!(matches!(
+            &props[0].value,
+            PropertyValue::DateRange { start, end }
+                if start == "2025-11-29T10:00:00" && end == "2025-11-29T12:00:00"
+        ));
+    }
+
+    #[test]
+    fn test_parse_date_range() {
+        let content = "[sprint::2025-11-29--2025-12-05]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert!(matches!(
+            &props[0].value,
+            PropertyValue::DateRange { start, end }
+                if start == "2025-11-29" && end == "2025-12-05"
+        ));
+    }
+
+    #[test]
+    fn test_parse_recurring_date() {
+        let content = "[revisar::2025-11-29 +1w]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert!(matches!(
+            &props[0].value,
+            PropertyValue::RecurringDate { date, repeater }
+                if date == "2025-11-29" && repeater == "+1w"
+        ));
+    }
+
+    #[test]
+    fn test_parse_recurring_date_with_restart_repeater() {
+        let content = "[revisar::2025-11-29 .+1m]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert!(matches!(
+            &props[0].value,
+            PropertyValue::RecurringDate { repeater, .. } if repeater == ".+1m"
+        ));
+    }
+
+    #[test]
+    fn test_parse_date_with_single_time() {
+        let content = "[reunion::2025-11-29 10:00]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert!(matches!(
+            &props[0].value,
+            PropertyValue::DateTime(d) if d == "2025-11-29T10:00:00"
+        ));
+    }
+
     #[test]
     fn test_parse_link() {
         let content = "Autor: [autor::@Cervantes]";
@@ -512,6 +1096,157 @@ Este es un gran libro.
         assert_eq!(props[1].group_id, None);
     }
 
+    #[test]
+    fn test_parse_property_with_params() {
+        let content = "Contacto: [telefono;tipo=trabajo;pref=1::555-1234]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert_eq!(props.len(), 1);
+        assert_eq!(props[0].key, "telefono");
+        assert_eq!(props[0].params.get("tipo"), Some(&"trabajo".to_string()));
+        assert_eq!(props[0].params.get("pref"), Some(&"1".to_string()));
+        assert!(matches!(&props[0].value, PropertyValue::Text(s) if s == "555-1234"));
+    }
+
+    #[test]
+    fn test_property_without_params_has_empty_map() {
+        let content = "[titulo::Mi Libro]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert!(props[0].params.is_empty());
+    }
+
+    #[test]
+    fn test_full_text_round_trips_params() {
+        let content = "[fecha;tz=Europe/Madrid::2025-11-29]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert_eq!(props[0].full_text(), content);
+    }
+
+    #[test]
+    fn test_grouped_property_with_params() {
+        let content = "[telefono;tipo=casa::555-0000, movil;tipo=personal::555-9999]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert_eq!(props.len(), 2);
+        assert_eq!(props[0].params.get("tipo"), Some(&"casa".to_string()));
+        assert_eq!(props[1].params.get("tipo"), Some(&"personal".to_string()));
+    }
+
+    #[test]
+    fn test_field_name_can_start_with_unicode_letter() {
+        let content = "[ñandú::ave]";
+        let props = InlinePropertyParser::parse(content);
+
+        assert_eq!(props.len(), 1);
+        assert_eq!(props[0].key, "ñandú");
+    }
+
+    #[test]
+    fn test_validator_recognizes_complete_property() {
+        let mut validator = PropertyValidator::new();
+        let input = "[titulo::Mi Libro] resto";
+        let consumed = validator.feed(input).unwrap();
+
+        assert_eq!(consumed, "[titulo::Mi Libro]".len());
+    }
+
+    #[test]
+    fn test_validator_rejects_invalid_bracket_region() {
+        let mut validator = PropertyValidator::new();
+        let consumed = validator.feed("[esto no es una propiedad] resto").unwrap();
+
+        assert_eq!(consumed, 0);
+    }
+
+    #[test]
+    fn test_validator_waits_for_more_input_before_deciding() {
+        let mut validator = PropertyValidator::new();
+
+        assert_eq!(validator.feed("[titulo::Mi "), None);
+        assert_eq!(validator.feed("Libro]"), Some("[titulo::Mi Libro]".len()));
+    }
+
+    #[test]
+    fn test_schema_coerces_declared_field() {
+        let content = "[precio::99.99]";
+        let mut schema = PropertySchema::new();
+        schema.insert("precio".to_string(), ExpectedType::Number);
+
+        let (props, errors) = InlinePropertyParser::parse_with_schema(content, &schema);
+
+        assert!(errors.is_empty());
+        assert_eq!(props.len(), 1);
+        assert!(matches!(&props[0].value, PropertyValue::Number(n) if (*n - 99.99).abs() < 0.001));
+    }
+
+    #[test]
+    fn test_schema_rejects_malformed_number() {
+        let content = "[precio::12.9.9]";
+        let mut schema = PropertySchema::new();
+        schema.insert("precio".to_string(), ExpectedType::Number);
+
+        let (props, errors) = InlinePropertyParser::parse_with_schema(content, &schema);
+
+        assert!(props.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].key, "precio");
+        assert_eq!(errors[0].expected, ExpectedType::Number);
+        assert_eq!(errors[0].raw_value, "12.9.9");
+    }
+
+    #[test]
+    fn test_schema_leaves_undeclared_fields_untyped() {
+        let content = "[precio::99.99][titulo::Mi Libro]";
+        let mut schema = PropertySchema::new();
+        schema.insert("precio".to_string(), ExpectedType::Number);
+
+        let (props, errors) = InlinePropertyParser::parse_with_schema(content, &schema);
+
+        assert!(errors.is_empty());
+        assert_eq!(props.len(), 2);
+        assert!(matches!(&props[1].value, PropertyValue::Text(s) if s == "Mi Libro"));
+    }
+
+    #[test]
+    fn test_schema_rejects_date_with_wrong_shape() {
+        let content = "[fecha::no es una fecha]";
+        let mut schema = PropertySchema::new();
+        schema.insert("fecha".to_string(), ExpectedType::Date);
+
+        let (props, errors) = InlinePropertyParser::parse_with_schema(content, &schema);
+
+        assert!(props.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].expected, ExpectedType::Date);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_to_records_collapses_group_into_one_object() {
+        let content = "[autor::Cervantes, libro::Quijote, año::1605]";
+        let props = InlinePropertyParser::parse(content);
+        let records = InlinePropertyParser::to_records(&props);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["autor"], serde_json::json!("Cervantes"));
+        assert_eq!(records[0]["libro"], serde_json::json!("Quijote"));
+        assert_eq!(records[0]["año"], serde_json::json!(1605.0));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_to_records_emits_individual_properties_separately() {
+        let content = "[titulo::Mi Libro][precio::9.99]";
+        let props = InlinePropertyParser::parse(content);
+        let records = InlinePropertyParser::to_records(&props);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["titulo"], serde_json::json!("Mi Libro"));
+        assert_eq!(records[1]["precio"], serde_json::json!(9.99));
+    }
+
     #[test]
     fn test_multiple_groups() {
         // Múltiples grupos en el mismo contenido
@@ -0,0 +1,266 @@
+//! Extensiones de CommonMark: tachado, listas de tareas, notas al pie y tablas
+//!
+//! `core::markdown::MarkdownParser` no llama a este módulo: implementa el
+//! reconocimiento de cada construcción como funciones puras sobre texto.
+//! Los estilos de los spans (`ExtendedStyle`: `Strikethrough`,
+//! `TaskListItem { checked }`, `FootnoteRef`, `TableCell { alignment }`) son
+//! un enum propio de este módulo, no variantes de `StyleType`.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Estilo reconocido por este módulo sobre un rango de bytes de una línea o
+/// del buffer completo. Se corresponde 1:1 con las variantes que pide la
+/// petición para `StyleType`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtendedStyle {
+    Strikethrough,
+    TaskListItem { checked: bool },
+    FootnoteRef { id: String },
+    TableCell { alignment: ColumnAlignment },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    Left,
+    Center,
+    Right,
+    None,
+}
+
+/// Un span con estilo dentro de una línea, en offsets de byte relativos al
+/// inicio de esa línea.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledSpan {
+    pub range: Range<usize>,
+    pub style: ExtendedStyle,
+}
+
+/// Encuentra todos los tramos `~~tachado~~` de una línea. No admite anidar
+/// otro `~~` dentro (como CommonMark, toma el primer cierre que encuentra).
+pub fn find_strikethrough(line: &str) -> Vec<StyledSpan> {
+    let mut spans = Vec::new();
+    let mut search_from = 0;
+    while let Some(start) = line[search_from..].find("~~") {
+        let start = search_from + start;
+        let after_open = start + 2;
+        if let Some(len) = line[after_open..].find("~~") {
+            let end = after_open + len + 2;
+            if len > 0 {
+                spans.push(StyledSpan { range: start..end, style: ExtendedStyle::Strikethrough });
+            }
+            search_from = end;
+        } else {
+            break;
+        }
+    }
+    spans
+}
+
+/// Reconoce una línea de lista de tareas GitHub (`- [ ] texto` / `- [x]
+/// texto`, con indentación y `*`/`-`/`+` como viñeta). Devuelve si está
+/// marcada y el texto restante tras la casilla.
+pub fn parse_task_list_item(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    let after_bullet = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))?;
+
+    let after_checkbox = after_bullet
+        .strip_prefix("[ ] ")
+        .map(|rest| (false, rest))
+        .or_else(|| after_bullet.strip_prefix("[x] ").map(|rest| (true, rest)))
+        .or_else(|| after_bullet.strip_prefix("[X] ").map(|rest| (true, rest)))?;
+
+    Some(after_checkbox)
+}
+
+/// Invierte el estado marcado/sin marcar de una línea de lista de tareas,
+/// dejando el resto de la línea intacto. Devuelve `None` si la línea no es
+/// una lista de tareas. Pensado para que `EditorAction::ToggleTask` llame a
+/// esto y reemplace la línea en el buffer.
+pub fn toggle_task_checkbox(line: &str) -> Option<String> {
+    parse_task_list_item(line)?;
+
+    // La posición de la casilla es fija una vez que sabemos que es una lista
+    // de tareas: indentación + viñeta ("- "/"* "/"+ ", 2 bytes). Buscar
+    // "[ ]"/"[x]" en el resto de la línea corrompería el texto si ese mismo
+    // patrón aparece dentro del contenido de la tarea.
+    let leading_ws = line.len() - line.trim_start().len();
+    let checkbox_start = leading_ws + 2;
+    let checkbox_end = checkbox_start + 3;
+
+    let mut out = line.to_string();
+    match &line[checkbox_start..checkbox_end] {
+        "[ ]" => out.replace_range(checkbox_start..checkbox_end, "[x]"),
+        "[x]" | "[X]" => out.replace_range(checkbox_start..checkbox_end, "[ ]"),
+        _ => return None,
+    }
+    Some(out)
+}
+
+/// Encuentra todas las referencias a notas al pie (`[^id]`) de una línea,
+/// distinguiéndolas de un enlace `[texto](url)` por el `^` inicial.
+pub fn find_footnote_refs(line: &str) -> Vec<StyledSpan> {
+    let mut spans = Vec::new();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'[' && i + 1 < bytes.len() && bytes[i + 1] == b'^' {
+            if let Some(close) = line[i..].find(']') {
+                let end = i + close + 1;
+                let id = &line[i + 2..i + close];
+                if !id.is_empty() {
+                    spans.push(StyledSpan {
+                        range: i..end,
+                        style: ExtendedStyle::FootnoteRef { id: id.to_string() },
+                    });
+                }
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Resuelve las definiciones de notas al pie (`[^id]: texto de la nota`) de
+/// todo el buffer, devolviendo un mapa id -> texto. Las definiciones pueden
+/// aparecer en cualquier línea, normalmente al final del documento.
+pub fn resolve_footnote_definitions(buffer: &str) -> HashMap<String, String> {
+    let mut definitions = HashMap::new();
+    for line in buffer.lines() {
+        let trimmed = line.trim_start();
+        if let Some(rest) = trimmed.strip_prefix("[^") {
+            if let Some(close) = rest.find(']') {
+                let id = &rest[..close];
+                if let Some(text) = rest[close + 1..].strip_prefix(':') {
+                    definitions.insert(id.trim().to_string(), text.trim().to_string());
+                }
+            }
+        }
+    }
+    definitions
+}
+
+/// Una tabla con tubos ya parseada: cabeceras, alineación por columna (de la
+/// fila separadora `---|:---:|---:`) y filas de datos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub alignments: Vec<ColumnAlignment>,
+    pub rows: Vec<Vec<String>>,
+}
+
+fn split_table_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim().trim_start_matches('|').trim_end_matches('|');
+    trimmed.split('|').map(|cell| cell.trim().to_string()).collect()
+}
+
+fn parse_alignment_cell(cell: &str) -> Option<ColumnAlignment> {
+    let cell = cell.trim();
+    if cell.is_empty() || !cell.chars().all(|c| c == '-' || c == ':') {
+        return None;
+    }
+    let left = cell.starts_with(':');
+    let right = cell.ends_with(':');
+    Some(match (left, right) {
+        (true, true) => ColumnAlignment::Center,
+        (true, false) => ColumnAlignment::Left,
+        (false, true) => ColumnAlignment::Right,
+        (false, false) => ColumnAlignment::None,
+    })
+}
+
+/// Parsea una tabla con tubos a partir de sus líneas ya partidas (cabecera,
+/// fila separadora de alineación, y filas de datos). Devuelve `None` si la
+/// segunda línea no es una fila separadora válida.
+pub fn parse_table(lines: &[&str]) -> Option<Table> {
+    if lines.len() < 2 {
+        return None;
+    }
+    let headers = split_table_row(lines[0]);
+    let separator_cells = split_table_row(lines[1]);
+    if separator_cells.len() != headers.len() {
+        return None;
+    }
+    let alignments: Vec<ColumnAlignment> =
+        separator_cells.iter().map(|cell| parse_alignment_cell(cell)).collect::<Option<Vec<_>>>()?;
+
+    let rows = lines[2..].iter().map(|line| split_table_row(line)).collect();
+
+    Some(Table { headers, alignments, rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_strikethrough_single_span() {
+        let spans = find_strikethrough("esto ~~no~~ importa");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].style, ExtendedStyle::Strikethrough);
+        assert_eq!(&"esto ~~no~~ importa"[spans[0].range.clone()], "~~no~~");
+    }
+
+    #[test]
+    fn test_parse_task_list_item_checked_and_unchecked() {
+        assert_eq!(parse_task_list_item("- [ ] pendiente"), Some((false, "pendiente")));
+        assert_eq!(parse_task_list_item("- [x] hecho"), Some((true, "hecho")));
+        assert_eq!(parse_task_list_item("texto normal"), None);
+    }
+
+    #[test]
+    fn test_toggle_task_checkbox_flips_state() {
+        assert_eq!(toggle_task_checkbox("- [ ] pendiente").as_deref(), Some("- [x] pendiente"));
+        assert_eq!(toggle_task_checkbox("- [x] hecho").as_deref(), Some("- [ ] hecho"));
+        assert_eq!(toggle_task_checkbox("no es tarea"), None);
+    }
+
+    #[test]
+    fn test_toggle_task_checkbox_ignores_brackets_in_task_text() {
+        assert_eq!(
+            toggle_task_checkbox("- [x] rellenar el campo [ ]").as_deref(),
+            Some("- [ ] rellenar el campo [ ]")
+        );
+        assert_eq!(
+            toggle_task_checkbox("- [ ] rellenar el campo [x]").as_deref(),
+            Some("- [x] rellenar el campo [x]")
+        );
+    }
+
+    #[test]
+    fn test_find_footnote_refs_ignores_normal_links() {
+        let spans = find_footnote_refs("ver [^1] y también [texto](url)");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].style, ExtendedStyle::FootnoteRef { id: "1".to_string() });
+    }
+
+    #[test]
+    fn test_resolve_footnote_definitions() {
+        let buffer = "Texto con [^nota].\n\n[^nota]: Esta es la explicación.\n";
+        let defs = resolve_footnote_definitions(buffer);
+        assert_eq!(defs.get("nota").map(String::as_str), Some("Esta es la explicación."));
+    }
+
+    #[test]
+    fn test_parse_table_with_alignment() {
+        let lines = vec!["Nombre | Edad | Ciudad", ":--- | :---: | ---:", "Ana | 30 | Madrid"];
+        let table = parse_table(&lines).unwrap();
+        assert_eq!(table.headers, vec!["Nombre", "Edad", "Ciudad"]);
+        assert_eq!(
+            table.alignments,
+            vec![ColumnAlignment::Left, ColumnAlignment::Center, ColumnAlignment::Right]
+        );
+        assert_eq!(table.rows, vec![vec!["Ana".to_string(), "30".to_string(), "Madrid".to_string()]]);
+    }
+
+    #[test]
+    fn test_parse_table_rejects_invalid_separator() {
+        let lines = vec!["Nombre | Edad", "no es separador"];
+        assert!(parse_table(&lines).is_none());
+    }
+}
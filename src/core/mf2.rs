@@ -0,0 +1,174 @@
+//! Exportar notas como elementos microformats2 (mf2) en JSON
+//!
+//! Sigue la convención mf2-json (cada propiedad es un array, aunque tenga un
+//! solo valor) para que el resultado interoperе con herramientas IndieWeb y
+//! Micropub. No depende de `serde_json`: el documento se construye a mano,
+//! igual que el HTML de la tabla en `base_ui`.
+
+use crate::core::{NoteWithProperties, PropertyValue};
+
+/// Serializar una nota como un único elemento `h-entry`.
+pub fn export_h_entry(note: &NoteWithProperties, visibility_property: &str) -> String {
+    h_entry_json(note, visibility_property)
+}
+
+/// Serializar varias notas como un `h-feed` con los `h-entry` anidados en
+/// `children`, en el mismo orden en que llegan.
+pub fn export_h_feed(notes: &[NoteWithProperties], visibility_property: &str) -> String {
+    let children: Vec<String> = notes
+        .iter()
+        .map(|note| h_entry_json(note, visibility_property))
+        .collect();
+    format!(r#"{{"type":["h-feed"],"children":[{}]}}"#, children.join(","))
+}
+
+fn h_entry_json(note: &NoteWithProperties, visibility_property: &str) -> String {
+    let mut fields = Vec::new();
+    fields.push(format!(r#""name":{}"#, json_string_array(&[note.metadata.name.clone()])));
+
+    if let Some(summary) = note.properties.get("summary") {
+        let text = summary.to_display_string();
+        if !text.is_empty() {
+            fields.push(format!(r#""summary":{}"#, json_string_array(&[text])));
+        }
+    }
+    if let Some(content) = note.content.as_ref().filter(|c| !c.is_empty()) {
+        fields.push(format!(r#""content":{}"#, json_string_array(&[content.clone()])));
+    }
+
+    fields.push(format!(r#""category":{}"#, json_string_array(&tags_of(note))));
+    fields.push(format!(
+        r#""published":{}"#,
+        json_string_array(&[note.metadata.created_at.to_rfc3339()])
+    ));
+    fields.push(format!(
+        r#""updated":{}"#,
+        json_string_array(&[note.metadata.updated_at.to_rfc3339()])
+    ));
+    fields.push(format!(
+        r#""visibility":{}"#,
+        json_string_array(&[visibility_of(note, visibility_property)])
+    ));
+
+    format!(r#"{{"type":["h-entry"],"properties":{{{}}}}}"#, fields.join(","))
+}
+
+/// Etiquetas de la nota para la propiedad mf2 `category`, que el formato
+/// exige como array aunque la nota solo tenga una.
+fn tags_of(note: &NoteWithProperties) -> Vec<String> {
+    match note.properties.get("tags") {
+        Some(PropertyValue::Tags(items)) => items.clone(),
+        Some(other) => {
+            let text = other.to_display_string();
+            text.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        }
+        None => Vec::new(),
+    }
+}
+
+/// `public`/`private` derivado de la columna de visibilidad configurable.
+/// Cualquier valor que no sea explícitamente "private" se trata como público,
+/// para que las notas sin esa propiedad se exporten visibles por defecto.
+fn visibility_of(note: &NoteWithProperties, visibility_property: &str) -> String {
+    match note.properties.get(visibility_property) {
+        Some(value) if value.to_display_string().eq_ignore_ascii_case("private") => {
+            "private".to_string()
+        }
+        _ => "public".to_string(),
+    }
+}
+
+fn json_string_array(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|v| format!("\"{}\"", escape_json(v))).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Escapar un texto para incrustarlo como cadena JSON.
+fn escape_json(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::NoteMetadata;
+    use std::collections::HashMap;
+
+    fn note(name: &str, tags: Vec<&str>, visibility: Option<&str>) -> NoteWithProperties {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "tags".to_string(),
+            PropertyValue::Tags(tags.into_iter().map(String::from).collect()),
+        );
+        if let Some(v) = visibility {
+            properties.insert("visibility".to_string(), PropertyValue::Text(v.to_string()));
+        }
+        NoteWithProperties {
+            metadata: NoteMetadata {
+                id: 1,
+                name: name.to_string(),
+                path: String::new(),
+                folder: None,
+                order_index: 0,
+                icon: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            },
+            properties,
+            content: Some("Hello \"world\"".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_h_entry_wraps_name_in_array() {
+        let json = export_h_entry(&note("Title", vec!["a"], None), "visibility");
+        assert!(json.contains(r#""name":["Title"]"#));
+    }
+
+    #[test]
+    fn test_h_entry_defaults_to_public() {
+        let json = export_h_entry(&note("Title", vec!["a"], None), "visibility");
+        assert!(json.contains(r#""visibility":["public"]"#));
+    }
+
+    #[test]
+    fn test_h_entry_respects_private_visibility() {
+        let json = export_h_entry(&note("Title", vec!["a"], Some("Private")), "visibility");
+        assert!(json.contains(r#""visibility":["private"]"#));
+    }
+
+    #[test]
+    fn test_h_entry_category_lists_all_tags() {
+        let json = export_h_entry(&note("Title", vec!["b", "a"], None), "visibility");
+        assert!(json.contains(r#""category":["b","a"]"#));
+    }
+
+    #[test]
+    fn test_h_entry_escapes_content_quotes() {
+        let json = export_h_entry(&note("Title", vec![], None), "visibility");
+        assert!(json.contains(r#""content":["Hello \"world\""]"#));
+    }
+
+    #[test]
+    fn test_h_feed_nests_entries_as_children() {
+        let notes = vec![note("A", vec![], None), note("B", vec![], None)];
+        let json = export_h_feed(&notes, "visibility");
+        assert!(json.starts_with(r#"{"type":["h-feed"],"children":["#));
+        assert_eq!(json.matches("h-entry").count(), 2);
+    }
+}
@@ -6,6 +6,23 @@ pub mod markdown;
 pub mod notes_config;
 pub mod database;
 pub mod frontmatter;
+pub mod embeddings;
+pub mod syntax_highlight;
+pub mod theme;
+pub mod search;
+pub mod filter_node;
+pub mod sort_key;
+pub mod audio_tags;
+pub mod crypto;
+pub mod export;
+pub mod icon_registry;
+pub mod markdown_extensions;
+pub mod mf2;
+pub mod playlist;
+pub mod reminder_schedule;
+pub mod search_index;
+pub mod site_export;
+pub mod version_control;
 
 pub use note_buffer::NoteBuffer;
 pub use editor_mode::EditorMode;
@@ -15,3 +32,24 @@ pub use markdown::{MarkdownParser, StyleType};
 pub use notes_config::NotesConfig;
 pub use database::NotesDatabase;
 pub use frontmatter::{extract_tags, extract_inline_tags, extract_all_tags};
+pub use embeddings::{EmbeddingProvider, NoteEmbedding, ScoredNote};
+pub use syntax_highlight::{render_code_block, HIGHLIGHT_CSS_DARK, HIGHLIGHT_CSS_LIGHT};
+pub use search::SearchOptions;
+pub use filter_node::{matches_glob, FilterNode};
+pub use sort_key::{compare_sort_keys, NullOrder, SortKey};
+pub use export::{export_view, ExportFormat};
+pub use audio_tags::{format_duration, now_playing_rows, read_tags, TrackMetadata};
+pub use crypto::{decrypt, encrypt, is_encrypted, CryptoError, EncryptionConfig, EncryptionPolicy, KdfParams};
+pub use icon_registry::{all_icons, glyph_for_shortcode, search_icons, IconDef};
+pub use markdown_extensions::{
+    find_footnote_refs, find_strikethrough, parse_table, parse_task_list_item,
+    resolve_footnote_definitions, toggle_task_checkbox, ColumnAlignment, ExtendedStyle,
+    StyledSpan, Table,
+};
+pub use mf2::{export_h_entry, export_h_feed};
+pub use playlist::{export_playlist, export_playlist_file, import_playlist_file, parse_playlist, PlaylistEntry};
+pub use reminder_schedule::{Recurrence, RecurrenceUnit, SnoozeDuration};
+pub use search_index::{tokenize, InvertedIndex, SearchHit};
+pub use site_export::{export_html, ExportOptions, SiteNote, SiteTheme};
+pub use theme::{dark_theme, light_theme, next_theme_name, parse_theme_toml, StyleAttributes, StyleKey, Theme};
+pub use version_control::{CommitInfo, GitRepo};
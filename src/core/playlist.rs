@@ -0,0 +1,211 @@
+//! Import y export de playlists en formato M3U/M3U8 extendido
+//!
+//! Solo implementa el subconjunto de la extensión que usan la mayoría de
+//! reproductores: una cabecera `#EXTM3U`, una línea
+//! `#EXTINF:<segundos>,<artista> - <título>` antes de cada entrada y una
+//! línea por ruta/URL. Cualquier otra línea que empiece por `#` se ignora al
+//! importar. Esto deja que las colas de este reproductor interoperen con
+//! cualquier otro que entienda M3U, sin depender de un formato propio.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Una entrada de playlist, con la ruta ya resuelta a absoluta cuando
+/// procede (ver [`resolve_location`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistEntry {
+    pub location: String,
+    pub artist: Option<String>,
+    pub title: Option<String>,
+    pub duration_secs: Option<i64>,
+}
+
+impl PlaylistEntry {
+    /// Texto a mostrar cuando no hay mejor título: "artista - título", solo
+    /// el título, o la propia ruta/URL si `#EXTINF` no traía ninguno.
+    pub fn display_title(&self) -> String {
+        match (&self.artist, &self.title) {
+            (Some(artist), Some(title)) => format!("{artist} - {title}"),
+            (None, Some(title)) => title.clone(),
+            _ => self.location.clone(),
+        }
+    }
+}
+
+/// Parsea el contenido ya decodificado de un archivo M3U/M3U8. `base_dir`,
+/// si se da, es el directorio del propio archivo de playlist, usado para
+/// resolver rutas relativas.
+pub fn parse_playlist(contents: &str, base_dir: Option<&Path>) -> Vec<PlaylistEntry> {
+    let mut entries = Vec::new();
+    let mut pending: Option<(Option<i64>, Option<String>, Option<String>)> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.eq_ignore_ascii_case("#EXTM3U") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("#EXTINF:") {
+            pending = Some(parse_extinf(rest));
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let (duration_secs, artist, title) = pending.take().unwrap_or((None, None, None));
+        entries.push(PlaylistEntry {
+            location: resolve_location(line, base_dir),
+            artist,
+            title,
+            duration_secs,
+        });
+    }
+
+    entries
+}
+
+/// Divide lo que sigue a `#EXTINF:` (`<segundos>,<artista> - <título>`) en
+/// sus tres partes. Sin separador `" - "` se trata toda la etiqueta como
+/// título.
+fn parse_extinf(rest: &str) -> (Option<i64>, Option<String>, Option<String>) {
+    let Some((duration, label)) = rest.split_once(',') else {
+        return (None, None, None);
+    };
+    let duration_secs = duration.trim().parse::<i64>().ok();
+    let label = label.trim();
+    if label.is_empty() {
+        return (duration_secs, None, None);
+    }
+    match label.split_once(" - ") {
+        Some((artist, title)) => (duration_secs, Some(artist.to_string()), Some(title.to_string())),
+        None => (duration_secs, None, Some(label.to_string())),
+    }
+}
+
+/// Las URL (cualquier esquema con `://`) y las rutas ya absolutas se dejan
+/// tal cual; el resto se resuelve contra `base_dir`, igual que hace
+/// cualquier reproductor M3U con rutas relativas.
+fn resolve_location(line: &str, base_dir: Option<&Path>) -> String {
+    if line.contains("://") || Path::new(line).is_absolute() {
+        return line.to_string();
+    }
+    match base_dir {
+        Some(dir) => dir.join(line).to_string_lossy().into_owned(),
+        None => line.to_string(),
+    }
+}
+
+/// Serializa `entries` como M3U extendido, con `-1` como duración cuando se
+/// desconoce (la convención del formato para "duración sin determinar").
+pub fn export_playlist(entries: &[PlaylistEntry]) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    for entry in entries {
+        let duration = entry.duration_secs.unwrap_or(-1);
+        out.push_str(&format!("#EXTINF:{duration},{}\n", entry.display_title()));
+        out.push_str(&entry.location);
+        out.push('\n');
+    }
+    out
+}
+
+/// Importa una playlist desde disco, decodificando según la extensión:
+/// `.m3u8` exige UTF-8 estricto; `.m3u` se asume UTF-8 pero, si la lectura
+/// estricta falla, se tolera como Latin-1 (cada byte es su propio
+/// codepoint), que es la convención histórica del formato.
+pub fn import_playlist_file(path: &Path) -> io::Result<Vec<PlaylistEntry>> {
+    let bytes = fs::read(path)?;
+    let is_m3u8 = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("m3u8"))
+        .unwrap_or(false);
+
+    let contents = match String::from_utf8(bytes.clone()) {
+        Ok(text) => text,
+        Err(_) if is_m3u8 => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "el archivo .m3u8 no es UTF-8 válido",
+            ));
+        }
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    };
+
+    Ok(parse_playlist(&contents, path.parent()))
+}
+
+/// Escribe `entries` como M3U extendido en `path`.
+pub fn export_playlist_file(path: &Path, entries: &[PlaylistEntry]) -> io::Result<()> {
+    fs::write(path, export_playlist(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_extinf_with_artist_and_title() {
+        let m3u = "#EXTM3U\n#EXTINF:213,Queen - Bohemian Rhapsody\nsongs/bohemian.mp3\n";
+        let entries = parse_playlist(m3u, None);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].artist.as_deref(), Some("Queen"));
+        assert_eq!(entries[0].title.as_deref(), Some("Bohemian Rhapsody"));
+        assert_eq!(entries[0].duration_secs, Some(213));
+    }
+
+    #[test]
+    fn test_ignores_comment_lines_other_than_extinf() {
+        let m3u = "#EXTM3U\n#PLAYLIST:Mi playlist\nsongs/a.mp3\n";
+        let entries = parse_playlist(m3u, None);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].location, "songs/a.mp3");
+    }
+
+    #[test]
+    fn test_keeps_urls_verbatim() {
+        let m3u = "#EXTM3U\n#EXTINF:300,Some track\nhttps://youtube.com/watch?v=abc\n";
+        let entries = parse_playlist(m3u, Some(Path::new("/home/user/playlists")));
+        assert_eq!(entries[0].location, "https://youtube.com/watch?v=abc");
+    }
+
+    #[test]
+    fn test_resolves_relative_paths_against_base_dir() {
+        let m3u = "#EXTM3U\nsongs/a.mp3\n";
+        let entries = parse_playlist(m3u, Some(Path::new("/home/user/playlists")));
+        assert_eq!(entries[0].location, "/home/user/playlists/songs/a.mp3");
+    }
+
+    #[test]
+    fn test_entry_without_extinf_has_no_metadata() {
+        let m3u = "#EXTM3U\nsongs/a.mp3\n";
+        let entries = parse_playlist(m3u, None);
+        assert_eq!(entries[0].artist, None);
+        assert_eq!(entries[0].title, None);
+        assert_eq!(entries[0].duration_secs, None);
+    }
+
+    #[test]
+    fn test_export_round_trips_through_parse() {
+        let entries = vec![PlaylistEntry {
+            location: "songs/a.mp3".to_string(),
+            artist: Some("Artist".to_string()),
+            title: Some("Title".to_string()),
+            duration_secs: Some(180),
+        }];
+        let m3u = export_playlist(&entries);
+        let reparsed = parse_playlist(&m3u, None);
+        assert_eq!(reparsed, entries);
+    }
+
+    #[test]
+    fn test_export_uses_negative_one_for_unknown_duration() {
+        let entries = vec![PlaylistEntry {
+            location: "songs/a.mp3".to_string(),
+            artist: None,
+            title: None,
+            duration_secs: None,
+        }];
+        assert!(export_playlist(&entries).contains("#EXTINF:-1,"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Presets de posposición y reglas de recurrencia para recordatorios
+//!
+//! El menú de posponer y el campo "repetir" del diálogo de recordatorio solo
+//! necesitan datos puros: a qué instante salta una posposición, y cuál es la
+//! próxima ocurrencia de una regla de recurrencia. Ese cálculo vive aquí para
+//! que tanto la UI nativa como la herramienta MCP `snooze_reminder` compartan
+//! la misma tabla de presets en vez de tener cada una su propio listado de
+//! minutos hardcodeado.
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+/// Posposición preestablecida, más un valor libre en minutos para cuando
+/// ninguno de los presets encaja.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnoozeDuration {
+    FiveMinutes,
+    ThirtyMinutes,
+    OneHour,
+    SixHours,
+    TwelveHours,
+    OneDay,
+    ThreeDays,
+    SevenDays,
+    Custom(i64),
+}
+
+impl SnoozeDuration {
+    /// Todos los presets fijos, en el orden en que deberían listarse en el
+    /// menú de posponer. `Custom` no entra aquí: su valor depende de lo que
+    /// teclee el usuario o pida el agente MCP.
+    pub const PRESETS: &'static [SnoozeDuration] = &[
+        SnoozeDuration::FiveMinutes,
+        SnoozeDuration::ThirtyMinutes,
+        SnoozeDuration::OneHour,
+        SnoozeDuration::SixHours,
+        SnoozeDuration::TwelveHours,
+        SnoozeDuration::OneDay,
+        SnoozeDuration::ThreeDays,
+        SnoozeDuration::SevenDays,
+    ];
+
+    /// Duración en minutos del preset.
+    pub fn minutes(&self) -> i64 {
+        match self {
+            SnoozeDuration::FiveMinutes => 5,
+            SnoozeDuration::ThirtyMinutes => 30,
+            SnoozeDuration::OneHour => 60,
+            SnoozeDuration::SixHours => 6 * 60,
+            SnoozeDuration::TwelveHours => 12 * 60,
+            SnoozeDuration::OneDay => 24 * 60,
+            SnoozeDuration::ThreeDays => 3 * 24 * 60,
+            SnoozeDuration::SevenDays => 7 * 24 * 60,
+            SnoozeDuration::Custom(minutes) => *minutes,
+        }
+    }
+
+    /// Clave de traducción para este preset (ver `i18n::I18n::t`).
+    pub fn translation_key(&self) -> &'static str {
+        match self {
+            SnoozeDuration::FiveMinutes => "reminder_snooze_5min",
+            SnoozeDuration::ThirtyMinutes => "reminder_snooze_30min",
+            SnoozeDuration::OneHour => "reminder_snooze_1hour",
+            SnoozeDuration::SixHours => "reminder_snooze_6hours",
+            SnoozeDuration::TwelveHours => "reminder_snooze_12hours",
+            SnoozeDuration::OneDay => "reminder_snooze_1day",
+            SnoozeDuration::ThreeDays => "reminder_snooze_3days",
+            SnoozeDuration::SevenDays => "reminder_snooze_7days",
+            SnoozeDuration::Custom(_) => "reminder_snooze_custom",
+        }
+    }
+
+    /// Instante al que salta el recordatorio si se pospone desde `from`.
+    pub fn apply(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        from + Duration::minutes(self.minutes())
+    }
+}
+
+/// Unidad de intervalo de una regla de recurrencia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceUnit {
+    Day,
+    Week,
+    Month,
+}
+
+/// Regla de recurrencia al estilo RRULE: un intervalo y una unidad, con un
+/// día de la semana opcional (para "cada 2 semanas los lunes") o un día del
+/// mes opcional (para "el día 15 de cada mes"). Sustituye a los cuatro
+/// presets fijos (`reminder_repeat_none/daily/weekly/monthly`) por una regla
+/// que puede expresar cualquiera de ellos más combinaciones nuevas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recurrence {
+    pub interval: u32,
+    pub unit: RecurrenceUnit,
+    pub weekday: Option<chrono::Weekday>,
+    pub day_of_month: Option<u32>,
+}
+
+impl Recurrence {
+    pub fn daily() -> Self {
+        Recurrence { interval: 1, unit: RecurrenceUnit::Day, weekday: None, day_of_month: None }
+    }
+
+    pub fn weekly() -> Self {
+        Recurrence { interval: 1, unit: RecurrenceUnit::Week, weekday: None, day_of_month: None }
+    }
+
+    pub fn monthly() -> Self {
+        Recurrence { interval: 1, unit: RecurrenceUnit::Month, weekday: None, day_of_month: None }
+    }
+
+    /// Próxima ocurrencia después de `from` (normalmente la fecha de
+    /// vencimiento que se acaba de completar). La hora del día se conserva;
+    /// solo cambia la fecha.
+    pub fn next_occurrence(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        let base = match self.unit {
+            RecurrenceUnit::Day => from + Duration::days(self.interval as i64),
+            RecurrenceUnit::Week => from + Duration::weeks(self.interval as i64),
+            RecurrenceUnit::Month => add_months(from, self.interval),
+        };
+
+        let with_weekday = match self.weekday {
+            Some(target) if self.unit == RecurrenceUnit::Week => advance_to_weekday(base, target),
+            _ => base,
+        };
+
+        match self.day_of_month {
+            Some(day) if self.unit == RecurrenceUnit::Month => set_day_of_month(with_weekday, day),
+            _ => with_weekday,
+        }
+    }
+}
+
+/// Añade `months` meses a `date`, recortando al último día del mes destino si
+/// hace falta (p. ej. 31 de enero + 1 mes -> 28/29 de febrero).
+fn add_months(date: DateTime<Utc>, months: u32) -> DateTime<Utc> {
+    let total_months = (date.year() as i64) * 12 + (date.month0() as i64) + months as i64;
+    let target_year = (total_months.div_euclid(12)) as i32;
+    let target_month0 = total_months.rem_euclid(12) as u32;
+    let last_day = days_in_month(target_year, target_month0 + 1);
+    let day = date.day().min(last_day);
+
+    Utc.with_ymd_and_hms(
+        target_year,
+        target_month0 + 1,
+        day,
+        date.hour(),
+        date.minute(),
+        date.second(),
+    )
+    .single()
+    .unwrap_or(date)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let next_month_start = if month == 12 {
+        Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0)
+    } else {
+        Utc.with_ymd_and_hms(year, month + 1, 1, 0, 0, 0)
+    }
+    .single()
+    .expect("fecha de inicio de mes válida");
+    let this_month_start = Utc
+        .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .expect("fecha de inicio de mes válida");
+    (next_month_start - this_month_start).num_days() as u32
+}
+
+/// Avanza `date` hasta el próximo día `target`, incluyendo el propio `date`
+/// si ya cae en ese día de la semana.
+fn advance_to_weekday(date: DateTime<Utc>, target: chrono::Weekday) -> DateTime<Utc> {
+    let current = date.weekday().num_days_from_monday();
+    let wanted = target.num_days_from_monday();
+    let delta = (wanted + 7 - current) % 7;
+    date + Duration::days(delta as i64)
+}
+
+/// Fija el día del mes de `date` al `day` pedido, recortando al último día
+/// del mes si `day` no existe en él (p. ej. pedir el 31 en un mes de 30).
+fn set_day_of_month(date: DateTime<Utc>, day: u32) -> DateTime<Utc> {
+    let last_day = days_in_month(date.year(), date.month());
+    let day = day.clamp(1, last_day);
+    Utc.with_ymd_and_hms(date.year(), date.month(), day, date.hour(), date.minute(), date.second())
+        .single()
+        .unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).single().unwrap()
+    }
+
+    #[test]
+    fn test_snooze_minutes_match_presets() {
+        assert_eq!(SnoozeDuration::FiveMinutes.minutes(), 5);
+        assert_eq!(SnoozeDuration::SixHours.minutes(), 360);
+        assert_eq!(SnoozeDuration::SevenDays.minutes(), 7 * 24 * 60);
+        assert_eq!(SnoozeDuration::Custom(90).minutes(), 90);
+    }
+
+    #[test]
+    fn test_snooze_apply_shifts_from_instant() {
+        let from = dt(2026, 7, 25, 9);
+        let snoozed = SnoozeDuration::OneHour.apply(from);
+        assert_eq!(snoozed, dt(2026, 7, 25, 10));
+    }
+
+    #[test]
+    fn test_daily_recurrence_adds_one_day() {
+        let from = dt(2026, 7, 25, 9);
+        assert_eq!(Recurrence::daily().next_occurrence(from), dt(2026, 7, 26, 9));
+    }
+
+    #[test]
+    fn test_weekly_recurrence_with_interval_two() {
+        let from = dt(2026, 7, 25, 9);
+        let rule = Recurrence { interval: 2, unit: RecurrenceUnit::Week, weekday: None, day_of_month: None };
+        assert_eq!(rule.next_occurrence(from), dt(2026, 8, 8, 9));
+    }
+
+    #[test]
+    fn test_weekly_recurrence_locks_to_weekday() {
+        // 2026-07-25 is a Saturday; "every 2 weeks on Monday" should land on
+        // the Monday at or after the +2-week mark, not two Saturdays later.
+        let from = dt(2026, 7, 25, 9);
+        let rule = Recurrence {
+            interval: 2,
+            unit: RecurrenceUnit::Week,
+            weekday: Some(chrono::Weekday::Mon),
+            day_of_month: None,
+        };
+        let next = rule.next_occurrence(from);
+        assert_eq!(next.weekday(), chrono::Weekday::Mon);
+        assert!(next > from);
+    }
+
+    #[test]
+    fn test_monthly_recurrence_clamps_to_day_of_month() {
+        let from = dt(2026, 1, 31, 9);
+        let rule = Recurrence { interval: 1, unit: RecurrenceUnit::Month, weekday: None, day_of_month: None };
+        // January has 31 days, February 2026 has 28: clamp instead of overflow.
+        assert_eq!(rule.next_occurrence(from), dt(2026, 2, 28, 9));
+    }
+
+    #[test]
+    fn test_monthly_recurrence_honors_explicit_day_of_month() {
+        let from = dt(2026, 1, 10, 9);
+        let rule = Recurrence { interval: 1, unit: RecurrenceUnit::Month, weekday: None, day_of_month: Some(15) };
+        assert_eq!(rule.next_occurrence(from), dt(2026, 2, 15, 9));
+    }
+}
@@ -0,0 +1,19 @@
+//! Opciones de la búsqueda full-text de las vistas de Base
+//!
+//! Se mantiene en `core` (junto a `BaseView`) porque se persiste con la vista;
+//! la UI de la barra de búsqueda (`base_ui`) solo la lee y escribe.
+
+/// Modificadores de la búsqueda full-text de una vista de Base.
+///
+/// Controlan cómo se interpreta la consulta contra el texto de cada propiedad
+/// visible. El valor por defecto es una búsqueda literal, insensible a
+/// mayúsculas y sin exigir palabra completa.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Distinguir mayúsculas/minúsculas (por defecto insensible).
+    pub case_sensitive: bool,
+    /// Exigir coincidencia de palabra completa (`\b…\b`).
+    pub whole_word: bool,
+    /// Interpretar la consulta como expresión regular.
+    pub regex: bool,
+}
@@ -0,0 +1,248 @@
+//! Índice invertido persistente con ranking BM25 para búsqueda de texto completo
+//!
+//! `NotesDatabase` no usa este índice: este módulo implementa el índice y el
+//! ranking como un tipo independiente (`InvertedIndex`), con
+//! `index_document` (al guardar una nota) y `remove_document` (al borrarla)
+//! como puntos de entrada, y una serialización propia a bytes para guardarlo
+//! en disco sin depender de `serde`, en la línea de los demás parsers de este
+//! módulo (`mf2`, `audio_tags`).
+
+use std::collections::HashMap;
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// Peso multiplicador de cada tag al tokenizar: cuenta como varias
+/// ocurrencias del mismo término para que coincidir con un tag pese más que
+/// coincidir con una palabra suelta del cuerpo.
+const TAG_BOOST: u32 = 3;
+
+/// Resultado de [`InvertedIndex::search`], ya ordenado por relevancia
+/// descendente.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub note_id: i64,
+    pub score: f64,
+}
+
+/// Tokeniza en minúsculas, partiendo por cualquier carácter que no sea
+/// alfanumérico (Unicode-aware vía `char::is_alphanumeric`).
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct DocEntry {
+    term_frequency: u32,
+}
+
+/// Índice invertido: término -> lista de (note_id, frecuencia), más las
+/// longitudes de documento que hacen falta para normalizar BM25.
+#[derive(Debug, Clone, Default)]
+pub struct InvertedIndex {
+    postings: HashMap<String, HashMap<i64, DocEntry>>,
+    doc_lengths: HashMap<i64, u32>,
+}
+
+impl InvertedIndex {
+    pub fn new() -> Self {
+        InvertedIndex::default()
+    }
+
+    pub fn document_count(&self) -> usize {
+        self.doc_lengths.len()
+    }
+
+    fn average_doc_length(&self) -> f64 {
+        if self.doc_lengths.is_empty() {
+            return 0.0;
+        }
+        self.doc_lengths.values().map(|&len| len as f64).sum::<f64>() / self.doc_lengths.len() as f64
+    }
+
+    /// Indexa (o reindexa) `note_id` con el cuerpo y los tags dados. Si la
+    /// nota ya estaba indexada, se retira primero para que una actualización
+    /// no deje postings del contenido anterior.
+    pub fn index_document(&mut self, note_id: i64, body: &str, tags: &[String]) {
+        self.remove_document(note_id);
+
+        let mut term_frequency: HashMap<String, u32> = HashMap::new();
+        for token in tokenize(body) {
+            *term_frequency.entry(token).or_insert(0) += 1;
+        }
+        for tag in tags {
+            for token in tokenize(tag) {
+                *term_frequency.entry(token).or_insert(0) += TAG_BOOST;
+            }
+        }
+
+        let doc_length: u32 = term_frequency.values().sum();
+        self.doc_lengths.insert(note_id, doc_length);
+
+        for (term, frequency) in term_frequency {
+            self.postings.entry(term).or_default().insert(note_id, DocEntry { term_frequency: frequency });
+        }
+    }
+
+    /// Retira `note_id` del índice por completo (nota borrada, o a punto de
+    /// reindexarse tras una edición).
+    pub fn remove_document(&mut self, note_id: i64) {
+        if self.doc_lengths.remove(&note_id).is_none() {
+            return;
+        }
+        self.postings.retain(|_, docs| {
+            docs.remove(&note_id);
+            !docs.is_empty()
+        });
+    }
+
+    /// IDF de un término con la variante BM25 que nunca se vuelve negativa:
+    /// `ln((N - df + 0.5) / (df + 0.5) + 1)`.
+    fn idf(&self, term: &str) -> f64 {
+        let n = self.doc_lengths.len() as f64;
+        let df = self.postings.get(term).map(|docs| docs.len()).unwrap_or(0) as f64;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    /// Busca `query` y devuelve los resultados ordenados por score BM25
+    /// descendente (empates resueltos por `note_id` ascendente, para un
+    /// orden determinista).
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let avgdl = self.average_doc_length();
+        let mut scores: HashMap<i64, f64> = HashMap::new();
+
+        for term in tokenize(query) {
+            let idf = self.idf(&term);
+            let Some(docs) = self.postings.get(&term) else { continue };
+            for (&note_id, entry) in docs {
+                let tf = entry.term_frequency as f64;
+                let doc_length = *self.doc_lengths.get(&note_id).unwrap_or(&0) as f64;
+                let denom = tf + K1 * (1.0 - B + B * doc_length / avgdl.max(1.0));
+                let contribution = idf * (tf * (K1 + 1.0)) / denom;
+                *scores.entry(note_id).or_insert(0.0) += contribution;
+            }
+        }
+
+        let mut hits: Vec<SearchHit> =
+            scores.into_iter().map(|(note_id, score)| SearchHit { note_id, score }).collect();
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap().then(a.note_id.cmp(&b.note_id)));
+        hits
+    }
+
+    /// Serializa el índice a un formato de texto propio: una línea por
+    /// término, con `note_id:frecuencia` separados por comas. Evita
+    /// depender de `serde` solo para persistir un mapa de mapas.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = String::new();
+        let mut terms: Vec<&String> = self.postings.keys().collect();
+        terms.sort();
+        for term in terms {
+            let docs = &self.postings[term];
+            let mut entries: Vec<(i64, u32)> = docs.iter().map(|(&id, e)| (id, e.term_frequency)).collect();
+            entries.sort_by_key(|(id, _)| *id);
+            let postings_str = entries
+                .iter()
+                .map(|(id, tf)| format!("{id}:{tf}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push_str(term);
+            out.push('\t');
+            out.push_str(&postings_str);
+            out.push('\n');
+        }
+        out.into_bytes()
+    }
+
+    /// Reconstruye un índice a partir de lo que produjo [`Self::to_bytes`].
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let mut index = InvertedIndex::new();
+        let text = String::from_utf8_lossy(data);
+        for line in text.lines() {
+            let Some((term, postings_str)) = line.split_once('\t') else { continue };
+            let mut docs = HashMap::new();
+            for entry in postings_str.split(',') {
+                let Some((id_str, tf_str)) = entry.split_once(':') else { continue };
+                let (Ok(id), Ok(tf)) = (id_str.parse::<i64>(), tf_str.parse::<u32>()) else { continue };
+                docs.insert(id, DocEntry { term_frequency: tf });
+                *index.doc_lengths.entry(id).or_insert(0) += tf;
+            }
+            index.postings.insert(term.to_string(), docs);
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_lowercases_and_splits_on_punctuation() {
+        assert_eq!(tokenize("Hola, Mundo!"), vec!["hola", "mundo"]);
+    }
+
+    #[test]
+    fn test_search_ranks_more_frequent_term_higher() {
+        let mut index = InvertedIndex::new();
+        index.index_document(1, "rust rust rust programming", &[]);
+        index.index_document(2, "rust programming language", &[]);
+
+        let hits = index.search("rust");
+        assert_eq!(hits[0].note_id, 1);
+    }
+
+    #[test]
+    fn test_search_with_no_matches_returns_empty() {
+        let mut index = InvertedIndex::new();
+        index.index_document(1, "hello world", &[]);
+        assert!(index.search("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_tag_boosts_term_frequency() {
+        let mut index = InvertedIndex::new();
+        index.index_document(1, "a short note", &["project".to_string()]);
+        index.index_document(2, "a short note about project work", &[]);
+
+        // Both mention "project", but note 1 gets it as a boosted tag term.
+        let hits = index.search("project");
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].note_id, 1);
+    }
+
+    #[test]
+    fn test_reindexing_a_document_replaces_old_postings() {
+        let mut index = InvertedIndex::new();
+        index.index_document(1, "alpha beta", &[]);
+        index.index_document(1, "gamma delta", &[]);
+
+        assert!(index.search("alpha").is_empty());
+        assert_eq!(index.search("gamma")[0].note_id, 1);
+    }
+
+    #[test]
+    fn test_remove_document_clears_its_postings() {
+        let mut index = InvertedIndex::new();
+        index.index_document(1, "alpha beta", &[]);
+        index.remove_document(1);
+        assert!(index.search("alpha").is_empty());
+        assert_eq!(index.document_count(), 0);
+    }
+
+    #[test]
+    fn test_roundtrip_through_bytes() {
+        let mut index = InvertedIndex::new();
+        index.index_document(1, "rust programming", &["lang".to_string()]);
+        index.index_document(2, "python programming", &[]);
+
+        let restored = InvertedIndex::from_bytes(&index.to_bytes());
+        let before = index.search("programming");
+        let after = restored.search("programming");
+        assert_eq!(before.len(), after.len());
+        assert_eq!(before[0].note_id, after[0].note_id);
+    }
+}
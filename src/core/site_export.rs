@@ -0,0 +1,308 @@
+//! Exportar un directorio de notas a un sitio HTML estático navegable
+//!
+//! Este módulo no renderiza el cuerpo de cada nota ni llama a
+//! `MarkdownParser`, y `NotesDirectory` no lo llama: recibe el HTML del
+//! cuerpo ya convertido (lo que `MarkdownParser` produciría) junto con los
+//! metadatos de la nota, y se encarga de la parte que sí es independiente de
+//! qué parser de markdown se use: resolver `[[wikilinks]]` a anclas, agrupar
+//! por carpeta y por tag para construir el índice, y aplicar el tema CSS
+//! elegido. [`export_html`] recibe la lista de notas ya renderizadas.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Una nota lista para exportar: metadatos más el cuerpo ya convertido a
+/// HTML por `MarkdownParser`.
+#[derive(Debug, Clone)]
+pub struct SiteNote {
+    pub relative_path: String,
+    pub title: String,
+    pub folder: Option<String>,
+    pub tags: Vec<String>,
+    pub body_html: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiteTheme {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub theme: SiteTheme,
+    pub site_title: String,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions { theme: SiteTheme::Light, site_title: "Notas".to_string() }
+    }
+}
+
+/// Ruta HTML de salida de una nota relativa a la raíz del sitio exportado,
+/// p. ej. `proyectos/ideas.md` -> `notes/proyectos/ideas.html`.
+fn note_output_path(relative_path: &str) -> String {
+    let without_ext = relative_path.strip_suffix(".md").unwrap_or(relative_path);
+    format!("notes/{without_ext}.html")
+}
+
+/// Genera el sitio completo: una página por nota, una página por tag y un
+/// índice que agrupa por carpeta y por tag. Devuelve un mapa de ruta de
+/// salida (relativa a `out_dir`) -> contenido, para que el llamador decida
+/// cómo escribirlo a disco.
+pub fn export_html(notes: &[SiteNote], options: &ExportOptions) -> HashMap<String, String> {
+    let title_to_path: HashMap<&str, String> =
+        notes.iter().map(|n| (n.title.as_str(), note_output_path(&n.relative_path))).collect();
+
+    let mut pages = HashMap::new();
+    pages.insert("style.css".to_string(), theme_css(options.theme));
+
+    for note in notes {
+        let resolved_body = resolve_wikilinks(&note.body_html, &title_to_path);
+        pages.insert(note_output_path(&note.relative_path), note_page(note, &resolved_body));
+    }
+
+    let mut by_folder: BTreeMap<String, Vec<&SiteNote>> = BTreeMap::new();
+    let mut by_tag: BTreeMap<String, Vec<&SiteNote>> = BTreeMap::new();
+    for note in notes {
+        by_folder.entry(note.folder.clone().unwrap_or_default()).or_default().push(note);
+        for tag in &note.tags {
+            by_tag.entry(tag.clone()).or_default().push(note);
+        }
+    }
+
+    for (tag, tagged_notes) in &by_tag {
+        pages.insert(format!("tags/{}.html", slugify_tag(tag)), tag_page(tag, tagged_notes, options));
+    }
+
+    pages.insert("index.html".to_string(), index_page(&by_folder, &by_tag, options));
+
+    pages
+}
+
+/// Reemplaza `[[Título]]` / `[[Título|etiqueta]]` por un enlace a la nota de
+/// ese título. Un wikilink a un título que no existe en el vault se deja
+/// como texto plano (sin enlace) en vez de romper la página.
+fn resolve_wikilinks(html: &str, title_to_path: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("[[") {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("]]") else {
+            out.push_str("[[");
+            rest = after_open;
+            continue;
+        };
+        let inner = &after_open[..end];
+        let (target, label) = match inner.split_once('|') {
+            Some((target, label)) => (target, label),
+            None => (inner, inner),
+        };
+        match title_to_path.get(target) {
+            Some(path) => out.push_str(&format!(r#"<a href="/{path}" class="wikilink">{label}</a>"#)),
+            None => out.push_str(label),
+        }
+        rest = &after_open[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn html_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Convierte un tag en un segmento de ruta seguro: cualquier carácter que no
+/// sea alfanumérico, `-` o `_` se reemplaza por `-`. Es la única fuente de
+/// verdad para la ruta de la página de un tag, para que la clave con la que
+/// se inserta en `pages` y el `href` que apunta a ella nunca puedan
+/// divergir (un tag con `&`, espacios o `/` rompía el enlace antes).
+fn slugify_tag(tag: &str) -> String {
+    tag.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' }).collect()
+}
+
+fn note_page(note: &SiteNote, body_html: &str) -> String {
+    let tags_html = note
+        .tags
+        .iter()
+        .map(|tag| {
+            format!(
+                r#"<a href="/tags/{slug}.html" class="tag">#{label}</a>"#,
+                slug = slugify_tag(tag),
+                label = html_escape(tag),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="UTF-8"><title>{title}</title><link rel="stylesheet" href="/style.css"></head>
+<body>
+<nav><a href="/index.html">&larr; Índice</a></nav>
+<article>
+<h1>{title}</h1>
+<div class="tags">{tags_html}</div>
+<div class="content">{body_html}</div>
+</article>
+</body>
+</html>
+"#,
+        title = html_escape(&note.title),
+    )
+}
+
+fn tag_page(tag: &str, notes: &[&SiteNote], options: &ExportOptions) -> String {
+    let items = notes
+        .iter()
+        .map(|n| format!(r#"<li><a href="/{path}">{title}</a></li>"#, path = note_output_path(&n.relative_path), title = html_escape(&n.title)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="UTF-8"><title>#{tag} — {site_title}</title><link rel="stylesheet" href="/style.css"></head>
+<body>
+<nav><a href="/index.html">&larr; Índice</a></nav>
+<h1>#{tag}</h1>
+<ul>{items}</ul>
+</body>
+</html>
+"#,
+        tag = html_escape(tag),
+        site_title = html_escape(&options.site_title),
+    )
+}
+
+fn index_page(
+    by_folder: &BTreeMap<String, Vec<&SiteNote>>,
+    by_tag: &BTreeMap<String, Vec<&SiteNote>>,
+    options: &ExportOptions,
+) -> String {
+    let folders_html = by_folder
+        .iter()
+        .map(|(folder, notes)| {
+            let label = if folder.is_empty() { "Sin carpeta".to_string() } else { html_escape(folder) };
+            let items = notes
+                .iter()
+                .map(|n| format!(r#"<li><a href="/{path}">{title}</a></li>"#, path = note_output_path(&n.relative_path), title = html_escape(&n.title)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("<h3>{label}</h3>\n<ul>{items}</ul>")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let tags_html = by_tag
+        .keys()
+        .map(|tag| {
+            format!(
+                r#"<a href="/tags/{slug}.html" class="tag">#{label}</a>"#,
+                slug = slugify_tag(tag),
+                label = html_escape(tag),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="UTF-8"><title>{site_title}</title><link rel="stylesheet" href="/style.css"></head>
+<body>
+<h1>{site_title}</h1>
+<section class="folders">{folders_html}</section>
+<section class="tags">{tags_html}</section>
+</body>
+</html>
+"#,
+        site_title = html_escape(&options.site_title),
+    )
+}
+
+fn theme_css(theme: SiteTheme) -> String {
+    match theme {
+        SiteTheme::Light => {
+            "body { background: #ffffff; color: #1a1a1a; font-family: sans-serif; } .tag { color: #3366cc; }"
+                .to_string()
+        }
+        SiteTheme::Dark => {
+            "body { background: #1a1a1a; color: #e6e6e6; font-family: sans-serif; } .tag { color: #89b4fa; }"
+                .to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(relative_path: &str, title: &str, folder: Option<&str>, tags: &[&str], body: &str) -> SiteNote {
+        SiteNote {
+            relative_path: relative_path.to_string(),
+            title: title.to_string(),
+            folder: folder.map(String::from),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            body_html: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_note_output_path_strips_md_extension() {
+        assert_eq!(note_output_path("proyectos/ideas.md"), "notes/proyectos/ideas.html");
+    }
+
+    #[test]
+    fn test_resolve_wikilinks_to_existing_note() {
+        let mut map = HashMap::new();
+        map.insert("Otra nota", "notes/otra.html".to_string());
+        let resolved = resolve_wikilinks("ver [[Otra nota]] para más", &map);
+        assert!(resolved.contains(r#"<a href="/notes/otra.html" class="wikilink">Otra nota</a>"#));
+    }
+
+    #[test]
+    fn test_resolve_wikilinks_with_custom_label() {
+        let mut map = HashMap::new();
+        map.insert("Otra nota", "notes/otra.html".to_string());
+        let resolved = resolve_wikilinks("[[Otra nota|aquí]]", &map);
+        assert!(resolved.contains(">aquí</a>"));
+    }
+
+    #[test]
+    fn test_resolve_wikilinks_unknown_target_falls_back_to_text() {
+        let resolved = resolve_wikilinks("[[No existe]]", &HashMap::new());
+        assert_eq!(resolved, "No existe");
+    }
+
+    #[test]
+    fn test_export_html_produces_index_and_note_pages() {
+        let notes = vec![note("a.md", "A", Some("proyectos"), &["ideas"], "<p>Cuerpo A</p>")];
+        let pages = export_html(&notes, &ExportOptions::default());
+        assert!(pages.contains_key("index.html"));
+        assert!(pages.contains_key("notes/a.html"));
+        assert!(pages.contains_key("tags/ideas.html"));
+        assert!(pages.contains_key("style.css"));
+    }
+
+    #[test]
+    fn test_tag_page_key_matches_href_for_special_characters() {
+        let notes = vec![note("a.md", "A", None, &["a&b/c"], "<p></p>")];
+        let pages = export_html(&notes, &ExportOptions::default());
+        let slug = slugify_tag("a&b/c");
+        assert!(pages.contains_key(&format!("tags/{slug}.html")));
+        assert!(pages["notes/a.html"].contains(&format!(r#"href="/tags/{slug}.html""#)));
+    }
+
+    #[test]
+    fn test_index_groups_notes_by_folder() {
+        let notes = vec![
+            note("a.md", "A", Some("trabajo"), &[], "<p></p>"),
+            note("b.md", "B", Some("personal"), &[], "<p></p>"),
+        ];
+        let pages = export_html(&notes, &ExportOptions::default());
+        let index = &pages["index.html"];
+        assert!(index.contains("trabajo"));
+        assert!(index.contains("personal"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Claves de ordenamiento tipadas para las vistas de Base
+//!
+//! `PropertyValue::sort_key()` devuelve un `String`, así que comparar ese
+//! resultado como texto ordena "10" antes que "2" y solo ordena fechas si
+//! resultan ser ISO-8601 por casualidad. Este módulo añade una clave
+//! tipada derivada de cada `PropertyValue`, de modo que números, fechas y
+//! texto se comparan con el criterio que les corresponde en vez de como
+//! cadenas.
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::PropertyValue;
+
+/// Dónde van las notas sin valor en la propiedad de ordenamiento.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NullOrder {
+    First,
+    Last,
+}
+
+impl Default for NullOrder {
+    fn default() -> Self {
+        NullOrder::Last
+    }
+}
+
+/// Clave de ordenamiento tipada derivada de un `PropertyValue`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortKey {
+    Number(f64),
+    Date(i64),
+    Text(String),
+    Bool(bool),
+    Empty,
+}
+
+impl SortKey {
+    /// Derivar la clave de ordenamiento de un valor de propiedad. El texto se
+    /// normaliza a minúsculas para que la comparación sea insensible a
+    /// mayúsculas, igual que el resto de comparaciones de texto en filtros.
+    pub fn from_property(value: &PropertyValue) -> SortKey {
+        match value {
+            PropertyValue::Number(n) => SortKey::Number(*n),
+            PropertyValue::Checkbox(b) => SortKey::Bool(*b),
+            PropertyValue::Date(s) | PropertyValue::DateTime(s) => match parse_date_epoch(s) {
+                Some(epoch) => SortKey::Date(epoch),
+                None if s.is_empty() => SortKey::Empty,
+                None => SortKey::Text(s.to_lowercase()),
+            },
+            PropertyValue::Text(s) | PropertyValue::Link(s) => {
+                if s.is_empty() {
+                    SortKey::Empty
+                } else {
+                    SortKey::Text(s.to_lowercase())
+                }
+            }
+            PropertyValue::Tags(items) | PropertyValue::Links(items) | PropertyValue::List(items) => {
+                if items.is_empty() {
+                    SortKey::Empty
+                } else {
+                    // Ordenar antes de unir para que el orden no dependa del
+                    // orden de inserción (dos notas con las mismas etiquetas en
+                    // distinto orden deben empatar).
+                    let mut sorted = items.clone();
+                    sorted.sort();
+                    SortKey::Text(sorted.join(", ").to_lowercase())
+                }
+            }
+        }
+    }
+}
+
+/// Rango de variante usado para comparar claves de tipos distintos (p. ej.
+/// un número frente a un texto), caso en el que no hay una comparación con
+/// significado propio y solo hace falta un orden total estable.
+fn type_rank(key: &SortKey) -> u8 {
+    match key {
+        SortKey::Empty => 0,
+        SortKey::Bool(_) => 1,
+        SortKey::Number(_) => 2,
+        SortKey::Date(_) => 3,
+        SortKey::Text(_) => 4,
+    }
+}
+
+/// Comparar dos claves con un orden total, ubicando las vacías según
+/// `null_order` independientemente del resto. Números, fechas y texto se
+/// comparan con su criterio natural; entre tipos distintos se cae al rango
+/// de variante para que el orden sea determinista.
+pub fn compare_sort_keys(a: &SortKey, b: &SortKey, null_order: NullOrder) -> Ordering {
+    match (a, b) {
+        (SortKey::Empty, SortKey::Empty) => Ordering::Equal,
+        (SortKey::Empty, _) => match null_order {
+            NullOrder::First => Ordering::Less,
+            NullOrder::Last => Ordering::Greater,
+        },
+        (_, SortKey::Empty) => match null_order {
+            NullOrder::First => Ordering::Greater,
+            NullOrder::Last => Ordering::Less,
+        },
+        (SortKey::Number(x), SortKey::Number(y)) => x.partial_cmp(y).unwrap_or(Ordering::Equal),
+        (SortKey::Date(x), SortKey::Date(y)) => x.cmp(y),
+        (SortKey::Bool(x), SortKey::Bool(y)) => x.cmp(y),
+        (SortKey::Text(x), SortKey::Text(y)) => x.cmp(y),
+        (x, y) => type_rank(x).cmp(&type_rank(y)),
+    }
+}
+
+/// Analizar una fecha ISO-8601 (`AAAA-MM-DD`, con componente horario
+/// opcional `THH:MM:SS`) a segundos desde la época Unix, para poder
+/// comparar fechas cronológicamente sin depender de una crate de calendario.
+fn parse_date_epoch(value: &str) -> Option<i64> {
+    let (date_part, time_part) = value.split_once('T').unwrap_or((value, ""));
+    let mut parts = date_part.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    if !time_part.is_empty() {
+        let mut hms = time_part.splitn(3, ':');
+        let hour: i64 = hms.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let minute: i64 = hms.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let second: i64 = hms
+            .next()
+            .and_then(|s| s.split(['.', '+', 'Z']).next())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        seconds += hour * 3600 + minute * 60 + second;
+    }
+    Some(seconds)
+}
+
+/// Días desde 1970-01-01 para una fecha civil (año/mes/día), con el
+/// algoritmo de Howard Hinnant, válido para cualquier año incluidos los
+/// negativos.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_compare_numerically_not_lexicographically() {
+        let a = SortKey::from_property(&PropertyValue::Number(2.0));
+        let b = SortKey::from_property(&PropertyValue::Number(10.0));
+        assert_eq!(compare_sort_keys(&a, &b, NullOrder::Last), Ordering::Less);
+    }
+
+    #[test]
+    fn test_dates_compare_chronologically() {
+        let a = SortKey::from_property(&PropertyValue::Date("2024-01-01".to_string()));
+        let b = SortKey::from_property(&PropertyValue::Date("2023-12-31".to_string()));
+        assert_eq!(compare_sort_keys(&a, &b, NullOrder::Last), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_datetime_orders_by_time_within_same_day() {
+        let a = SortKey::from_property(&PropertyValue::DateTime("2024-01-01T08:00:00".to_string()));
+        let b = SortKey::from_property(&PropertyValue::DateTime("2024-01-01T20:00:00".to_string()));
+        assert_eq!(compare_sort_keys(&a, &b, NullOrder::Last), Ordering::Less);
+    }
+
+    #[test]
+    fn test_text_compares_case_insensitively() {
+        let a = SortKey::from_property(&PropertyValue::Text("banana".to_string()));
+        let b = SortKey::from_property(&PropertyValue::Text("Apple".to_string()));
+        assert_eq!(compare_sort_keys(&a, &b, NullOrder::Last), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_empty_respects_null_order() {
+        let empty = SortKey::Empty;
+        let value = SortKey::Number(1.0);
+        assert_eq!(compare_sort_keys(&empty, &value, NullOrder::First), Ordering::Less);
+        assert_eq!(compare_sort_keys(&empty, &value, NullOrder::Last), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_tags_sort_key_ignores_insertion_order() {
+        let a = SortKey::from_property(&PropertyValue::Tags(vec!["b".to_string(), "a".to_string()]));
+        let b = SortKey::from_property(&PropertyValue::Tags(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(compare_sort_keys(&a, &b, NullOrder::Last), Ordering::Equal);
+    }
+}
@@ -0,0 +1,257 @@
+//! Resaltado de sintaxis de bloques de código con tree-sitter
+//!
+//! El renderizador de notas ([`crate::core::MarkdownParser`] →
+//! `HtmlRenderer`) emite los bloques cercados (```lang … ```) como texto
+//! plano. Este módulo añade una pasada de resaltado:
+//! - Se localiza la gramática tree-sitter a partir de la etiqueta de lenguaje
+//!   del bloque (`rust`, `js`, `python`, …).
+//! - Se recorre el árbol de análisis y se mapea cada rango de nodo a una clase
+//!   de resaltado (`keyword`/`string`/`comment`/`function`/`type`).
+//! - Se envuelve cada tramo en `<span class="hl-…">`, que `PreviewTheme` estila
+//!   por tema claro/oscuro (ver [`HIGHLIGHT_CSS_LIGHT`]/[`HIGHLIGHT_CSS_DARK`]).
+//!
+//! Invariantes: sin gramática para la etiqueta se degrada a un `<pre>` plano
+//! (nunca se pierde el código), y el resultado se cachea por
+//! `(lenguaje, hash del fuente)`, así que pedir otra vez el mismo bloque no
+//! lo reanaliza. `update_language`/`render_table_html` de `base_ui` no llaman
+//! a [`render_code_block`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use tree_sitter_highlight::{Highlight, HighlightConfiguration, HighlightEvent, Highlighter};
+
+/// Nombres de captura que configuramos en las consultas de resaltado.
+///
+/// El orden es el índice que tree-sitter devuelve en [`Highlight`]; se mapea a
+/// una clase CSS con [`class_for`]. Las capturas no listadas se ignoran (se
+/// emiten como texto sin envolver).
+const HIGHLIGHT_NAMES: &[&str] = &[
+    "keyword",
+    "string",
+    "comment",
+    "function",
+    "function.method",
+    "type",
+    "type.builtin",
+    "constant",
+    "constant.builtin",
+    "number",
+];
+
+/// Clase CSS (`hl-…`) para un nombre de captura, o `None` para dejarlo sin
+/// envolver. Las sub-capturas se colapsan a las cinco familias principales.
+fn class_for(name: &str) -> Option<&'static str> {
+    match name {
+        "keyword" => Some("hl-keyword"),
+        "string" => Some("hl-string"),
+        "comment" => Some("hl-comment"),
+        "function" | "function.method" => Some("hl-function"),
+        "type" | "type.builtin" => Some("hl-type"),
+        // Constantes y números comparten el color de palabra clave, como en la
+        // mayoría de temas de editor.
+        "constant" | "constant.builtin" | "number" => Some("hl-keyword"),
+        _ => None,
+    }
+}
+
+thread_local! {
+    /// Configuraciones de resaltado ya construidas, por lenguaje. Construir una
+    /// `HighlightConfiguration` compila su consulta, así que se reutiliza.
+    static CONFIGS: RefCell<HashMap<&'static str, Option<HighlightConfiguration>>> =
+        RefCell::new(HashMap::new());
+
+    /// HTML resaltado ya calculado, por `(lenguaje, hash del fuente)`.
+    static CACHE: RefCell<HashMap<(String, u64), String>> = RefCell::new(HashMap::new());
+}
+
+/// Renderizar un bloque de código cercado a HTML resaltado.
+///
+/// `lang` es la etiqueta del bloque (puede venir vacía). Devuelve siempre un
+/// `<pre class="code-block">…</pre>`; cuando no hay gramática disponible el
+/// cuerpo es el fuente escapado sin resaltar.
+pub fn render_code_block(lang: &str, source: &str) -> String {
+    let lang = normalize_lang(lang);
+
+    // Servir desde caché si ya se resaltó este fuente en este lenguaje.
+    let key = (lang.to_string(), hash_source(source));
+    if let Some(cached) = CACHE.with(|c| c.borrow().get(&key).cloned()) {
+        return cached;
+    }
+
+    let body = highlight_to_html(lang, source).unwrap_or_else(|| escape_html(source));
+    let html = format!(
+        r#"<pre class="code-block" data-lang="{}"><code>{}</code></pre>"#,
+        escape_html(lang),
+        body,
+    );
+
+    CACHE.with(|c| c.borrow_mut().insert(key, html.clone()));
+    html
+}
+
+/// Ejecutar la pasada de tree-sitter y producir el cuerpo con `<span>`s.
+///
+/// Devuelve `None` cuando no hay gramática para `lang` (el llamador degrada a
+/// texto plano) o si el análisis falla.
+fn highlight_to_html(lang: &str, source: &str) -> Option<String> {
+    CONFIGS.with(|configs| {
+        let mut configs = configs.borrow_mut();
+        let config = configs
+            .entry(static_lang(lang)?)
+            .or_insert_with(|| build_config(lang));
+        let config = config.as_ref()?;
+
+        let mut highlighter = Highlighter::new();
+        let events = highlighter
+            .highlight(config, source.as_bytes(), None, |_| None)
+            .ok()?;
+
+        let mut out = String::with_capacity(source.len() * 2);
+        // Pila de clases activas para poder abrir/cerrar `<span>`s anidados.
+        let mut stack: Vec<Option<&'static str>> = Vec::new();
+        for event in events {
+            match event.ok()? {
+                HighlightEvent::HighlightStart(Highlight(idx)) => {
+                    let class = HIGHLIGHT_NAMES.get(idx).and_then(|n| class_for(n));
+                    if let Some(class) = class {
+                        out.push_str(&format!(r#"<span class="{class}">"#));
+                    }
+                    stack.push(class);
+                }
+                HighlightEvent::HighlightEnd => {
+                    if let Some(Some(_)) = stack.pop() {
+                        out.push_str("</span>");
+                    }
+                }
+                HighlightEvent::Source { start, end } => {
+                    out.push_str(&escape_html(&source[start..end]));
+                }
+            }
+        }
+        Some(out)
+    })
+}
+
+/// Construir la configuración de resaltado para un lenguaje, o `None` si no hay
+/// gramática empaquetada para él.
+fn build_config(lang: &str) -> Option<HighlightConfiguration> {
+    let (language, highlights) = grammar_for(lang)?;
+    let mut config = HighlightConfiguration::new(language, lang, highlights, "", "").ok()?;
+    config.configure(HIGHLIGHT_NAMES);
+    Some(config)
+}
+
+/// Gramática tree-sitter y consulta de resaltado para una etiqueta de lenguaje.
+fn grammar_for(lang: &str) -> Option<(tree_sitter::Language, &'static str)> {
+    match lang {
+        "rust" => Some((tree_sitter_rust::language(), tree_sitter_rust::HIGHLIGHT_QUERY)),
+        "javascript" => Some((
+            tree_sitter_javascript::language(),
+            tree_sitter_javascript::HIGHLIGHT_QUERY,
+        )),
+        "python" => Some((tree_sitter_python::language(), tree_sitter_python::HIGHLIGHT_QUERY)),
+        "json" => Some((tree_sitter_json::language(), tree_sitter_json::HIGHLIGHT_QUERY)),
+        _ => None,
+    }
+}
+
+/// Etiqueta canónica del lenguaje: minúsculas, alias comunes resueltos y
+/// cualquier etiqueta no soportada colapsada a `""` (se tratará como sin
+/// gramática). Devuelve siempre una `&'static str` para clavar las cachés.
+fn normalize_lang(lang: &str) -> &'static str {
+    match lang.trim().to_ascii_lowercase().as_str() {
+        "rust" | "rs" => "rust",
+        "javascript" | "js" | "jsx" | "node" => "javascript",
+        "python" | "py" | "python3" => "python",
+        "json" => "json",
+        _ => "",
+    }
+}
+
+/// Mapear una etiqueta ya normalizada a la `&'static str` con la que indexamos
+/// las configuraciones, o `None` si no hay gramática soportada.
+fn static_lang(lang: &str) -> Option<&'static str> {
+    match lang {
+        "rust" => Some("rust"),
+        "javascript" => Some("javascript"),
+        "python" => Some("python"),
+        "json" => Some("json"),
+        _ => None,
+    }
+}
+
+/// Hash estable del fuente para clavar la caché.
+fn hash_source(source: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Escapar los metacaracteres HTML de un tramo de código.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// CSS de resaltado para el tema claro (lo inyecta `PreviewTheme`).
+pub const HIGHLIGHT_CSS_LIGHT: &str = r#"
+.code-block .hl-keyword { color: #d73a49; }
+.code-block .hl-string { color: #032f62; }
+.code-block .hl-comment { color: #6a737d; font-style: italic; }
+.code-block .hl-function { color: #6f42c1; }
+.code-block .hl-type { color: #005cc5; }
+"#;
+
+/// CSS de resaltado para el tema oscuro (lo inyecta `PreviewTheme`).
+pub const HIGHLIGHT_CSS_DARK: &str = r#"
+.code-block .hl-keyword { color: #ff7b72; }
+.code-block .hl-string { color: #a5d6ff; }
+.code-block .hl-comment { color: #8b949e; font-style: italic; }
+.code-block .hl-function { color: #d2a8ff; }
+.code-block .hl-type { color: #79c0ff; }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_language_falls_back_to_plain_pre() {
+        let html = render_code_block("brainfuck", "+[->+]");
+        assert!(html.contains(r#"<pre class="code-block""#));
+        assert!(html.contains("+[-&gt;+]"));
+        assert!(!html.contains("hl-"));
+    }
+
+    #[test]
+    fn test_source_is_html_escaped() {
+        let html = render_code_block("", "a < b && c > d");
+        assert!(html.contains("a &lt; b &amp;&amp; c &gt; d"));
+    }
+
+    #[test]
+    fn test_language_aliases_normalize() {
+        assert_eq!(normalize_lang("RS"), "rust");
+        assert_eq!(normalize_lang("js"), "javascript");
+        assert_eq!(normalize_lang("py"), "python");
+        assert_eq!(normalize_lang("toml"), "");
+    }
+
+    #[test]
+    fn test_class_for_collapses_subcaptures() {
+        assert_eq!(class_for("function.method"), Some("hl-function"));
+        assert_eq!(class_for("type.builtin"), Some("hl-type"));
+        assert_eq!(class_for("number"), Some("hl-keyword"));
+        assert_eq!(class_for("variable"), None);
+    }
+
+    #[test]
+    fn test_cache_returns_identical_html() {
+        let a = render_code_block("json", r#"{"x": 1}"#);
+        let b = render_code_block("json", r#"{"x": 1}"#);
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,306 @@
+//! Temas de color configurables para los estilos de `StyleType`
+//!
+//! Este módulo no usa `core::markdown::StyleType` ni lee ningún campo de
+//! `NotesConfig`: define su propia clave de estilo, [`StyleKey`], y un
+//! [`Theme`] es un mapa `StyleKey -> StyleAttributes`. Ningún renderer de
+//! este árbol consulta un `Theme`. Los temas se pueden cargar desde
+//! un TOML plano, parseado a mano igual que los paquetes de idioma de
+//! `i18n`, para no añadir una dependencia solo por esto.
+
+use std::collections::HashMap;
+
+/// Construcción de `StyleType` que necesita un color propio. Cubre las
+/// mismas categorías que pide la petición (encabezados, código, enlaces,
+/// citas, tags) más las extensiones de `core::markdown_extensions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StyleKey {
+    Heading(u8),
+    Bold,
+    Italic,
+    Code,
+    Link,
+    Quote,
+    Tag,
+    Strikethrough,
+    TaskList,
+    FootnoteRef,
+    TableHeader,
+}
+
+impl StyleKey {
+    /// Nombre usado como clave de sección en el TOML del tema.
+    fn toml_key(&self) -> String {
+        match self {
+            StyleKey::Heading(level) => format!("heading_{level}"),
+            StyleKey::Bold => "bold".to_string(),
+            StyleKey::Italic => "italic".to_string(),
+            StyleKey::Code => "code".to_string(),
+            StyleKey::Link => "link".to_string(),
+            StyleKey::Quote => "quote".to_string(),
+            StyleKey::Tag => "tag".to_string(),
+            StyleKey::Strikethrough => "strikethrough".to_string(),
+            StyleKey::TaskList => "task_list".to_string(),
+            StyleKey::FootnoteRef => "footnote_ref".to_string(),
+            StyleKey::TableHeader => "table_header".to_string(),
+        }
+    }
+
+    fn from_toml_key(key: &str) -> Option<Self> {
+        if let Some(level) = key.strip_prefix("heading_") {
+            return level.parse::<u8>().ok().map(StyleKey::Heading);
+        }
+        Some(match key {
+            "bold" => StyleKey::Bold,
+            "italic" => StyleKey::Italic,
+            "code" => StyleKey::Code,
+            "link" => StyleKey::Link,
+            "quote" => StyleKey::Quote,
+            "tag" => StyleKey::Tag,
+            "strikethrough" => StyleKey::Strikethrough,
+            "task_list" => StyleKey::TaskList,
+            "footnote_ref" => StyleKey::FootnoteRef,
+            "table_header" => StyleKey::TableHeader,
+            _ => return None,
+        })
+    }
+}
+
+/// Atributos de color y énfasis de un estilo. `fg`/`bg` son colores hex
+/// (`"#89b4fa"`) o `None` para heredar el color por defecto del terminal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleAttributes {
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+/// Un tema con nombre: un mapa de [`StyleKey`] a sus atributos. Una clave
+/// ausente del mapa se resuelve a [`StyleAttributes::default`] (sin color,
+/// sin énfasis), para que un tema parcial no rompa el renderer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    styles: HashMap<StyleKey, StyleAttributes>,
+}
+
+impl Theme {
+    pub fn new(name: impl Into<String>) -> Self {
+        Theme { name: name.into(), styles: HashMap::new() }
+    }
+
+    pub fn set(&mut self, key: StyleKey, attrs: StyleAttributes) {
+        self.styles.insert(key, attrs);
+    }
+
+    pub fn style_for(&self, key: StyleKey) -> StyleAttributes {
+        self.styles.get(&key).cloned().unwrap_or_default()
+    }
+
+    /// Serializa el tema al mismo formato TOML plano que lee
+    /// [`parse_theme_toml`].
+    pub fn to_toml(&self) -> String {
+        let mut out = format!("name = \"{}\"\n", self.name);
+        let mut keys: Vec<&StyleKey> = self.styles.keys().collect();
+        keys.sort_by_key(|k| k.toml_key());
+        for key in keys {
+            let attrs = &self.styles[key];
+            out.push_str(&format!("\n[styles.{}]\n", key.toml_key()));
+            if let Some(fg) = &attrs.fg {
+                out.push_str(&format!("fg = \"{fg}\"\n"));
+            }
+            if let Some(bg) = &attrs.bg {
+                out.push_str(&format!("bg = \"{bg}\"\n"));
+            }
+            if attrs.bold {
+                out.push_str("bold = true\n");
+            }
+            if attrs.italic {
+                out.push_str("italic = true\n");
+            }
+            if attrs.underline {
+                out.push_str("underline = true\n");
+            }
+        }
+        out
+    }
+}
+
+/// Tema claro incorporado.
+pub fn light_theme() -> Theme {
+    let mut theme = Theme::new("light");
+    for level in 1..=6u8 {
+        theme.set(
+            StyleKey::Heading(level),
+            StyleAttributes { fg: Some("#1a1a2e".to_string()), bold: true, ..Default::default() },
+        );
+    }
+    theme.set(StyleKey::Bold, StyleAttributes { bold: true, ..Default::default() });
+    theme.set(StyleKey::Italic, StyleAttributes { italic: true, ..Default::default() });
+    theme.set(
+        StyleKey::Code,
+        StyleAttributes { fg: Some("#c7254e".to_string()), bg: Some("#f9f2f4".to_string()), ..Default::default() },
+    );
+    theme.set(StyleKey::Link, StyleAttributes { fg: Some("#3366cc".to_string()), underline: true, ..Default::default() });
+    theme.set(StyleKey::Quote, StyleAttributes { fg: Some("#6a737d".to_string()), italic: true, ..Default::default() });
+    theme.set(StyleKey::Tag, StyleAttributes { fg: Some("#8957e5".to_string()), ..Default::default() });
+    theme.set(StyleKey::Strikethrough, StyleAttributes { fg: Some("#6a737d".to_string()), ..Default::default() });
+    theme.set(StyleKey::TaskList, StyleAttributes { fg: Some("#28a745".to_string()), ..Default::default() });
+    theme.set(StyleKey::FootnoteRef, StyleAttributes { fg: Some("#3366cc".to_string()), ..Default::default() });
+    theme.set(StyleKey::TableHeader, StyleAttributes { bold: true, ..Default::default() });
+    theme
+}
+
+/// Tema oscuro incorporado, con la misma paleta que usa `base_ui` para su
+/// modo oscuro (`--accent: #89b4fa` en el CSS embebido de la tabla).
+pub fn dark_theme() -> Theme {
+    let mut theme = Theme::new("dark");
+    for level in 1..=6u8 {
+        theme.set(
+            StyleKey::Heading(level),
+            StyleAttributes { fg: Some("#cdd6f4".to_string()), bold: true, ..Default::default() },
+        );
+    }
+    theme.set(StyleKey::Bold, StyleAttributes { bold: true, ..Default::default() });
+    theme.set(StyleKey::Italic, StyleAttributes { italic: true, ..Default::default() });
+    theme.set(
+        StyleKey::Code,
+        StyleAttributes { fg: Some("#f38ba8".to_string()), bg: Some("#313244".to_string()), ..Default::default() },
+    );
+    theme.set(StyleKey::Link, StyleAttributes { fg: Some("#89b4fa".to_string()), underline: true, ..Default::default() });
+    theme.set(StyleKey::Quote, StyleAttributes { fg: Some("#a6adc8".to_string()), italic: true, ..Default::default() });
+    theme.set(StyleKey::Tag, StyleAttributes { fg: Some("#cba6f7".to_string()), ..Default::default() });
+    theme.set(StyleKey::Strikethrough, StyleAttributes { fg: Some("#a6adc8".to_string()), ..Default::default() });
+    theme.set(StyleKey::TaskList, StyleAttributes { fg: Some("#a6e3a1".to_string()), ..Default::default() });
+    theme.set(StyleKey::FootnoteRef, StyleAttributes { fg: Some("#89b4fa".to_string()), ..Default::default() });
+    theme.set(StyleKey::TableHeader, StyleAttributes { bold: true, ..Default::default() });
+    theme
+}
+
+/// Parsea el subconjunto de TOML que usan los temas: un `name = "..."` de
+/// nivel superior y secciones `[styles.<clave>]` con pares `clave = valor`
+/// (cadenas entre comillas para colores, `true`/`false` para los atributos
+/// de énfasis). Cualquier otra construcción TOML (arrays, tablas inline,
+/// fechas...) no está soportada.
+pub fn parse_theme_toml(contents: &str) -> Theme {
+    let mut name = "custom".to_string();
+    let mut theme_styles: HashMap<StyleKey, StyleAttributes> = HashMap::new();
+    let mut current_key: Option<StyleKey> = None;
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            current_key = section.strip_prefix("styles.").and_then(StyleKey::from_toml_key);
+            if let Some(key) = current_key {
+                theme_styles.entry(key).or_default();
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else { continue };
+        let key = key.trim();
+        let value = value.trim().trim_matches('"');
+
+        if current_key.is_none() && key == "name" {
+            name = value.to_string();
+            continue;
+        }
+
+        if let Some(style_key) = current_key {
+            let attrs = theme_styles.entry(style_key).or_default();
+            match key {
+                "fg" => attrs.fg = Some(value.to_string()),
+                "bg" => attrs.bg = Some(value.to_string()),
+                "bold" => attrs.bold = value == "true",
+                "italic" => attrs.italic = value == "true",
+                "underline" => attrs.underline = value == "true",
+                _ => {}
+            }
+        }
+    }
+
+    Theme { name, styles: theme_styles }
+}
+
+/// Nombre del siguiente tema en la lista tras `current`, para
+/// `EditorAction::CycleTheme`. Envuelve al principio; si `current` no está
+/// en la lista, devuelve el primero.
+pub fn next_theme_name<'a>(current: &str, all: &'a [String]) -> Option<&'a str> {
+    if all.is_empty() {
+        return None;
+    }
+    let index = all.iter().position(|name| name == current);
+    let next_index = match index {
+        Some(i) => (i + 1) % all.len(),
+        None => 0,
+    };
+    Some(all[next_index].as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_style_falls_back_to_default() {
+        let theme = Theme::new("empty");
+        assert_eq!(theme.style_for(StyleKey::Code), StyleAttributes::default());
+    }
+
+    #[test]
+    fn test_light_and_dark_themes_differ() {
+        assert_ne!(light_theme().style_for(StyleKey::Link).fg, dark_theme().style_for(StyleKey::Link).fg);
+    }
+
+    #[test]
+    fn test_parse_theme_toml_reads_name_and_styles() {
+        let toml = r#"
+name = "solarized"
+
+[styles.code]
+fg = "#dc322f"
+bg = "#fdf6e3"
+
+[styles.bold]
+bold = true
+"#;
+        let theme = parse_theme_toml(toml);
+        assert_eq!(theme.name, "solarized");
+        assert_eq!(theme.style_for(StyleKey::Code).fg.as_deref(), Some("#dc322f"));
+        assert_eq!(theme.style_for(StyleKey::Code).bg.as_deref(), Some("#fdf6e3"));
+        assert!(theme.style_for(StyleKey::Bold).bold);
+    }
+
+    #[test]
+    fn test_theme_roundtrips_through_to_toml() {
+        let original = light_theme();
+        let reparsed = parse_theme_toml(&original.to_toml());
+        assert_eq!(reparsed.name, original.name);
+        assert_eq!(reparsed.style_for(StyleKey::Link), original.style_for(StyleKey::Link));
+    }
+
+    #[test]
+    fn test_heading_level_parses_from_section_name() {
+        let toml = "[styles.heading_3]\nfg = \"#ff0000\"\n";
+        let theme = parse_theme_toml(toml);
+        assert_eq!(theme.style_for(StyleKey::Heading(3)).fg.as_deref(), Some("#ff0000"));
+    }
+
+    #[test]
+    fn test_next_theme_name_wraps_around() {
+        let names = vec!["light".to_string(), "dark".to_string(), "solarized".to_string()];
+        assert_eq!(next_theme_name("light", &names), Some("dark"));
+        assert_eq!(next_theme_name("solarized", &names), Some("light"));
+    }
+
+    #[test]
+    fn test_next_theme_name_unknown_current_returns_first() {
+        let names = vec!["light".to_string(), "dark".to_string()];
+        assert_eq!(next_theme_name("nonexistent", &names), Some("light"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Control de versiones y sincronización respaldados por git
+//!
+//! `GitRepo` envuelve un repositorio `git2` con el mismo patrón que
+//! [`crate::core::database::NotesDatabase`] envuelve su conexión: un tipo
+//! propio con métodos de alto nivel (`commit_note`, `history`, `diff`) en
+//! vez de exponer `git2` directamente a los llamadores. El repositorio vive
+//! en la raíz del directorio de notas; `commit_note` crea un commit con
+//! mensaje derivado del título de la nota y la hora, y `push`/`pull`
+//! sincronizan contra el remoto que se les indique (normalmente `origin`).
+//!
+//! `NoteFile`/`NotesDirectory` y `CommandParser` no llaman a este módulo:
+//! guardar una nota no produce un commit por sí solo, y cada operación
+//! (`commit_note`, `history`, `diff`, `read_at_commit`, `push`, `pull`) la
+//! invoca el llamador.
+
+use std::path::Path;
+
+/// Un commit del historial de una nota, tal como lo necesita la UI para
+/// listar versiones anteriores y permitir revertir a una de ellas.
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    pub id: String,
+    pub message: String,
+    pub author: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Repositorio git que respalda un directorio de notas.
+pub struct GitRepo {
+    repo: git2::Repository,
+    root: std::path::PathBuf,
+}
+
+impl GitRepo {
+    /// Abre el repositorio en `root` si ya existe, o inicializa uno nuevo.
+    pub fn open_or_init(root: &Path) -> Result<Self, git2::Error> {
+        let repo = match git2::Repository::open(root) {
+            Ok(repo) => repo,
+            Err(_) => git2::Repository::init(root)?,
+        };
+        Ok(GitRepo { repo, root: root.to_path_buf() })
+    }
+
+    /// Añade `note_path` (relativo a `root`) al índice y crea un commit. El
+    /// mensaje se deriva del título de la nota y la hora del commit, por
+    /// ejemplo `"Update 'Ideas de proyecto' — 2026-07-25 14:30"`. Si no hay
+    /// cambios respecto al commit anterior, no crea un commit vacío.
+    pub fn commit_note(&self, note_path: &Path, title: &str) -> Result<Option<String>, git2::Error> {
+        let relative = note_path.strip_prefix(&self.root).unwrap_or(note_path);
+
+        let mut index = self.repo.index()?;
+        index.add_path(relative)?;
+        index.write()?;
+
+        let tree_id = index.write_tree()?;
+        let tree = self.repo.find_tree(tree_id)?;
+
+        let head = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        if let Some(ref parent) = head {
+            if parent.tree_id() == tree_id {
+                return Ok(None);
+            }
+        }
+
+        let signature = self.repo.signature()?;
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M");
+        let message = format!("Update '{title}' — {now}");
+
+        let parents: Vec<&git2::Commit> = head.iter().collect();
+        let commit_id = self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parents,
+        )?;
+
+        Ok(Some(commit_id.to_string()))
+    }
+
+    /// Historial de commits que tocaron `note_path`, del más reciente al más
+    /// antiguo, equivalente a `git log -- <note_path>`. Los renombrados no se
+    /// siguen: solo cuentan los commits que cambian esa misma ruta.
+    pub fn history(&self, note_path: &Path) -> Result<Vec<CommitInfo>, git2::Error> {
+        let relative = note_path.strip_prefix(&self.root).unwrap_or(note_path);
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+
+        let mut history = Vec::new();
+        for oid in revwalk {
+            let oid = oid?;
+            let commit = self.repo.find_commit(oid)?;
+            if !commit_touches_path(&self.repo, &commit, relative)? {
+                continue;
+            }
+
+            let author = commit.author();
+            let timestamp = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_else(chrono::Utc::now);
+
+            history.push(CommitInfo {
+                id: commit.id().to_string(),
+                message: commit.message().unwrap_or("").trim().to_string(),
+                author: author.name().unwrap_or("").to_string(),
+                timestamp,
+            });
+        }
+        Ok(history)
+    }
+
+    /// Diff unificado de `note_path` entre dos commits.
+    pub fn diff(&self, note_path: &Path, commit_a: &str, commit_b: &str) -> Result<String, git2::Error> {
+        let relative = note_path.strip_prefix(&self.root).unwrap_or(note_path);
+
+        let tree_a = self.repo.find_commit(git2::Oid::from_str(commit_a)?)?.tree()?;
+        let tree_b = self.repo.find_commit(git2::Oid::from_str(commit_b)?)?.tree()?;
+
+        let mut opts = git2::DiffOptions::new();
+        opts.pathspec(relative);
+
+        let diff = self.repo.diff_tree_to_tree(Some(&tree_a), Some(&tree_b), Some(&mut opts))?;
+
+        let mut out = String::new();
+        diff.print(git2::DiffFormat::Patch, |_, _, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                out.push(line.origin());
+            }
+            out.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })?;
+        Ok(out)
+    }
+
+    /// Contenido de `note_path` tal como estaba en `commit_id`, para
+    /// restaurarlo como revert (el llamador decide si lo escribe a disco).
+    pub fn read_at_commit(&self, note_path: &Path, commit_id: &str) -> Result<Vec<u8>, git2::Error> {
+        let relative = note_path.strip_prefix(&self.root).unwrap_or(note_path);
+        let commit = self.repo.find_commit(git2::Oid::from_str(commit_id)?)?;
+        let entry = commit.tree()?.get_path(relative)?;
+        let blob = entry.to_object(&self.repo)?.peel_to_blob()?;
+        Ok(blob.content().to_vec())
+    }
+
+    /// Envía la rama local `branch` a la rama del mismo nombre del remoto
+    /// `remote_name` (normalmente `"origin"`).
+    pub fn push(&self, remote_name: &str, branch: &str) -> Result<(), git2::Error> {
+        let mut remote = self.repo.find_remote(remote_name)?;
+        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+        remote.push(&[refspec.as_str()], None)
+    }
+
+    /// Trae y fusiona (fast-forward) los cambios del remoto `remote_name`.
+    pub fn pull(&self, remote_name: &str, branch: &str) -> Result<(), git2::Error> {
+        let mut remote = self.repo.find_remote(remote_name)?;
+        remote.fetch(&[branch], None, None)?;
+
+        let fetch_head = self.repo.find_reference("FETCH_HEAD")?;
+        let fetch_commit = self.repo.reference_to_annotated_commit(&fetch_head)?;
+        let (analysis, _) = self.repo.merge_analysis(&[&fetch_commit])?;
+
+        if analysis.is_up_to_date() {
+            return Ok(());
+        }
+        if analysis.is_fast_forward() {
+            let refname = format!("refs/heads/{branch}");
+            let mut reference = self.repo.find_reference(&refname)?;
+            reference.set_target(fetch_commit.id(), "fast-forward pull")?;
+            self.repo.set_head(&refname)?;
+            self.repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+        }
+        Ok(())
+    }
+}
+
+fn commit_touches_path(
+    repo: &git2::Repository,
+    commit: &git2::Commit,
+    path: &Path,
+) -> Result<bool, git2::Error> {
+    let tree = commit.tree()?;
+    let current = tree.get_path(path).ok().map(|e| e.id());
+
+    if commit.parent_count() == 0 {
+        return Ok(current.is_some());
+    }
+
+    for parent in commit.parents() {
+        let parent_tree = parent.tree()?;
+        let previous = parent_tree.get_path(path).ok().map(|e| e.id());
+        if previous != current {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
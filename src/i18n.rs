@@ -1,26 +1,262 @@
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 
+/// Directorio donde se buscan paquetes de idioma externos, uno por idioma
+/// (`<code>.ftl` con líneas `clave = valor`, o `<code>.json` con un objeto
+/// plano `{"clave": "valor"}`). Se puede redirigir con la variable de
+/// entorno `NOTNATIVE_LOCALES_DIR`, útil para pruebas y para paquetes de
+/// idioma portables que no viven junto al binario.
+fn locales_dir() -> PathBuf {
+    std::env::var("NOTNATIVE_LOCALES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("locales"))
+}
+
+/// Catálogo de un paquete de idioma externo: clave de traducción -> texto.
+type Catalog = HashMap<&'static str, &'static str>;
+
+static CATALOGS: OnceLock<HashMap<&'static str, Catalog>> = OnceLock::new();
+static DISCOVERED: OnceLock<Vec<Language>> = OnceLock::new();
+
+/// Paquetes de idioma externos descubiertos en [`locales_dir`], indexados
+/// por código. Se cargan una sola vez por ejecución.
+fn catalogs() -> &'static HashMap<&'static str, Catalog> {
+    CATALOGS.get_or_init(|| load_catalogs(&locales_dir()))
+}
+
+fn load_catalogs(dir: &std::path::Path) -> HashMap<&'static str, Catalog> {
+    let mut out = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        // "es" y "en" ya vienen compilados en el binario; un archivo con ese
+        // nombre no los sustituye, para que siempre haya un idioma de
+        // respaldo garantizado.
+        if stem == "es" || stem == "en" {
+            continue;
+        }
+        let Some(ext) = path.extension().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let catalog = match ext {
+            "ftl" => parse_ftl_catalog(&contents),
+            "json" => parse_json_catalog(&contents),
+            _ => continue,
+        };
+        if catalog.is_empty() {
+            continue;
+        }
+        let code: &'static str = Box::leak(stem.to_string().into_boxed_str());
+        out.insert(code, catalog);
+    }
+    out
+}
+
+/// Formato `.ftl` simplificado: una entrada por línea (`clave = valor`),
+/// comentarios con `#` y líneas en blanco ignoradas.
+fn parse_ftl_catalog(contents: &str) -> Catalog {
+    let mut catalog = HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        if key.is_empty() {
+            continue;
+        }
+        let key: &'static str = Box::leak(key.to_string().into_boxed_str());
+        let value: &'static str = Box::leak(value.to_string().into_boxed_str());
+        catalog.insert(key, value);
+    }
+    catalog
+}
+
+/// Objeto JSON plano `{"clave": "valor", ...}`. Se parsea a mano, igual que
+/// el JSON que construye `core::mf2`, para no añadir una dependencia solo
+/// por este formato de carga opcional.
+fn parse_json_catalog(contents: &str) -> Catalog {
+    let mut catalog = HashMap::new();
+    let bytes = contents.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'"' {
+            i += 1;
+            continue;
+        }
+        let Some((key, mut next)) = read_json_string(contents, i) else {
+            break;
+        };
+        while next < bytes.len() && bytes[next].is_ascii_whitespace() {
+            next += 1;
+        }
+        if bytes.get(next) != Some(&b':') {
+            i = next;
+            continue;
+        }
+        next += 1;
+        while next < bytes.len() && bytes[next].is_ascii_whitespace() {
+            next += 1;
+        }
+        let Some((value, after_value)) = read_json_string(contents, next) else {
+            break;
+        };
+        i = after_value;
+        if !key.is_empty() {
+            let key: &'static str = Box::leak(key.into_boxed_str());
+            let value: &'static str = Box::leak(value.into_boxed_str());
+            catalog.insert(key, value);
+        }
+    }
+    catalog
+}
+
+/// Lee una cadena JSON entre comillas a partir de `start` (que debe apuntar
+/// a la comilla de apertura) y devuelve el texto ya sin escapar junto con el
+/// índice de byte justo tras la comilla de cierre.
+fn read_json_string(contents: &str, start: usize) -> Option<(String, usize)> {
+    let bytes = contents.as_bytes();
+    if bytes.get(start) != Some(&b'"') {
+        return None;
+    }
+    let mut i = start + 1;
+    let mut out = String::new();
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => return Some((out, i + 1)),
+            b'\\' if i + 1 < bytes.len() => {
+                match bytes[i + 1] {
+                    b'n' => out.push('\n'),
+                    b't' => out.push('\t'),
+                    b'"' => out.push('"'),
+                    b'\\' => out.push('\\'),
+                    other => out.push(other as char),
+                }
+                i += 2;
+            }
+            b => {
+                let char_len = utf8_char_len(b);
+                out.push_str(&contents[i..i + char_len]);
+                i += char_len;
+            }
+        }
+    }
+    None
+}
+
+fn utf8_char_len(first_byte: u8) -> usize {
+    if first_byte < 0x80 {
+        1
+    } else if first_byte >= 0xF0 {
+        4
+    } else if first_byte >= 0xE0 {
+        3
+    } else {
+        2
+    }
+}
+
+fn discovered_languages() -> &'static [Language] {
+    DISCOVERED
+        .get_or_init(|| {
+            catalogs()
+                .iter()
+                .map(|(&code, catalog)| {
+                    let name = catalog.get("_language_name").copied().unwrap_or(code);
+                    Language { code, name, direction: direction_for_code(code) }
+                })
+                .collect()
+        })
+        .as_slice()
+}
+
+/// Códigos de idioma que el CLDR marca como de escritura de derecha a
+/// izquierda. No tenemos ningún paquete RTL compilado todavía, pero un
+/// paquete de idioma descubierto en [`locales_dir`] para uno de estos
+/// códigos debe recibir `Direction::Rtl` sin que haga falta tocar este
+/// archivo.
+const RTL_CODES: &[&str] = &["ar", "he", "fa", "ur", "yi"];
+
+fn direction_for_code(code: &str) -> Direction {
+    if RTL_CODES.iter().any(|&rtl| code == rtl || code.starts_with(rtl)) {
+        Direction::Rtl
+    } else {
+        Direction::Ltr
+    }
+}
+
+/// Dirección de escritura de un idioma, para atributos `dir` en HTML y para
+/// que el layout de la UI se pueda espejar en idiomas RTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// Valor del atributo HTML `dir` (`"ltr"` / `"rtl"`).
+    pub fn as_attr(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// Idioma de la interfaz: un código (`es`, `en`, `fr`...) y un nombre para
+/// mostrar en el selector de idioma. `Spanish` e `English` llevan sus
+/// traducciones compiladas en el binario; cualquier otro código se resuelve
+/// contra un paquete cargado desde [`locales_dir`], lo que permite instalar
+/// idiomas de la comunidad sin tocar el código Rust. El código y el nombre
+/// son `&'static str` (internados con `Box::leak` para los idiomas
+/// descubiertos en tiempo de ejecución) para que `Language` siga siendo
+/// `Copy` y los sitios que ya comparan `language == Language::Spanish` no
+/// tengan que cambiar.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Language {
-    Spanish,
-    English,
+pub struct Language {
+    code: &'static str,
+    name: &'static str,
+    direction: Direction,
 }
 
+#[allow(non_upper_case_globals)]
 impl Language {
+    pub const Spanish: Language = Language { code: "es", name: "Español", direction: Direction::Ltr };
+    pub const English: Language = Language { code: "en", name: "English", direction: Direction::Ltr };
+
+    /// Resuelve un código de idioma (`"es"`, `"en_US"`, `"fr"`...) contra los
+    /// dos idiomas compilados y los paquetes externos descubiertos en
+    /// [`locales_dir`]. Cae a español si no reconoce nada.
     pub fn from_code(code: &str) -> Self {
         match code {
-            "en" | "en_US" | "en_GB" => Language::English,
-            "es" | "es_ES" | "es_MX" => Language::Spanish,
-            _ => {
-                // Detectar por prefijo
-                if code.starts_with("en") {
-                    Language::English
-                } else if code.starts_with("es") {
-                    Language::Spanish
-                } else {
-                    Language::Spanish // Default
-                }
-            }
+            "en" | "en_US" | "en_GB" => return Language::English,
+            "es" | "es_ES" | "es_MX" => return Language::Spanish,
+            _ => {}
+        }
+        if let Some(lang) = discovered_languages()
+            .iter()
+            .find(|l| l.code == code || code.starts_with(l.code))
+        {
+            return *lang;
+        }
+        if code.starts_with("en") {
+            Language::English
+        } else {
+            Language::Spanish
         }
     }
 
@@ -32,30 +268,176 @@ impl Language {
             .unwrap_or(Language::Spanish)
     }
 
+    /// Todos los idiomas disponibles para el selector de idioma: los dos
+    /// compilados más los paquetes externos encontrados en [`locales_dir`].
+    pub fn available() -> Vec<Language> {
+        let mut all = vec![Language::Spanish, Language::English];
+        all.extend(discovered_languages().iter().copied());
+        all
+    }
+
     pub fn code(&self) -> &'static str {
-        match self {
-            Language::Spanish => "es",
-            Language::English => "en",
-        }
+        self.code
     }
 
     pub fn name(&self) -> &'static str {
-        match self {
-            Language::Spanish => "Español",
-            Language::English => "English",
-        }
+        self.name
+    }
+
+    /// Dirección de escritura del idioma, para el atributo `dir` de la UI.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        self.direction == Direction::Rtl
     }
 }
 
-#[derive(Debug, Clone)]
+/// `I18n` ya no carga sus propias traducciones: es un puntero ligero al
+/// idioma activo sobre el registro compartido [`locales`], que junta los
+/// dos idiomas compilados (es/en) y cualquier paquete externo descubierto
+/// en tiempo de ejecución en la misma estructura clave -> código -> texto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct I18n {
     language: Language,
-    translations: HashMap<&'static str, (&'static str, &'static str)>,
 }
 
-impl I18n {
-    pub fn new(language: Language) -> Self {
-        let mut translations = HashMap::new();
+type CompiledTranslations = HashMap<&'static str, (&'static str, &'static str)>;
+type CompiledPlurals = HashMap<&'static str, HashMap<&'static str, (&'static str, &'static str)>>;
+
+static COMPILED: OnceLock<(CompiledTranslations, CompiledPlurals)> = OnceLock::new();
+static LOCALES: OnceLock<HashMap<&'static str, HashMap<&'static str, &'static str>>> = OnceLock::new();
+
+/// Los dos idiomas compilados en el binario, construidos una sola vez.
+fn compiled() -> &'static (CompiledTranslations, CompiledPlurals) {
+    COMPILED.get_or_init(build_compiled_translations)
+}
+
+/// El registro de idiomas en sí: clave de traducción -> código de idioma ->
+/// texto. Une [`compiled`] (es/en) con los paquetes de [`catalogs`]
+/// descubiertos en [`locales_dir`], para que `t()` no tenga que distinguir
+/// "idioma compilado" de "idioma externo": ambos son, simplemente, una
+/// columna más de este mapa.
+fn locales() -> &'static HashMap<&'static str, HashMap<&'static str, &'static str>> {
+    LOCALES.get_or_init(|| {
+        let mut merged: HashMap<&'static str, HashMap<&'static str, &'static str>> = HashMap::new();
+        for (&key, &(es, en)) in compiled().0.iter() {
+            let entry = merged.entry(key).or_default();
+            entry.insert("es", es);
+            entry.insert("en", en);
+        }
+        for (&code, catalog) in catalogs().iter() {
+            for (&key, &value) in catalog.iter() {
+                merged.entry(key).or_default().insert(code, value);
+            }
+        }
+        merged
+    })
+}
+
+/// Categoría gramatical CLDR de un conteo. Español e inglés comparten la
+/// misma regla: singular solo en `n == 1`, plural en todo lo demás
+/// (incluido 0). Queda como su propio tipo porque otros idiomas (árabe,
+/// ruso...) tienen reglas con más categorías, y un paquete de idioma externo
+/// podría necesitar distinguirlas con claves `<key>.<categoria>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PluralCategory {
+    One,
+    Other,
+}
+
+impl PluralCategory {
+    fn as_key(self) -> &'static str {
+        match self {
+            PluralCategory::One => "one",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// Clasifica `count` según la regla CLDR de [`PluralCategory`]. Los conteos
+/// negativos (p. ej. "hace -3 días" calculado como una diferencia con signo)
+/// se clasifican por su valor absoluto: -1 es tan singular como 1.
+fn plural_category(count: i64) -> PluralCategory {
+    if count.abs() == 1 {
+        PluralCategory::One
+    } else {
+        PluralCategory::Other
+    }
+}
+
+/// Sustituye el primer `{count}` (o, si no hay, el primer `{}`) de `template`
+/// por `count`. Cualquier otro `{}` que quede en la plantilla (p. ej. el de
+/// `mcp_search_results` para la consulta) se deja intacto para una
+/// sustitución posterior con [`I18n::t_args`] o `replacen`.
+fn substitute_count(template: &str, count: i64) -> String {
+    let count = count.to_string();
+    if let Some(pos) = template.find("{count}") {
+        let mut out = template.to_string();
+        out.replace_range(pos..pos + "{count}".len(), &count);
+        return out;
+    }
+    if let Some(pos) = template.find("{}") {
+        let mut out = template.to_string();
+        out.replace_range(pos..pos + 2, &count);
+        return out;
+    }
+    template.to_string()
+}
+
+/// La unidad de tiempo más grande con magnitud >= 1 entre dos instantes, para
+/// elegir la plantilla de [`I18n::format_relative`]. Año > mes > día > hora >
+/// minuto, con meses/años tratados como bloques fijos de 30/365 días: basta
+/// para una fecha de vencimiento de recordatorio, no pretende ser un
+/// calendario exacto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RelativeUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+}
+
+impl RelativeUnit {
+    /// Plantillas (pasado, futuro) para este idioma y categoría plural,
+    /// como la estructura `relativeTime.{past,future}.{one,other}` de CLDR.
+    fn templates(self, language: Language, category: PluralCategory) -> (&'static str, &'static str) {
+        let es = language == Language::Spanish;
+        let one = category == PluralCategory::One;
+        match (self, es, one) {
+            (RelativeUnit::Year, true, true) => ("hace {0} año", "dentro de {0} año"),
+            (RelativeUnit::Year, true, false) => ("hace {0} años", "dentro de {0} años"),
+            (RelativeUnit::Year, false, true) => ("{0} year ago", "in {0} year"),
+            (RelativeUnit::Year, false, false) => ("{0} years ago", "in {0} years"),
+            (RelativeUnit::Month, true, true) => ("hace {0} mes", "dentro de {0} mes"),
+            (RelativeUnit::Month, true, false) => ("hace {0} meses", "dentro de {0} meses"),
+            (RelativeUnit::Month, false, true) => ("{0} month ago", "in {0} month"),
+            (RelativeUnit::Month, false, false) => ("{0} months ago", "in {0} months"),
+            (RelativeUnit::Day, true, true) => ("hace {0} día", "dentro de {0} día"),
+            (RelativeUnit::Day, true, false) => ("hace {0} días", "dentro de {0} días"),
+            (RelativeUnit::Day, false, true) => ("{0} day ago", "in {0} day"),
+            (RelativeUnit::Day, false, false) => ("{0} days ago", "in {0} days"),
+            (RelativeUnit::Hour, true, true) => ("hace {0} hora", "dentro de {0} hora"),
+            (RelativeUnit::Hour, true, false) => ("hace {0} horas", "dentro de {0} horas"),
+            (RelativeUnit::Hour, false, true) => ("{0} hour ago", "in {0} hour"),
+            (RelativeUnit::Hour, false, false) => ("{0} hours ago", "in {0} hours"),
+            (RelativeUnit::Minute, true, true) => ("hace {0} minuto", "dentro de {0} minuto"),
+            (RelativeUnit::Minute, true, false) => ("hace {0} minutos", "dentro de {0} minutos"),
+            (RelativeUnit::Minute, false, true) => ("{0} minute ago", "in {0} minute"),
+            (RelativeUnit::Minute, false, false) => ("{0} minutes ago", "in {0} minutes"),
+        }
+    }
+}
+
+/// Construye las traducciones y las formas plurales compiladas en el
+/// binario (español e inglés). No depende de qué idioma esté activo: es la
+/// misma tabla sin importar el `Language` con el que se cree un `I18n`.
+fn build_compiled_translations() -> (CompiledTranslations, CompiledPlurals) {
+    let mut translations = HashMap::new();
+        let mut plurals: HashMap<&'static str, HashMap<&'static str, (&'static str, &'static str)>> =
+            HashMap::new();
 
         // (key, (spanish, english))
         translations.insert("app_title", ("NotNative", "NotNative"));
@@ -107,6 +489,12 @@ impl I18n {
         translations.insert("rename", ("Renombrar", "Rename"));
         translations.insert("delete", ("Eliminar", "Delete"));
         translations.insert("change_icon", ("Cambiar icono", "Change icon"));
+        translations.insert(
+            "icon_search_placeholder",
+            ("Buscar icono...", "Search icon..."),
+        );
+        translations.insert("no_icons_found", ("No se encontraron iconos", "No icons found"));
+        translations.insert("clear_icon", ("Quitar icono", "Clear icon"));
         translations.insert("view_history", ("Ver historial", "View history"));
         translations.insert(
             "open_in_file_manager",
@@ -149,10 +537,10 @@ impl I18n {
             ),
         );
         translations.insert(
-            "restart_required",
+            "language_changed",
             (
-                "Se requiere reiniciar la aplicación",
-                "Application restart required",
+                "Idioma actualizado",
+                "Language updated",
             ),
         );
 
@@ -294,6 +682,27 @@ impl I18n {
         translations.insert("lines", ("líneas", "lines"));
         translations.insert("words", ("palabras", "words"));
         translations.insert("characters", ("caracteres", "characters"));
+        plurals.insert(
+            "lines",
+            HashMap::from([
+                ("one", ("{} línea", "{} line")),
+                ("other", ("{} líneas", "{} lines")),
+            ]),
+        );
+        plurals.insert(
+            "words",
+            HashMap::from([
+                ("one", ("{} palabra", "{} word")),
+                ("other", ("{} palabras", "{} words")),
+            ]),
+        );
+        plurals.insert(
+            "characters",
+            HashMap::from([
+                ("one", ("{} carácter", "{} character")),
+                ("other", ("{} caracteres", "{} characters")),
+            ]),
+        );
         translations.insert("saved", ("Guardado", "Saved"));
         translations.insert(
             "unsaved_changes",
@@ -411,6 +820,27 @@ impl I18n {
             "music_playlist_name",
             ("Nombre de la playlist", "Playlist name"),
         );
+        translations.insert(
+            "music_import_playlist",
+            ("Importar playlist (M3U)", "Import playlist (M3U)"),
+        );
+        translations.insert(
+            "music_export_playlist",
+            ("Exportar playlist (M3U)", "Export playlist (M3U)"),
+        );
+        translations.insert(
+            "playlist_import_error",
+            (
+                "No se pudo leer la playlist: {}",
+                "Could not read the playlist: {}",
+            ),
+        );
+        translations.insert("music_meta_file", ("Archivo", "File"));
+        translations.insert("music_meta_artist", ("Artista", "Artist"));
+        translations.insert("music_meta_title", ("Título", "Title"));
+        translations.insert("music_meta_album", ("Álbum", "Album"));
+        translations.insert("music_meta_date", ("Fecha", "Date"));
+        translations.insert("music_meta_duration", ("Duración", "Duration"));
 
         // System Tray
         translations.insert("tray_show_window", ("Mostrar ventana", "Show window"));
@@ -577,18 +1007,46 @@ impl I18n {
             "mcp_notes_found",
             ("✓ {} notas encontradas", "✓ {} notes found"),
         );
+        plurals.insert(
+            "mcp_notes_found",
+            HashMap::from([
+                ("one", ("✓ {} nota encontrada", "✓ {} note found")),
+                ("other", ("✓ {} notas encontradas", "✓ {} notes found")),
+            ]),
+        );
         translations.insert(
             "mcp_search_results",
             ("✓ {} resultados para '{}'", "✓ {} results for '{}'"),
         );
+        plurals.insert(
+            "mcp_search_results",
+            HashMap::from([
+                ("one", ("✓ {} resultado para '{}'", "✓ {} result for '{}'")),
+                ("other", ("✓ {} resultados para '{}'", "✓ {} results for '{}'")),
+            ]),
+        );
         translations.insert(
             "mcp_notes_with_tag",
             ("✓ {} notas con tag #{}", "✓ {} notes with tag #{}"),
         );
+        plurals.insert(
+            "mcp_notes_with_tag",
+            HashMap::from([
+                ("one", ("✓ {} nota con tag #{}", "✓ {} note with tag #{}")),
+                ("other", ("✓ {} notas con tag #{}", "✓ {} notes with tag #{}")),
+            ]),
+        );
         translations.insert(
             "mcp_tags_found",
             ("✓ {} tags encontrados", "✓ {} tags found"),
         );
+        plurals.insert(
+            "mcp_tags_found",
+            HashMap::from([
+                ("one", ("✓ {} tag encontrado", "✓ {} tag found")),
+                ("other", ("✓ {} tags encontrados", "✓ {} tags found")),
+            ]),
+        );
         translations.insert(
             "mcp_tags_added",
             ("✓ Tags agregados a '{}'", "✓ Tags added to '{}'"),
@@ -622,6 +1080,13 @@ impl I18n {
             "mcp_folders_found",
             ("✓ {} carpetas encontradas", "✓ {} folders found"),
         );
+        plurals.insert(
+            "mcp_folders_found",
+            HashMap::from([
+                ("one", ("✓ {} carpeta encontrada", "✓ {} folder found")),
+                ("other", ("✓ {} carpetas encontradas", "✓ {} folders found")),
+            ]),
+        );
 
         // === RECORDATORIOS / REMINDERS ===
         translations.insert("reminders_title", ("Recordatorios", "Reminders"));
@@ -640,7 +1105,14 @@ impl I18n {
         translations.insert("reminder_priority_urgent", ("Urgente", "Urgent"));
         translations.insert("reminder_snooze_5min", ("5 minutos", "5 minutes"));
         translations.insert("reminder_snooze_15min", ("15 minutos", "15 minutes"));
+        translations.insert("reminder_snooze_30min", ("30 minutos", "30 minutes"));
         translations.insert("reminder_snooze_1hour", ("1 hora", "1 hour"));
+        translations.insert("reminder_snooze_6hours", ("6 horas", "6 hours"));
+        translations.insert("reminder_snooze_12hours", ("12 horas", "12 hours"));
+        translations.insert("reminder_snooze_1day", ("1 día", "1 day"));
+        translations.insert("reminder_snooze_3days", ("3 días", "3 days"));
+        translations.insert("reminder_snooze_7days", ("7 días", "7 days"));
+        translations.insert("reminder_snooze_custom", ("Personalizado...", "Custom..."));
         translations.insert("reminder_snooze_tomorrow", ("Mañana", "Tomorrow"));
         translations.insert(
             "reminder_notification_title",
@@ -663,11 +1135,20 @@ impl I18n {
         translations.insert("reminder_repeat_daily", ("Diariamente", "Daily"));
         translations.insert("reminder_repeat_weekly", ("Semanalmente", "Weekly"));
         translations.insert("reminder_repeat_monthly", ("Mensualmente", "Monthly"));
+        translations.insert("reminder_repeat_custom", ("Personalizado...", "Custom..."));
         translations.insert("no_reminders", ("No hay recordatorios", "No reminders"));
         translations.insert("reminders_count", ("{} pendientes", "{} pending"));
+        plurals.insert(
+            "reminders_count",
+            HashMap::from([
+                ("one", ("{} pendiente", "{} pending")),
+                ("other", ("{} pendientes", "{} pending")),
+            ]),
+        );
         translations.insert("reminder_overdue", ("Vencido", "Overdue"));
         translations.insert("reminder_today", ("Hoy", "Today"));
         translations.insert("reminder_tomorrow", ("Mañana", "Tomorrow"));
+        translations.insert("reminder_yesterday", ("Ayer", "Yesterday"));
         translations.insert(
             "reminder_created",
             ("Recordatorio creado", "Reminder created"),
@@ -722,8 +1203,8 @@ impl I18n {
         translations.insert(
             "mcp_snooze_reminder_desc",
             (
-                "Pospone un recordatorio por un tiempo específico",
-                "Snooze a reminder for a specific time",
+                "Pospone un recordatorio por un preset (5min, 30min, 1h, 6h, 12h, 1d, 3d, 7d) o por minutos personalizados",
+                "Snoozes a reminder by a preset (5min, 30min, 1h, 6h, 12h, 1d, 3d, 7d) or by a custom number of minutes",
             ),
         );
         translations.insert(
@@ -822,6 +1303,13 @@ impl I18n {
             "found_relevant_notes",
             ("Encontré {} notas relevantes:", "Found {} relevant notes:"),
         );
+        plurals.insert(
+            "found_relevant_notes",
+            HashMap::from([
+                ("one", ("Encontré {} nota relevante:", "Found {} relevant note:")),
+                ("other", ("Encontré {} notas relevantes:", "Found {} relevant notes:")),
+            ]),
+        );
         translations.insert(
             "semantic_results",
             (
@@ -1222,6 +1710,56 @@ impl I18n {
             ("Configurar Columnas", "Configure Columns"),
         );
         translations.insert("base_data_source", ("Origen de datos", "Data source mode"));
+        translations.insert("base_theme", ("Tema", "Theme"));
+        translations.insert("base_theme_light", ("☀️ Claro", "☀️ Light"));
+        translations.insert("base_theme_dark", ("🌙 Oscuro", "🌙 Dark"));
+        translations.insert("base_theme_system", ("🖥️ Sistema", "🖥️ System"));
+        translations.insert("base_switch_view", ("Cambiar a vista", "Switch to view"));
+        translations.insert("base_group_by", ("Agrupar por", "Group by"));
+        translations.insert("base_group_none", ("Sin agrupar", "No grouping"));
+        translations.insert("base_search_case", ("Distinguir mayúsculas", "Case sensitive"));
+        translations.insert("base_search_word", ("Palabra completa", "Whole word"));
+        translations.insert("base_search_regex", ("Expresión regular", "Regular expression"));
+        translations.insert(
+            "base_search_invalid",
+            ("Patrón inválido", "Invalid pattern"),
+        );
+        translations.insert("base_selection", ("Selección", "Selection"));
+        translations.insert("base_select_all", ("Seleccionar todo", "Select all"));
+        translations.insert("base_deselect_all", ("Deseleccionar todo", "Deselect all"));
+        translations.insert("base_invert_selection", ("Invertir selección", "Invert selection"));
+        translations.insert("base_delete_selected", ("Eliminar seleccionadas", "Delete selected"));
+        translations.insert("base_dedup_by", ("Duplicados por", "Duplicates by"));
+        translations.insert(
+            "base_dedup_keep_newest",
+            ("Seleccionar todos menos el más reciente", "Select all but newest"),
+        );
+        translations.insert(
+            "base_dedup_keep_oldest",
+            ("Seleccionar todos menos el más antiguo", "Select all but oldest"),
+        );
+        translations.insert("base_move_to", ("Mover a…", "Move to…"));
+        translations.insert(
+            "base_move_no_destinations",
+            ("No hay otros destinos", "No other destinations"),
+        );
+        translations.insert("base_set_property", ("Fijar propiedad", "Set property"));
+        translations.insert("base_set_property_value", ("Valor", "Value"));
+        translations.insert("base_add_view", ("Nueva vista", "New view"));
+        translations.insert("base_view_new", ("Nueva vista", "New view"));
+        translations.insert("base_view_rename", ("Renombrar vista", "Rename view"));
+        translations.insert("base_view_close", ("Cerrar vista", "Close view"));
+        translations.insert(
+            "base_view_delete_confirm",
+            (
+                "¿Eliminar esta vista? No se puede deshacer.",
+                "Delete this view? This cannot be undone.",
+            ),
+        );
+        translations.insert(
+            "base_command_palette",
+            ("Buscar acción…", "Search action…"),
+        );
         translations.insert("base_formula_rows", ("Filas con fórmulas", "Formula rows"));
         translations.insert(
             "base_formula_rows_title",
@@ -1248,6 +1786,30 @@ impl I18n {
             "base_export_xlsx_error",
             ("Error al exportar", "Export error"),
         );
+        translations.insert(
+            "base_export_view",
+            ("Exportar vista", "Export view"),
+        );
+        translations.insert(
+            "base_export_view_csv",
+            ("Exportar como CSV", "Export as CSV"),
+        );
+        translations.insert(
+            "base_export_view_markdown",
+            ("Exportar como Markdown", "Export as Markdown"),
+        );
+        translations.insert(
+            "base_export_view_error",
+            ("Error al exportar la vista", "Error exporting view"),
+        );
+        translations.insert(
+            "base_export_view_mf2_entry",
+            ("Exportar como microformats2 (h-entry)", "Export as microformats2 (h-entry)"),
+        );
+        translations.insert(
+            "base_export_view_mf2_feed",
+            ("Exportar como microformats2 (h-feed)", "Export as microformats2 (h-feed)"),
+        );
         translations.insert(
             "base_show_graph",
             ("Mostrar grafo de relaciones", "Show relationships graph"),
@@ -1329,6 +1891,24 @@ impl I18n {
             "base_sort_descending",
             ("Orden descendente", "Sort descending"),
         );
+        translations.insert(
+            "base_sort_active_levels",
+            ("Niveles de ordenamiento", "Sort levels"),
+        );
+        translations.insert(
+            "base_sort_add_level",
+            ("Añadir nivel de desempate", "Add tiebreaker level"),
+        );
+        translations.insert("base_sort_move_up", ("Subir nivel", "Move level up"));
+        translations.insert("base_sort_move_down", ("Bajar nivel", "Move level down"));
+        translations.insert(
+            "base_sort_toggle_direction",
+            ("Invertir dirección", "Toggle direction"),
+        );
+        translations.insert(
+            "base_sort_remove_level",
+            ("Quitar nivel", "Remove level"),
+        );
 
         // === FILTER POPOVER ===
         translations.insert("base_add_filter_title", ("Añadir filtro", "Add Filter"));
@@ -1341,6 +1921,16 @@ impl I18n {
         );
         translations.insert("base_cancel", ("Cancelar", "Cancel"));
         translations.insert("base_apply_filter", ("Aplicar filtro", "Add Filter"));
+        translations.insert(
+            "base_filter_combine_with",
+            ("Combinar con los filtros existentes", "Combine with existing filters"),
+        );
+        translations.insert("base_filter_combine_and", ("Y (cumplir todos)", "AND (match all)"));
+        translations.insert("base_filter_combine_or", ("O (cumplir alguno)", "OR (match any)"));
+        translations.insert(
+            "base_filter_toggle_group",
+            ("Alternar entre Y/O", "Toggle AND/OR"),
+        );
 
         // Operadores de filtro
         translations.insert("filter_op_equals", ("igual a", "equals"));
@@ -1361,24 +1951,87 @@ impl I18n {
         translations.insert("filter_op_ends_with", ("termina con", "ends with"));
         translations.insert("filter_op_is_empty", ("está vacío", "is empty"));
         translations.insert("filter_op_is_not_empty", ("no está vacío", "is not empty"));
+        translations.insert("filter_op_matches", ("coincide con patrón", "matches pattern"));
 
-        Self {
-            language,
-            translations,
-        }
+        (translations, plurals)
     }
 
+impl I18n {
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+
+    /// Traduce `key` en el idioma activo, buscando en el registro unificado
+    /// [`locales`] y cayendo al inglés si la clave no existe para este
+    /// idioma (p. ej. un idioma externo sin esa clave). Una clave que no
+    /// existe en ningún idioma se devuelve tal cual.
     pub fn t(&self, key: &str) -> String {
-        self.translations
+        locales()
             .get(key)
-            .map(|(es, en)| match self.language {
-                Language::Spanish => *es,
-                Language::English => *en,
-            })
+            .and_then(|by_lang| by_lang.get(self.language.code).or_else(|| by_lang.get("en")))
+            .copied()
             .unwrap_or(key)
             .to_string()
     }
 
+    /// Traduce una clave con forma plural (p. ej. `mcp_notes_found`)
+    /// eligiendo singular/plural según `count` con [`plural_category`], y
+    /// sustituyendo `{count}`/el primer `{}` por el número. Todos los idiomas
+    /// usan esa misma regla (`one` si |count| == 1, `other` en otro caso);
+    /// las categorías `zero`/`few`/`many` no se distinguen. Si la clave no
+    /// tiene forma plural registrada cae a [`I18n::t`].
+    pub fn t_plural(&self, key: &str, count: i64) -> String {
+        let category = plural_category(count).as_key();
+
+        if self.language == Language::Spanish || self.language == Language::English {
+            return match compiled()
+                .1
+                .get(key)
+                .and_then(|forms| forms.get(category).or_else(|| forms.get("other")))
+            {
+                Some((es, en)) => {
+                    substitute_count(if self.language == Language::Spanish { es } else { en }, count)
+                }
+                None => self.t(key),
+            };
+        }
+
+        let plural_key = format!("{key}.{category}");
+        let other_key = format!("{key}.other");
+        if let Some(template) = catalogs()
+            .get(self.language.code)
+            .and_then(|c| c.get(plural_key.as_str()).or_else(|| c.get(other_key.as_str())))
+        {
+            return substitute_count(template, count);
+        }
+
+        // Sin forma plural en el paquete externo: caer al inglés compilado.
+        match compiled()
+            .1
+            .get(key)
+            .and_then(|forms| forms.get(category).or_else(|| forms.get("other")))
+        {
+            Some((_, en)) => substitute_count(en, count),
+            None => self.t(key),
+        }
+    }
+
+    /// Traduce `key` y sustituye cada `{nombre}` presente en la plantilla por
+    /// el valor correspondiente de `args`, en el orden dado.
+    pub fn t_args(&self, key: &str, args: &[(&str, String)]) -> String {
+        let mut out = self.t(key);
+        for (name, value) in args {
+            out = out.replace(&format!("{{{name}}}"), value);
+        }
+        out
+    }
+
+    /// Cambia el idioma activo sin reconstruir `I18n`: las llamadas a
+    /// [`I18n::t`]/[`I18n::t_plural`] siguientes ya leen del nuevo idioma.
+    /// `I18n` no tiene acceso a los widgets, así que el selector de idioma en
+    /// preferencias debe llamar a este método y después forzar un relayout
+    /// de la UI (repoblar labels, cabeceras de columna, etc.) para que el
+    /// cambio se vea al instante, sin pedirle al usuario que reinicie.
     pub fn set_language(&mut self, language: Language) {
         self.language = language;
     }
@@ -1387,9 +2040,58 @@ impl I18n {
         self.language
     }
 
-    /// Obtiene todas las traducciones disponibles para una clave
+    /// Dirección de escritura del idioma activo, para el atributo `dir` de
+    /// cualquier superficie de la UI que necesite espejarse en RTL.
+    pub fn t_dir(&self) -> Direction {
+        self.language.direction()
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        self.language.is_rtl()
+    }
+
+    /// Texto de tiempo relativo localizado entre `target` y `now` (p. ej.
+    /// "hace 2 días" / "in 2 days"), para las fechas de vencimiento de
+    /// recordatorios. `reminder_today`/`reminder_tomorrow`/`reminder_yesterday`
+    /// cubren los casos con nombre propio para una diferencia de 0 o ±1 día
+    /// de calendario; el resto cae a la unidad más grande con magnitud >= 1
+    /// (año > mes > día > hora > minuto) con la forma plural correcta vía
+    /// [`plural_category`].
+    pub fn format_relative(&self, target: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        let day_diff = (target.date_naive() - now.date_naive()).num_days();
+        match day_diff {
+            0 => return self.t("reminder_today"),
+            1 => return self.t("reminder_tomorrow"),
+            -1 => return self.t("reminder_yesterday"),
+            _ => {}
+        }
+
+        let total_minutes = (target - now).num_minutes();
+        let is_future = total_minutes > 0;
+        let abs_minutes = total_minutes.abs();
+
+        let (unit, amount) = if abs_minutes >= 60 * 24 * 365 {
+            (RelativeUnit::Year, abs_minutes / (60 * 24 * 365))
+        } else if abs_minutes >= 60 * 24 * 30 {
+            (RelativeUnit::Month, abs_minutes / (60 * 24 * 30))
+        } else if abs_minutes >= 60 * 24 {
+            (RelativeUnit::Day, abs_minutes / (60 * 24))
+        } else if abs_minutes >= 60 {
+            (RelativeUnit::Hour, abs_minutes / 60)
+        } else {
+            (RelativeUnit::Minute, abs_minutes.max(1))
+        };
+
+        let (past, future) = unit.templates(self.language, plural_category(amount));
+        let template = if is_future { future } else { past };
+        template.replacen("{0}", &amount.to_string(), 1)
+    }
+
+    /// Obtiene la traducción española e inglesa compiladas de una clave,
+    /// sin pasar por los paquetes de idioma externos.
     pub fn all_translations(&self, key: &str) -> Option<(String, String)> {
-        self.translations
+        compiled()
+            .0
             .get(key)
             .map(|(es, en)| (es.to_string(), en.to_string()))
     }